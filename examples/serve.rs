@@ -0,0 +1,95 @@
+use ame2020::{
+    dataset::{Dataset, Duplicates},
+    element,
+};
+use clap::Parser;
+use serde_json::{json, Value};
+use std::{error::Error, fs::File, io::BufReader, path::PathBuf};
+use tiny_http::{Header, Request, Response, Server};
+
+/// Example program for serving the atomic mass evaluation as json, like the IAEA Live Chart API
+///
+/// The endpoints are `/nuclide/26Al`, `/isotopes/Sn`, and
+/// `/qvalue?initial=26Al&products=26Mg`, where the nuclides in a list are separated by commas.
+#[derive(Parser, Debug)]
+#[command(about, long_about = None)]
+struct Cli {
+    /// File to read from.
+    file: PathBuf,
+    /// Port to listen on.
+    #[arg(long, default_value_t = 8000)]
+    port: u16,
+}
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let cli = Cli::parse();
+    let dataset = Dataset::read(BufReader::new(File::open(cli.file)?), Duplicates::Error)?;
+    let server = Server::http(("127.0.0.1", cli.port))?;
+    eprintln!("listening on http://127.0.0.1:{}", cli.port);
+
+    for request in server.incoming_requests() {
+        let (status, body) = match route(&dataset, request.url()) {
+            Ok(body) => (200, body),
+            Err((status, message)) => (status, json!({ "error": message })),
+        };
+        respond(request, status, &body)?;
+    }
+    Ok(())
+}
+
+fn respond(
+    request: Request,
+    status: u16,
+    body: &Value,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let header = Header::from_bytes("Content-Type", "application/json").expect("header is valid");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    request.respond(response)?;
+    Ok(())
+}
+
+fn route(dataset: &Dataset, url: &str) -> Result<Value, (u16, String)> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let not_found = |what: &str| (404, format!("{what} not found"));
+    match path.trim_matches('/').split('/').collect::<Vec<_>>()[..] {
+        ["nuclide", name] => {
+            let (z, n) = parse_nuclide(name)?;
+            let nuc = dataset.get(z, n).ok_or_else(|| not_found(name))?;
+            Ok(json!(nuc))
+        }
+        ["isotopes", symbol] => {
+            let z = element::z(symbol).ok_or_else(|| (400, format!("unknown element {symbol}")))?;
+            let isotopes: Vec<_> = dataset.iter().filter(|nuc| nuc.z == z).collect();
+            Ok(json!(isotopes))
+        }
+        ["qvalue"] => {
+            let mut initial = Vec::new();
+            let mut products = Vec::new();
+            for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+                let list = match key {
+                    "initial" => &mut initial,
+                    "products" => &mut products,
+                    _ => return Err((400, format!("unknown parameter {key}"))),
+                };
+                for name in value.split(',').filter(|name| !name.is_empty()) {
+                    list.push(parse_nuclide(name)?);
+                }
+            }
+            let q = dataset.q_value(&initial, &products).ok_or_else(|| {
+                (
+                    404,
+                    "a nuclide is missing, or A isn't conserved".to_string(),
+                )
+            })?;
+            Ok(json!({ "q_value": q, "unit": "keV" }))
+        }
+        _ => Err(not_found(path)),
+    }
+}
+
+// parses a name like `26Al` into Z and N
+fn parse_nuclide(name: &str) -> Result<(u32, u32), (u16, String)> {
+    element::nuclide(name).ok_or_else(|| (400, format!("invalid nuclide {name}")))
+}
@@ -0,0 +1,150 @@
+#ifndef AME2020_H
+#define AME2020_H
+
+/* Generated with cbindgen, with `cbindgen --output include/ame2020.h`. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// A nuclide, read with the `ame2020_nuclide_*` functions.
+//
+// This is never constructed. A pointer to it is a pointer to a [`Nuclide`] in a table.
+typedef struct Ame2020Nuclide Ame2020Nuclide;
+
+// A table of nuclides, indexed by Z and N.
+typedef struct Ame2020Table Ame2020Table;
+
+// A value with a mean and uncertainty.
+typedef struct Ame2020Value {
+  // The mean
+  double mean;
+  // The uncertainty
+  double uncertainty;
+  // Whether the value is estimated, rather than based on experimental data
+  bool is_estimated;
+} Ame2020Value;
+
+// Reads and parses the file at `path`.
+//
+// Returns `NULL` if reading or parsing fails, with the error in [`ame2020_last_error`].
+//
+// # Safety
+//
+// `path` must be a valid, nul-terminated string.
+struct Ame2020Table *ame2020_parse_file(const char *path);
+
+// Returns the message of the last error on this thread, or `NULL` if there hasn't been one.
+//
+// The string is valid until the next error on this thread.
+const char *ame2020_last_error(void);
+
+// Frees `table`, which may be `NULL`.
+//
+// # Safety
+//
+// `table` must be from [`ame2020_parse_file`], and not already freed.
+void ame2020_free(struct Ame2020Table *table);
+
+// Returns the number of nuclides in `table`.
+//
+// # Safety
+//
+// `table` must be a valid table.
+size_t ame2020_len(const struct Ame2020Table *table);
+
+// Returns the nuclide in `table` with `z` protons and `n` neutrons, or `NULL`.
+//
+// # Safety
+//
+// `table` must be a valid table.
+const struct Ame2020Nuclide *ame2020_get(const struct Ame2020Table *table, uint32_t z, uint32_t n);
+
+// Returns the nuclide at `index` in `table`, in the order they were read, or `NULL`.
+//
+// # Safety
+//
+// `table` must be a valid table.
+const struct Ame2020Nuclide *ame2020_get_index(const struct Ame2020Table *table, size_t index);
+
+// Returns the number of neutrons of `nuclide`.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+uint32_t ame2020_nuclide_n(const struct Ame2020Nuclide *nuclide);
+
+// Returns the number of protons of `nuclide`.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+uint32_t ame2020_nuclide_z(const struct Ame2020Nuclide *nuclide);
+
+// Returns the mass number of `nuclide`.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+uint32_t ame2020_nuclide_a(const struct Ame2020Nuclide *nuclide);
+
+// Returns the element symbol of `nuclide`.
+//
+// The string is static.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+const char *ame2020_nuclide_element(const struct Ame2020Nuclide *nuclide);
+
+// Returns the origin of the data of `nuclide`, or `NULL`.
+//
+// The string is static.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+const char *ame2020_nuclide_origin(const struct Ame2020Nuclide *nuclide);
+
+// Returns the mass excess of `nuclide`, in keV.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+struct Ame2020Value ame2020_nuclide_mass_excess(const struct Ame2020Nuclide *nuclide);
+
+// Writes the binding energy per nucleon of `nuclide`, in keV, to `value`, and returns whether
+// there is one.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide, and `value` must be valid for writes.
+bool ame2020_nuclide_binding_energy_per_a(const struct Ame2020Nuclide *nuclide,
+                                          struct Ame2020Value *value);
+
+// Returns the label of the decay of `nuclide`, or `NULL`.
+//
+// The string is static.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+const char *ame2020_nuclide_decay_mode(const struct Ame2020Nuclide *nuclide);
+
+// Writes the beta decay energy of `nuclide`, in keV, to `value`, and returns whether there is
+// one.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide, and `value` must be valid for writes.
+bool ame2020_nuclide_beta_decay_energy(const struct Ame2020Nuclide *nuclide,
+                                       struct Ame2020Value *value);
+
+// Returns the atomic mass of `nuclide`, in atomic mass units.
+//
+// # Safety
+//
+// `nuclide` must be a valid nuclide.
+struct Ame2020Value ame2020_nuclide_atomic_mass(const struct Ame2020Nuclide *nuclide);
+
+#endif  /* AME2020_H */
@@ -0,0 +1,97 @@
+//! R bindings for [ame2020], built into the `ame2020` R package with
+//! [extendr](https://extendr.github.io).
+//!
+//! Each nuclide is a row of a `data.frame`, and each value is three columns: the mean, the
+//! uncertainty, and whether it is estimated. Values that aren't in the table are `NA`.
+use ame2020::{dataset::Dataset, dataset::Duplicates, AmeError, DecayMode, Nuclide, Value};
+use extendr_api::{prelude::*, Error, Result};
+use std::{fs::File, io::BufReader};
+
+#[derive(IntoDataFrameRow)]
+struct Row {
+    z: i32,
+    n: i32,
+    a: i32,
+    element: String,
+    origin: Option<String>,
+    mass_excess: f64,
+    mass_excess_uncertainty: f64,
+    mass_excess_is_estimated: bool,
+    binding_energy_per_a: Option<f64>,
+    binding_energy_per_a_uncertainty: Option<f64>,
+    binding_energy_per_a_is_estimated: Option<bool>,
+    decay_mode: Option<String>,
+    beta_decay_energy: Option<f64>,
+    beta_decay_energy_uncertainty: Option<f64>,
+    beta_decay_energy_is_estimated: Option<bool>,
+    atomic_mass: f64,
+    atomic_mass_uncertainty: f64,
+    atomic_mass_is_estimated: bool,
+}
+
+impl From<&Nuclide> for Row {
+    fn from(nuc: &Nuclide) -> Self {
+        // Z and N are at most a few hundred
+        let int = |x: u32| i32::try_from(x).unwrap_or(i32::MAX);
+        let mean = |value: Option<&Value>| value.map(|value| value.mean);
+        let uncertainty = |value: Option<&Value>| value.map(|value| value.uncertainty);
+        let is_estimated = |value: Option<&Value>| value.map(|value| value.is_estimated);
+        Self {
+            z: int(nuc.z),
+            n: int(nuc.n),
+            a: int(nuc.a()),
+            element: nuc.element.to_string(),
+            origin: nuc.origin.map(|origin| origin.to_string()),
+            mass_excess: nuc.mass_excess.mean,
+            mass_excess_uncertainty: nuc.mass_excess.uncertainty,
+            mass_excess_is_estimated: nuc.mass_excess.is_estimated,
+            binding_energy_per_a: mean(nuc.binding_energy_per_a.as_ref()),
+            binding_energy_per_a_uncertainty: uncertainty(nuc.binding_energy_per_a.as_ref()),
+            binding_energy_per_a_is_estimated: is_estimated(nuc.binding_energy_per_a.as_ref()),
+            decay_mode: nuc.decay_mode.map(|mode| DecayMode::label(mode).to_string()),
+            beta_decay_energy: mean(nuc.beta_decay_energy.as_ref()),
+            beta_decay_energy_uncertainty: uncertainty(nuc.beta_decay_energy.as_ref()),
+            beta_decay_energy_is_estimated: is_estimated(nuc.beta_decay_energy.as_ref()),
+            atomic_mass: nuc.atomic_mass.mean,
+            atomic_mass_uncertainty: nuc.atomic_mass.uncertainty,
+            atomic_mass_is_estimated: nuc.atomic_mass.is_estimated,
+        }
+    }
+}
+
+fn to_r_err(err: &AmeError) -> Error {
+    Error::Other(err.to_string())
+}
+
+fn data_frame(dataset: &Dataset) -> Result<Dataframe<Row>> {
+    dataset
+        .iter()
+        .map(Row::from)
+        .collect::<Vec<_>>()
+        .into_dataframe()
+}
+
+/// Parses the text of a `mass.mas20` file into a data frame.
+/// @export
+#[extendr]
+fn parse_ame2020(text: &str) -> Result<Dataframe<Row>> {
+    let dataset =
+        Dataset::from_slice(text.as_bytes(), Duplicates::Error).map_err(|err| to_r_err(&err))?;
+    data_frame(&dataset)
+}
+
+/// Reads and parses the `mass.mas20` file at `path` into a data frame.
+/// @export
+#[extendr]
+fn read_ame2020(path: &str) -> Result<Dataframe<Row>> {
+    let file = File::open(path).map_err(|err| to_r_err(&err.into()))?;
+    let dataset =
+        Dataset::read(BufReader::new(file), Duplicates::Error).map_err(|err| to_r_err(&err))?;
+    data_frame(&dataset)
+}
+
+extendr_module! {
+    mod ame2020;
+    fn parse_ame2020;
+    fn read_ame2020;
+}
@@ -0,0 +1,256 @@
+//! Natural isotopic abundances, from the NIST [Atomic Weights and Isotopic Compositions] table.
+//!
+//! The table is read in its linearized ASCII format, with a record for each isotope, such as:
+//!
+//! ```text
+//! Atomic Number = 1
+//! Atomic Symbol = H
+//! Mass Number = 1
+//! Relative Atomic Mass = 1.00782503223(9)
+//! Isotopic Composition = 0.999885(70)
+//! Standard Atomic Weight = [1.00784,1.00811]
+//! Notes = m
+//! ```
+//!
+//! The uncertainties are in the last digits, in parentheses, and a value with no parentheses is
+//! exact. Estimated values are marked with `#`, such as `7.052750(1080#)`. The isotopic
+//! composition is the fraction of the atoms of the element, and is empty for isotopes that don't
+//! occur naturally.
+//! The standard atomic weight and the notes aren't read, and nor are lines that aren't a key and
+//! a value.
+//!
+//! [Atomic Weights and Isotopic Compositions]: https://www.nist.gov/pml/atomic-weights-and-isotopic-compositions-relative-atomic-masses
+//!
+//! With the abundances joined to the nuclides, [`atomic_weights`] computes the atomic weight of each
+//! element.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::{abundance, Iter, Nuclide};
+//! use std::{fs::File, io::BufReader};
+//!
+//! let data: Vec<Nuclide> = Iter::from_path("mass.mas20")?.collect::<Result<_, _>>()?;
+//! let isotopes = abundance::read(BufReader::new(File::open("nist.txt")?))?;
+//! for (nuc, abundance) in abundance::join(&data, &isotopes) {
+//!     if let Some(abundance) = abundance {
+//!         println!("{}-{}: {}", nuc.element, nuc.a(), abundance.mean);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::{AmeError, Nuclide, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::BufRead,
+};
+
+const ATOMIC_NUMBER: &str = "Atomic Number";
+const MASS_NUMBER: &str = "Mass Number";
+const RELATIVE_ATOMIC_MASS: &str = "Relative Atomic Mass";
+const ISOTOPIC_COMPOSITION: &str = "Isotopic Composition";
+
+/// An isotope in the NIST table.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Isotope {
+    /// Proton number
+    pub z: u32,
+    /// Mass number
+    pub a: u32,
+    /// Atomic mass in atomic mass units
+    pub relative_atomic_mass: Value,
+    /// The fraction of the atoms of the element, if it occurs naturally
+    pub isotopic_composition: Option<Value>,
+}
+
+impl Isotope {
+    /// Returns the neutron number, N = A-Z.
+    #[must_use]
+    pub fn n(&self) -> u32 {
+        self.a.saturating_sub(self.z)
+    }
+}
+
+/// Reads the isotopes from the NIST table in `reader`.
+///
+/// # Errors
+///
+/// If reading fails, or a value is missing or fails to parse, an error is returned. The error is
+/// [`AmeError::InvalidColumn`], with the key as the column, and the line the record starts on.
+pub fn read<R: BufRead>(reader: R) -> Result<Vec<Isotope>, AmeError> {
+    let mut isotopes = Vec::new();
+    // the keys and values of the current record, and the line it starts on
+    let mut record: HashMap<String, String> = HashMap::new();
+    let mut start = 1;
+    for (line, text) in (1..).zip(reader.lines()) {
+        let text = text?;
+        match text.split_once('=') {
+            Some((key, value)) => {
+                if record.is_empty() {
+                    start = line;
+                }
+                record.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            // a blank line ends the record
+            None if text.trim().is_empty() && !record.is_empty() => {
+                isotopes.push(isotope(&record, start)?);
+                record.clear();
+            }
+            // such as the HTML around the table, when it is saved from the web page
+            None => {}
+        }
+    }
+    if !record.is_empty() {
+        isotopes.push(isotope(&record, start)?);
+    }
+    Ok(isotopes)
+}
+
+fn isotope(record: &HashMap<String, String>, line: usize) -> Result<Isotope, AmeError> {
+    let invalid = |column| AmeError::InvalidColumn { line, column };
+    let field = |key: &'static str| record.get(key).map_or("", String::as_str);
+    let int = |key| field(key).parse().map_err(|_| invalid(key));
+    let value = |key| parse_value(field(key)).ok_or_else(|| invalid(key));
+    Ok(Isotope {
+        z: int(ATOMIC_NUMBER)?,
+        a: int(MASS_NUMBER)?,
+        relative_atomic_mass: value(RELATIVE_ATOMIC_MASS)?,
+        isotopic_composition: if field(ISOTOPIC_COMPOSITION).is_empty() {
+            None
+        } else {
+            Some(value(ISOTOPIC_COMPOSITION)?)
+        },
+    })
+}
+
+// parses a value like `1.00782503223(9)`, where the uncertainty is in the last digits. a `#`
+// inside or after the parentheses marks an estimated value.
+fn parse_value(s: &str) -> Option<Value> {
+    let (s, mut is_estimated) = strip_estimated(s);
+    let (mean, uncertainty) = match s.split_once('(') {
+        Some((mean, uncertainty)) => {
+            let (uncertainty, estimated) = strip_estimated(uncertainty.strip_suffix(')')?);
+            is_estimated |= estimated;
+            (mean, uncertainty)
+        }
+        None => (s, "0"),
+    };
+    let decimals = mean
+        .split_once('.')
+        .map_or(0, |(_, decimals)| decimals.len());
+    let scale = 10_f64.powi(-i32::try_from(decimals).ok()?);
+    let mean: f64 = mean.parse().ok()?;
+    let uncertainty = uncertainty.parse::<u32>().ok()?;
+    Some(Value {
+        mean,
+        uncertainty: f64::from(uncertainty) * scale,
+        is_estimated,
+    })
+}
+
+fn strip_estimated(s: &str) -> (&str, bool) {
+    s.strip_suffix('#').map_or((s, false), |s| (s, true))
+}
+
+/// Returns each of `nuclides` with its natural abundance, as a fraction, from `isotopes`.
+///
+/// The abundance is `None` if the nuclide doesn't occur naturally, or isn't in `isotopes`.
+#[must_use]
+pub fn join<'a>(
+    nuclides: &'a [Nuclide],
+    isotopes: &[Isotope],
+) -> Vec<(&'a Nuclide, Option<Value>)> {
+    let abundances: HashMap<(u32, u32), &Value> = isotopes
+        .iter()
+        .filter_map(|isotope| {
+            Some((
+                (isotope.z, isotope.n()),
+                isotope.isotopic_composition.as_ref()?,
+            ))
+        })
+        .collect();
+    nuclides
+        .iter()
+        .map(|nuc| {
+            (
+                nuc,
+                abundances.get(&(nuc.z, nuc.n)).map(|&value| value.clone()),
+            )
+        })
+        .collect()
+}
+
+/// Returns the atomic weight of each element, by Z, from nuclides and their abundances, such as
+/// from [`join`].
+///
+/// The atomic weight is the mean of the atomic masses, weighted by the abundances, which are
+/// normalized to add up to 1. The uncertainty is from the uncertainties of the masses and the
+/// abundances, which are taken to be independent. The weight is estimated if any of the masses or
+/// abundances are. Elements with no natural abundances aren't included.
+///
+/// The standard atomic weights from IUPAC also account for the variation between natural sources,
+/// so they can have larger uncertainties, or be intervals.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{abundance::atomic_weights, parse_str, Value};
+///
+/// let data = parse_str(r"1
+/// 1
+/// 0 -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014
+///    0    1    1    2 H         13135.722895    0.000015   1112.2831     0.0002  B-      *                    2 014101.777844    0.000015").unwrap();
+/// let abundance = |mean| Some(Value { mean, uncertainty: 0.0, is_estimated: false });
+/// let joined = [(&data[0], abundance(0.999_885)), (&data[1], abundance(0.000_115))];
+/// let hydrogen = &atomic_weights(&joined)[&1];
+/// assert!((hydrogen.mean - 1.007_94).abs() < 1e-5);
+/// ```
+#[must_use]
+pub fn atomic_weights(joined: &[(&Nuclide, Option<Value>)]) -> BTreeMap<u32, Value> {
+    let mut elements: BTreeMap<u32, Vec<(&Value, &Value)>> = BTreeMap::new();
+    for (nuc, abundance) in joined {
+        if let Some(abundance) = abundance {
+            elements
+                .entry(nuc.z)
+                .or_default()
+                .push((&nuc.atomic_mass, abundance));
+        }
+    }
+    elements
+        .into_iter()
+        .filter_map(|(z, isotopes)| {
+            let total: f64 = isotopes.iter().map(|(_, abundance)| abundance.mean).sum();
+            if total <= 0.0 {
+                return None;
+            }
+            let mean = isotopes
+                .iter()
+                .map(|(mass, abundance)| mass.mean * abundance.mean)
+                .sum::<f64>()
+                / total;
+            // the derivatives are x_i / total for the masses, and (m_i - mean) / total for the
+            // abundances, because of the normalization
+            let variance = isotopes
+                .iter()
+                .map(|(mass, abundance)| {
+                    (abundance.mean * mass.uncertainty).powi(2)
+                        + ((mass.mean - mean) * abundance.uncertainty).powi(2)
+                })
+                .sum::<f64>()
+                / total.powi(2);
+            let is_estimated = isotopes
+                .iter()
+                .any(|(mass, abundance)| mass.is_estimated || abundance.is_estimated);
+            Some((
+                z,
+                Value {
+                    mean,
+                    uncertainty: variance.sqrt(),
+                    is_estimated,
+                },
+            ))
+        })
+        .collect()
+}
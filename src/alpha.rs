@@ -0,0 +1,171 @@
+//! α decay chains, and estimates of α decay half-lives from Q<sub>α</sub>.
+//!
+//! [`chain`] follows the α decays of a nuclide, and [`ViolaSeaborg`] estimates their half-lives.
+//!
+//! The Viola–Seaborg formula is
+//!
+//! log<sub>10</sub>(T<sub>½</sub>/s) = (aZ + b) / √Q<sub>α</sub> + cZ + d + h,
+//!
+//! where Q<sub>α</sub> is in mega-electronvolts, Z is of the parent, and h is the hindrance of
+//! nuclides with an odd Z or N. The parameters are in [`ViolaSeaborg`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{alpha::ViolaSeaborg, Value};
+//!
+//! // 238U, which has a half-life of 4.5 billion years, 10^17.15 s
+//! let q = Value {
+//!     mean: 4_269.9,
+//!     uncertainty: 2.1,
+//!     is_estimated: false,
+//! };
+//! let log_half_life = ViolaSeaborg::SOBICZEWSKI.log_half_life(92, 146, &q).unwrap();
+//! assert!((log_half_life.mean - 17.15).abs() < 0.5);
+//! ```
+use crate::{dataset::Dataset, reaction::Reaction, DecayMode, Value};
+
+/// A decay in a chain, from [`chain`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Decay {
+    /// The (Z, N) of the nuclide that decays
+    pub parent: (u32, u32),
+    /// The (Z, N) of the nuclide it decays to
+    pub daughter: (u32, u32),
+    /// How it decays, which is [`DecayMode::Alpha`], [`DecayMode::BetaMinus`], or
+    /// [`DecayMode::ElectronCapture`]
+    pub mode: DecayMode,
+    /// The Q-value, in keV
+    pub q_value: Value,
+}
+
+/// Returns the α decays of the nuclide with `z` protons and `n` neutrons, and of its daughters,
+/// for as long as Q<sub>α</sub> is positive.
+///
+/// If `beta`, when Q<sub>α</sub> isn't positive, the chain continues with β⁻ decay or electron
+/// capture, if either has a positive Q-value. The chain stops at a nuclide that can't decay in
+/// these ways, or that isn't in the dataset.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{alpha, dataset::{Dataset, Duplicates}};
+///
+/// // 294Og and its daughters, with rounded masses
+/// let dataset = Dataset::from_slice(br"1
+/// 1
+/// 0  0    2    2    4 He         2424.91587     0.00015    7073.9156     0.0000  B-      *                    4 002603.25413     0.00016
+///   58  170  112  282 Cn       153000#        500#         7218#         2#      B-      *                  282 164252#        537#
+///   58  172  114  286 Fl       165770#        400#         7180#         1#      B-      *                  286 177961#        429#
+///   58  174  116  290 Lv       179195#        400#         7140#         1#      B-      *                  290 192374#        429#
+///   58  176  118  294 Og       193490#        500#         7099#         2#      B-      *                  294 207720#        537#", Duplicates::Error).unwrap();
+/// let chain = alpha::chain(&dataset, 118, 176, false);
+/// let daughters: Vec<_> = chain.iter().map(|decay| decay.daughter).collect();
+/// // 278Ds isn't in the dataset, so the chain stops at 282Cn
+/// assert_eq!(daughters, [(116, 174), (114, 172), (112, 170)]);
+/// assert!((chain[0].q_value.mean - 11_870.0).abs() < 1.0);
+/// ```
+#[must_use]
+pub fn chain(dataset: &Dataset, z: u32, n: u32, beta: bool) -> Vec<Decay> {
+    let modes: &[DecayMode] = if beta {
+        &[
+            DecayMode::Alpha,
+            DecayMode::BetaMinus,
+            DecayMode::ElectronCapture,
+        ]
+    } else {
+        &[DecayMode::Alpha]
+    };
+    let mut decays = Vec::new();
+    let mut parent = (z, n);
+    // each decay either lowers A, or is a β decay with a positive Q-value, which can't be undone,
+    // so the chain ends
+    while let Some(decay) = modes.iter().find_map(|&mode| {
+        let reaction = Reaction::decay(parent.0, parent.1, mode)?;
+        let q_value = dataset.reaction_q_value(&reaction)?;
+        (q_value.mean > 0.0).then(|| Decay {
+            parent,
+            daughter: reaction.products[0],
+            mode,
+            q_value,
+        })
+    }) {
+        parent = decay.daughter;
+        decays.push(decay);
+    }
+    decays
+}
+
+/// The parameters of the Viola–Seaborg formula.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ViolaSeaborg {
+    /// The coefficient of Z/√Q<sub>α</sub>
+    pub a: f64,
+    /// The coefficient of 1/√Q<sub>α</sub>
+    pub b: f64,
+    /// The coefficient of Z
+    pub c: f64,
+    /// The constant
+    pub d: f64,
+    /// The hindrance of nuclides with an odd Z and an even N
+    pub odd_z: f64,
+    /// The hindrance of nuclides with an even Z and an odd N
+    pub odd_n: f64,
+    /// The hindrance of nuclides with an odd Z and an odd N
+    pub odd_odd: f64,
+}
+
+impl ViolaSeaborg {
+    /// The parameters of Sobiczewski, Patyk, and Ćwiok, Phys. Lett. B 224, 1 (1989), for heavy
+    /// and superheavy nuclides, with the hindrances of Parkhomenko and Sobiczewski, Acta Phys.
+    /// Pol. B 36, 3095 (2005)
+    pub const SOBICZEWSKI: Self = Self {
+        a: 1.661_75,
+        b: -8.516_6,
+        c: -0.202_28,
+        d: -33.906_9,
+        odd_z: 0.772,
+        odd_n: 1.066,
+        odd_odd: 1.114,
+    };
+
+    /// Returns log<sub>10</sub> of the half-life in seconds of the nuclide with `z` protons and
+    /// `n` neutrons, from `q_alpha`, its Q<sub>α</sub> in keV.
+    ///
+    /// The uncertainty is propagated from that of `q_alpha`, to first order, and doesn't include
+    /// that of the formula, which is about a factor of 2 to 3 in the half-life. If Q<sub>α</sub>
+    /// isn't positive, `None` is returned.
+    #[must_use]
+    pub fn log_half_life(&self, z: u32, n: u32, q_alpha: &Value) -> Option<Value> {
+        if q_alpha.mean <= 0.0 {
+            return None;
+        }
+        let hindrance = match (z % 2, n % 2) {
+            (0, 0) => 0.0,
+            (1, 0) => self.odd_z,
+            (0, 1) => self.odd_n,
+            _ => self.odd_odd,
+        };
+        let z = f64::from(z);
+        let (q, uncertainty) = (q_alpha.mean / 1000.0, q_alpha.uncertainty / 1000.0);
+        let numerator = self.a * z + self.b;
+        // the derivative with respect to Q is -numerator / (2 Q^(3/2))
+        let slope = numerator / (2.0 * q * q.sqrt());
+        Some(Value {
+            mean: numerator / q.sqrt() + self.c * z + self.d + hindrance,
+            uncertainty: slope.abs() * uncertainty,
+            is_estimated: q_alpha.is_estimated,
+        })
+    }
+
+    /// Returns [`log_half_life`](Self::log_half_life) with the Q<sub>α</sub> of the nuclide with
+    /// `z` protons and `n` neutrons in `dataset`.
+    ///
+    /// If the nuclide, or its daughter, isn't in the dataset, or Q<sub>α</sub> isn't positive,
+    /// `None` is returned.
+    #[must_use]
+    pub fn estimate(&self, dataset: &Dataset, z: u32, n: u32) -> Option<Value> {
+        let q_alpha = dataset.reaction_q_value(&Reaction::decay(z, n, DecayMode::Alpha)?)?;
+        self.log_half_life(z, n, &q_alpha)
+    }
+}
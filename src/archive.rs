@@ -0,0 +1,93 @@
+//! Zero-copy serialization with [rkyv](https://rkyv.org).
+//!
+//! [`Value`](crate::Value), [`DecayMode`](crate::DecayMode), [`Nuclide`], and
+//! [`ExperimentalNuclide`](crate::ExperimentalNuclide) implement rkyv's `Archive`, `Serialize`,
+//! and `Deserialize`. The archived types, such as `ArchivedNuclide`, can be read directly from
+//! the bytes, such as from a memory-mapped file, without deserializing. The element symbols and
+//! origins are archived as strings, with [`AsString`].
+//!
+//! The bytes must be aligned for the archived types, which memory maps always are. Unlike
+//! [`snapshot`](crate::snapshot), the format is rkyv's, and may change with its version.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, Nuclide};
+//! use rkyv::{rancor::Error, Archived};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let bytes = rkyv::to_bytes::<Error>(&data).unwrap();
+//!
+//! let archived = rkyv::access::<Archived<Vec<Nuclide>>, Error>(&bytes).unwrap();
+//! assert_eq!(archived[0].element, "n");
+//! assert_eq!(archived[0].mass_excess.mean, 8071.31806);
+//!
+//! let deserialized = rkyv::deserialize::<Vec<Nuclide>, Error>(archived).unwrap();
+//! assert_eq!(deserialized, data);
+//! ```
+#[cfg(doc)]
+use crate::Nuclide;
+use arrayvec::ArrayString;
+use rkyv::{
+    rancor::{Fallible, Source},
+    ser::{Allocator, Writer},
+    string::{ArchivedString, StringResolver},
+    with::{ArchiveWith, DeserializeWith, SerializeWith},
+    Place,
+};
+use thiserror::Error;
+
+/// The error from deserializing a string that is too long for its `ArrayString`.
+#[derive(Error, Copy, Clone, Eq, PartialEq, Debug)]
+#[error("string is longer than the capacity {capacity}")]
+pub struct TooLong {
+    /// The capacity of the `ArrayString`
+    pub capacity: usize,
+}
+
+/// Archives an [`ArrayString`] as an `ArchivedString`.
+///
+/// Deserializing fails with [`TooLong`] if the string is too long for the `ArrayString`.
+#[derive(Copy, Clone, Debug)]
+pub struct AsString;
+
+impl<const CAP: usize> ArchiveWith<ArrayString<CAP>> for AsString {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &ArrayString<CAP>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedString::resolve_from_str(field, resolver, out);
+    }
+}
+
+impl<const CAP: usize, S> SerializeWith<ArrayString<CAP>, S> for AsString
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &ArrayString<CAP>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(field, serializer)
+    }
+}
+
+impl<const CAP: usize, D> DeserializeWith<ArchivedString, ArrayString<CAP>, D> for AsString
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedString,
+        _deserializer: &mut D,
+    ) -> Result<ArrayString<CAP>, D::Error> {
+        ArrayString::from(field.as_str()).map_err(|_| D::Error::new(TooLong { capacity: CAP }))
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{parser::Parser, AmeError, Float, Nuclide, ParseOptions, Warning};
+use futures_core::{stream::FusedStream, Stream};
+use std::{
+    marker::PhantomData,
+    ops::ControlFlow,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Split};
+
+/// A stream that reads AME2020 data asynchronously.
+///
+/// This yields the same items as [`Iter`][crate::Iter], but reads from a
+/// [`tokio::io::AsyncBufRead`].
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use ame2020::AsyncIter;
+/// use std::{future::poll_fn, pin::Pin};
+/// use futures_core::Stream;
+///
+/// let data: &[u8] = br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+/// let mut iter = AsyncIter::new(data);
+/// let next = poll_fn(|cx| Pin::new(&mut iter).poll_next(cx)).await;
+/// assert!(next.is_some());
+/// # }
+/// ```
+///
+/// The values are `f64`, unless another [`Float`] is chosen with [`with_float`][Self::with_float].
+///
+/// # Errors
+///
+/// If a line fails to parse, the stream will yield `Some(Err)`, and polling
+/// again continues with the following line.
+///
+/// If there is any other reading error, the stream will yield `Some(Err)`, and the stream is
+/// finished. Once the stream has yielded `None`, it will always yield `None`.
+pub struct AsyncIter<R: AsyncBufRead + Unpin, F = f64> {
+    lines: Split<R>,
+    parser: Parser,
+    is_finished: bool,
+    // a function pointer, so the stream is `Send`, `Sync`, and `Unpin` whatever `F` is
+    float: PhantomData<fn() -> F>,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncIter<R> {
+    /// Creates a new `AsyncIter` from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    /// Creates a new `AsyncIter` from `reader`, which parses according to `options`.
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        Self::with_float(reader, options)
+    }
+}
+
+impl<R: AsyncBufRead + Unpin, F: Float> AsyncIter<R, F> {
+    /// Creates a new `AsyncIter` from `reader`, which parses according to `options`, with the
+    /// values as `F`.
+    pub fn with_float(reader: R, options: ParseOptions) -> Self {
+        Self {
+            lines: reader.split(b'\n'),
+            parser: Parser::new(options),
+            is_finished: false,
+            float: PhantomData,
+        }
+    }
+
+    /// Skips lines that fail to parse, instead of returning errors for them.
+    ///
+    /// The errors for the skipped lines are available from [`skipped`][Self::skipped]. Other
+    /// reading errors are still returned.
+    #[must_use]
+    pub fn lenient(mut self) -> Self {
+        self.parser.set_lenient(true);
+        self
+    }
+
+    /// Returns the errors for the lines that have been skipped so far, in order.
+    ///
+    /// Lines are only skipped if [`lenient`][Self::lenient] was used.
+    pub fn skipped(&self) -> &[AmeError] {
+        self.parser.skipped()
+    }
+
+    /// Returns the warnings for the lines that have been read so far, in order.
+    #[must_use]
+    pub fn warnings(&self) -> &[Warning] {
+        self.parser.warnings()
+    }
+
+    /// Returns the number of the most recent line read, starting from 1.
+    ///
+    /// After an item is returned, this is the line it was on.
+    #[must_use]
+    pub fn line_number(&self) -> usize {
+        self.parser.line_number()
+    }
+}
+
+impl<R: AsyncBufRead + Unpin, F: Float> Stream for AsyncIter<R, F> {
+    type Item = Result<Nuclide<F>, AmeError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.is_finished || self.parser.is_stopped() {
+            return Poll::Ready(None);
+        }
+        loop {
+            match ready!(Pin::new(&mut self.lines).poll_next_segment(cx)) {
+                Ok(Some(line)) => match self
+                    .parser
+                    .parse_line(line.strip_suffix(b"\r").unwrap_or(&line))
+                {
+                    ControlFlow::Continue(()) => {}
+                    ControlFlow::Break(res) => return Poll::Ready(Some(res)),
+                },
+                Ok(None) => {
+                    self.is_finished = true;
+                    return Poll::Ready(None);
+                }
+                Err(e) => {
+                    self.is_finished = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+            }
+        }
+    }
+}
+
+impl<R: AsyncBufRead + Unpin, F: Float> FusedStream for AsyncIter<R, F> {
+    fn is_terminated(&self) -> bool {
+        self.is_finished || self.parser.is_stopped()
+    }
+}
@@ -0,0 +1,216 @@
+use ame2020::{dataset::Dataset, element, reaction::Reaction, DecayMode, Value};
+use clap::Parser;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::{error::Error, path::PathBuf};
+
+/// Browses the chart of nuclides in the terminal
+///
+/// The arrow keys move across the chart, with N increasing to the right and Z increasing upwards,
+/// and the panel shows the selected nuclide. `/` searches for a nuclide, such as `132Sn`, or an
+/// element, such as `Sn`, and `q` quits. Estimated nuclides are gray.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// File to read from, or `-` for the standard input. By default, the `file` in the config.
+    file: Option<PathBuf>,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let dataset = crate::read(args.file.as_deref())?;
+    let (z, n) = dataset.iter().next().map_or((0, 0), |nuc| (nuc.z, nuc.n));
+    let mut app = App {
+        dataset,
+        z,
+        n,
+        search: None,
+        message: None,
+    };
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+struct App {
+    dataset: Dataset,
+    // the selected nuclide, which may not be in the dataset
+    z: u32,
+    n: u32,
+    // the text that is being searched for, after `/`
+    search: Option<String>,
+    // the result of the last search, if it failed
+    message: Option<String>,
+}
+
+impl App {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match (&mut self.search, key.code) {
+                (Some(search), KeyCode::Char(c)) => search.push(c),
+                (Some(search), KeyCode::Backspace) => {
+                    search.pop();
+                }
+                (Some(_), KeyCode::Enter) => {
+                    let search = self.search.take().unwrap_or_default();
+                    self.find(search.trim());
+                }
+                (Some(_), KeyCode::Esc) => self.search = None,
+                (None, KeyCode::Char('q') | KeyCode::Esc) => return Ok(()),
+                (None, KeyCode::Char('/')) => {
+                    self.search = Some(String::new());
+                    self.message = None;
+                }
+                (None, KeyCode::Left) => self.n = self.n.saturating_sub(1),
+                (None, KeyCode::Right) => self.n += 1,
+                (None, KeyCode::Down) => self.z = self.z.saturating_sub(1),
+                (None, KeyCode::Up) => self.z += 1,
+                _ => {}
+            }
+        }
+    }
+
+    // selects the nuclide named `name`, or the isotope of the element named `name` with the
+    // closest N
+    fn find(&mut self, name: &str) {
+        if let Some((z, n)) = element::nuclide(name) {
+            (self.z, self.n) = (z, n);
+            return;
+        }
+        let closest = element::z(name).and_then(|z| {
+            self.dataset
+                .iter()
+                .filter(|nuc| nuc.z == z)
+                .min_by_key(|nuc| nuc.n.abs_diff(self.n))
+        });
+        match closest {
+            Some(nuc) => (self.z, self.n) = (nuc.z, nuc.n),
+            None => self.message = Some(format!("{name} not found")),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [chart, panel] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(48)]).areas(frame.area());
+        self.draw_chart(frame, chart);
+        self.draw_panel(frame, panel);
+    }
+
+    fn draw_chart(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" chart of nuclides ");
+        let inner = block.inner(area);
+        // each nuclide is two characters wide, and the selection is in the middle
+        let columns = u32::from(inner.width / 2);
+        let rows = u32::from(inner.height);
+        let n_start = self.n.saturating_sub(columns / 2);
+        let z_start = self.z.saturating_sub(rows / 2);
+        let lines: Vec<Line> = (0..rows)
+            .rev()
+            .map(|row| {
+                let z = z_start + row;
+                let spans: Vec<Span> = (0..columns)
+                    .map(|column| {
+                        let n = n_start + column;
+                        let mut style = match self.dataset.get(z, n) {
+                            Some(nuc) if nuc.is_estimated() => Style::new().bg(Color::DarkGray),
+                            Some(_) => Style::new().bg(Color::Green),
+                            None => Style::new(),
+                        };
+                        if (z, n) == (self.z, self.n) {
+                            style = style.bg(Color::Yellow);
+                        }
+                        Span::styled("  ", style)
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_panel(&self, frame: &mut Frame, area: Rect) {
+        let (z, n) = (self.z, self.n);
+        let mut lines = Vec::new();
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{name}: "),
+                    Style::new().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value),
+            ])
+        };
+        let title = match element::symbol(z) {
+            Some(symbol) => format!(" {}{symbol} ", z + n),
+            None => format!(" Z = {z}, N = {n} "),
+        };
+        lines.push(field("Z, N, A", format!("{z}, {n}, {}", z + n)));
+        match self.dataset.get(z, n) {
+            Some(nuc) => {
+                let value = |value: Option<&Value>, unit: &str| {
+                    value.map_or_else(
+                        || "-".to_string(),
+                        |value| format!("{} {unit}", crate::value(Some(value))),
+                    )
+                };
+                lines.push(field(
+                    "origin",
+                    nuc.origin.as_deref().unwrap_or("-").to_string(),
+                ));
+                lines.push(field("mass excess", value(Some(&nuc.mass_excess), "keV")));
+                lines.push(field(
+                    "binding energy per A",
+                    value(nuc.binding_energy_per_a.as_ref(), "keV"),
+                ));
+                let decay = nuc.decay_mode.map_or("", DecayMode::label);
+                lines.push(field(
+                    &format!("beta decay energy {decay}"),
+                    value(nuc.beta_decay_energy.as_ref(), "keV"),
+                ));
+                lines.push(field("atomic mass", value(Some(&nuc.atomic_mass), "u")));
+                lines.push(Line::raw(""));
+                let derived = |value: Option<Value>| {
+                    value.map_or_else(
+                        || "-".to_string(),
+                        |value| format!("{} keV", crate::rounded(&value)),
+                    )
+                };
+                for (name, protons, neutrons) in
+                    [("Sn", 0, 1), ("Sp", 1, 0), ("S2n", 0, 2), ("S2p", 2, 0)]
+                {
+                    let energy = self.dataset.separation_energy(z, n, protons, neutrons);
+                    lines.push(field(name, derived(energy)));
+                }
+                let alpha = Reaction::decay(z, n, DecayMode::Alpha)
+                    .and_then(|decay| self.dataset.reaction_q_value(&decay));
+                lines.push(field("Qα", derived(alpha)));
+            }
+            None => lines.push(Line::raw("not in the table")),
+        }
+        lines.push(Line::raw(""));
+        if let Some(search) = &self.search {
+            lines.push(field("search", format!("{search}_")));
+        } else if let Some(message) = &self.message {
+            lines.push(Line::styled(message.clone(), Style::new().fg(Color::Red)));
+        } else {
+            lines.push(Line::styled(
+                "arrows: move, /: search, q: quit",
+                Style::new().fg(Color::DarkGray),
+            ));
+        }
+        let block = Block::bordered().title(title);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
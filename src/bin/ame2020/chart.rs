@@ -0,0 +1,75 @@
+use ame2020::{
+    filter::{Filter, Quantity},
+    svg::{self, ColorScale, SvgOptions},
+    Nuclide,
+};
+use clap::{Parser, ValueEnum};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+/// Draws the chart of nuclides, as an SVG image
+///
+/// The nuclides are colored by an expression, with the names of `filter`, such as `s2n` or
+/// `mass_excess_uncertainty`. Nuclides without a value are gray.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// File to read from, or `-` for the standard input. By default, the `file` in the config.
+    file: Option<PathBuf>,
+    /// Expression to color the nuclides by.
+    #[arg(long, default_value = "mass_excess")]
+    color: Quantity,
+    /// Only draw the nuclides that match this expression, such as `z <= 50`.
+    #[arg(long)]
+    filter: Option<Filter>,
+    /// File to write to, instead of stdout.
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+    /// Size of each nuclide, in pixels.
+    #[arg(long, default_value_t = 10)]
+    cell_size: u32,
+    /// Colors, from the smallest value to the largest.
+    #[arg(long, value_enum, default_value_t = Scale::Viridis)]
+    scale: Scale,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+enum Scale {
+    Viridis,
+    Grayscale,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let dataset = crate::read(args.file.as_deref())?;
+    let nuclides: Vec<Nuclide> = dataset
+        .iter()
+        .filter(|nuc| {
+            args.filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(&dataset, nuc))
+        })
+        .cloned()
+        .collect();
+    let options = SvgOptions::new()
+        .cell_size(args.cell_size)
+        .color_scale(match args.scale {
+            Scale::Viridis => ColorScale::Viridis,
+            Scale::Grayscale => ColorScale::Grayscale,
+        });
+    let writer: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    let mut writer = BufWriter::new(writer);
+    svg::write(
+        &mut writer,
+        &nuclides,
+        |nuc| args.color.eval(&dataset, nuc),
+        &options,
+    )?;
+    writer.flush()?;
+    Ok(())
+}
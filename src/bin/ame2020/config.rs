@@ -0,0 +1,61 @@
+use crate::convert::{EnergyUnit, Format, MassUnit};
+use serde::Deserialize;
+use std::{env, error::Error, fs, io, path::PathBuf, sync::OnceLock};
+
+/// The defaults from the config file, such as:
+///
+/// ```toml
+/// file = "/home/me/data/mass.mas20"
+/// format = "csv"
+/// energy = "MeV"
+/// mass = "micro-u"
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The file to read from, when none is given
+    pub file: Option<PathBuf>,
+    /// The format to write
+    pub format: Option<Format>,
+    /// The unit of the energies
+    pub energy: Option<EnergyUnit>,
+    /// The unit of the atomic mass
+    pub mass: Option<MassUnit>,
+}
+
+// the config, or the error from reading it, as a string so that it can be shared
+static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+
+// the config file, which is `$AME2020_CONFIG`, or `ame2020/config.toml` in `$XDG_CONFIG_HOME` or
+// `~/.config`
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AME2020_CONFIG") {
+        return Some(path.into());
+    }
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join("ame2020").join("config.toml"))
+}
+
+// reads the config file, if there is one
+fn load() -> Result<Config, Box<dyn Error>> {
+    let config = match path().map(fs::read_to_string) {
+        Some(Ok(text)) => toml::from_str(&text).map_err(|e| {
+            let path = path().unwrap_or_default();
+            format!("invalid config {}: {e}", path.display())
+        })?,
+        Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => Config::default(),
+    };
+    Ok(config)
+}
+
+// returns the config, which is read the first time it is needed, so that a subcommand that doesn't
+// use it, such as `completions`, works even if it is invalid
+pub fn get() -> Result<&'static Config, Box<dyn Error>> {
+    CONFIG
+        .get_or_init(|| load().map_err(|e| e.to_string()))
+        .as_ref()
+        .map_err(|e| e.clone().into())
+}
@@ -0,0 +1,429 @@
+use ame2020::{Nuclide, Value, ValueGetter, VALUE_COLUMNS};
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt32Array};
+use arrow_schema::{DataType, Field as ArrowField, Schema};
+use clap::{Parser, ValueEnum};
+use parquet::arrow::ArrowWriter;
+use rusqlite::{
+    types::{ToSqlOutput, ValueRef},
+    Connection, ToSql,
+};
+use serde::Deserialize;
+use serde_json::Map;
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Converts the data to other formats
+///
+/// In csv, parquet, and sqlite, each value is split into three columns, for the mean, the
+/// uncertainty, and whether the value is estimated, such as `mass_excess`,
+/// `mass_excess_uncertainty`, and `mass_excess_is_estimated`. In sqlite, the table is `nuclides`.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// File to read from, or `-` for the standard input. By default, the `file` in the config.
+    file: Option<PathBuf>,
+    #[command(flatten)]
+    output: Export,
+}
+
+/// The options for writing nuclides, which are shared by the subcommands that write them
+#[derive(clap::Args, Debug)]
+pub struct Export {
+    /// Format to write. By default, the `format` in the config, or json.
+    #[arg(long, value_enum)]
+    to: Option<Format>,
+    /// File to write to, instead of stdout. sqlite needs a file.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Fields to write, separated by commas, such as `z,n,mass_excess`. By default, all of them
+    /// are written, except `a` in JSON.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    columns: Vec<Column>,
+    /// Unit of the mass excess, binding energy per A, and beta decay energy. By default, the
+    /// `energy` in the config, or keV.
+    #[arg(long, value_enum)]
+    energy: Option<EnergyUnit>,
+    /// Unit of the atomic mass. By default, the `mass` in the config, or u.
+    #[arg(long, value_enum)]
+    mass: Option<MassUnit>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Json,
+    Jsonl,
+    Csv,
+    Parquet,
+    Sqlite,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+#[value(rename_all = "snake_case")]
+enum Column {
+    Z,
+    N,
+    A,
+    Element,
+    Origin,
+    DecayMode,
+    MassExcess,
+    BindingEnergyPerA,
+    BetaDecayEnergy,
+    AtomicMass,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum, Deserialize)]
+pub enum EnergyUnit {
+    #[value(name = "eV")]
+    #[serde(rename = "eV")]
+    EV,
+    #[value(name = "keV")]
+    #[serde(rename = "keV")]
+    KeV,
+    #[value(name = "MeV")]
+    #[serde(rename = "MeV")]
+    MeV,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MassUnit {
+    /// atomic mass units
+    U,
+    /// micro atomic mass units, as in the table
+    MicroU,
+}
+
+// a column of the tabular formats, which is part of a column of the options
+#[derive(Copy, Clone)]
+enum Cells {
+    UInt(&'static str, fn(&Nuclide) -> u32),
+    Text(&'static str, fn(&Nuclide) -> Option<&str>),
+    Mean(&'static str, ValueGetter),
+    Uncertainty(&'static str, ValueGetter),
+    IsEstimated(&'static str, ValueGetter),
+}
+
+enum Cell<'a> {
+    UInt(u32),
+    Float(Option<f64>),
+    Bool(Option<bool>),
+    Text(Option<&'a str>),
+}
+
+impl Column {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Z => "z",
+            Self::N => "n",
+            Self::A => "a",
+            Self::Element => "element",
+            Self::Origin => "origin",
+            Self::DecayMode => "decay_mode",
+            Self::MassExcess => "mass_excess",
+            Self::BindingEnergyPerA => "binding_energy_per_a",
+            Self::BetaDecayEnergy => "beta_decay_energy",
+            Self::AtomicMass => "atomic_mass",
+        }
+    }
+
+    // whether the field is optional
+    fn is_nullable(self) -> bool {
+        matches!(
+            self,
+            Self::Origin | Self::DecayMode | Self::BindingEnergyPerA | Self::BetaDecayEnergy
+        )
+    }
+
+    fn cells(self) -> Vec<Cells> {
+        let name = self.name();
+        let value = |f: ValueGetter| {
+            vec![
+                Cells::Mean(name, f),
+                Cells::Uncertainty(name, f),
+                Cells::IsEstimated(name, f),
+            ]
+        };
+        match self {
+            Self::Z => vec![Cells::UInt(name, |nuc| nuc.z)],
+            Self::N => vec![Cells::UInt(name, |nuc| nuc.n)],
+            Self::A => vec![Cells::UInt(name, Nuclide::a)],
+            Self::Element => vec![Cells::Text(name, |nuc| Some(&nuc.element))],
+            Self::Origin => vec![Cells::Text(name, |nuc| nuc.origin.as_deref())],
+            Self::DecayMode => vec![Cells::Text(name, |nuc| {
+                nuc.decay_mode.map(ame2020::DecayMode::label)
+            })],
+            // in the same order as the values
+            Self::MassExcess => value(VALUE_COLUMNS[0].1),
+            Self::BindingEnergyPerA => value(VALUE_COLUMNS[1].1),
+            Self::BetaDecayEnergy => value(VALUE_COLUMNS[2].1),
+            Self::AtomicMass => value(VALUE_COLUMNS[3].1),
+        }
+    }
+}
+
+impl Cells {
+    fn name(self) -> String {
+        match self {
+            Self::UInt(name, _) | Self::Text(name, _) | Self::Mean(name, _) => name.to_string(),
+            Self::Uncertainty(name, _) => format!("{name}_uncertainty"),
+            Self::IsEstimated(name, _) => format!("{name}_is_estimated"),
+        }
+    }
+
+    fn cell(self, nuc: &Nuclide) -> Cell<'_> {
+        match self {
+            Self::UInt(_, f) => Cell::UInt(f(nuc)),
+            Self::Text(_, f) => Cell::Text(f(nuc)),
+            Self::Mean(_, f) => Cell::Float(f(nuc).map(|value| value.mean)),
+            Self::Uncertainty(_, f) => Cell::Float(f(nuc).map(|value| value.uncertainty)),
+            Self::IsEstimated(_, f) => Cell::Bool(f(nuc).map(|value| value.is_estimated)),
+        }
+    }
+
+    fn sql_type(self) -> &'static str {
+        match self {
+            Self::UInt(..) | Self::IsEstimated(..) => "INTEGER",
+            Self::Text(..) => "TEXT",
+            Self::Mean(..) | Self::Uncertainty(..) => "REAL",
+        }
+    }
+
+    fn array(self, nuclides: &[Nuclide]) -> ArrayRef {
+        match self {
+            Self::UInt(_, f) => Arc::new(nuclides.iter().map(f).collect::<UInt32Array>()),
+            Self::Text(_, f) => Arc::new(nuclides.iter().map(f).collect::<StringArray>()),
+            Self::Mean(_, f) => Arc::new(
+                nuclides
+                    .iter()
+                    .map(|nuc| f(nuc).map(|value| value.mean))
+                    .collect::<Float64Array>(),
+            ),
+            Self::Uncertainty(_, f) => Arc::new(
+                nuclides
+                    .iter()
+                    .map(|nuc| f(nuc).map(|value| value.uncertainty))
+                    .collect::<Float64Array>(),
+            ),
+            Self::IsEstimated(_, f) => Arc::new(
+                nuclides
+                    .iter()
+                    .map(|nuc| f(nuc).map(|value| value.is_estimated))
+                    .collect::<BooleanArray>(),
+            ),
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::UInt(..) => DataType::UInt32,
+            Self::Text(..) => DataType::Utf8,
+            Self::Mean(..) | Self::Uncertainty(..) => DataType::Float64,
+            Self::IsEstimated(..) => DataType::Boolean,
+        }
+    }
+}
+
+impl ToSql for Cell<'_> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(match *self {
+            Cell::UInt(x) => ValueRef::Integer(x.into()),
+            Cell::Float(Some(x)) => ValueRef::Real(x),
+            Cell::Bool(Some(x)) => ValueRef::Integer(x.into()),
+            Cell::Text(Some(s)) => ValueRef::Text(s.as_bytes()),
+            Cell::Float(None) | Cell::Bool(None) | Cell::Text(None) => ValueRef::Null,
+        }))
+    }
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    args.output.write(crate::read(args.file.as_deref())?.iter())
+}
+
+impl Export {
+    /// Writes `nuclides`, in the units and with the columns of the options.
+    pub fn write<'a>(
+        &self,
+        nuclides: impl IntoIterator<Item = &'a Nuclide>,
+    ) -> Result<(), Box<dyn Error>> {
+        let config = crate::config::get()?;
+        let to = self.to.or(config.format).unwrap_or(Format::Json);
+        let energy = match self.energy.or(config.energy).unwrap_or(EnergyUnit::KeV) {
+            EnergyUnit::EV => 1e3,
+            EnergyUnit::KeV => 1.0,
+            EnergyUnit::MeV => 1e-3,
+        };
+        let mass = match self.mass.or(config.mass).unwrap_or(MassUnit::U) {
+            MassUnit::U => 1.0,
+            MassUnit::MicroU => 1e6,
+        };
+        let nuclides: Vec<Nuclide> = nuclides
+            .into_iter()
+            .map(|nuc| Nuclide {
+                mass_excess: scale(&nuc.mass_excess, energy),
+                binding_energy_per_a: nuc.binding_energy_per_a.as_ref().map(|x| scale(x, energy)),
+                beta_decay_energy: nuc.beta_decay_energy.as_ref().map(|x| scale(x, energy)),
+                atomic_mass: scale(&nuc.atomic_mass, mass),
+                ..nuc.clone()
+            })
+            .collect();
+
+        let columns = if self.columns.is_empty() {
+            Column::value_variants().to_vec()
+        } else {
+            self.columns.clone()
+        };
+
+        if to == Format::Sqlite {
+            let path = self.output.as_ref().ok_or("sqlite needs --output")?;
+            return write_sqlite(&mut Connection::open(path)?, &columns, &nuclides);
+        }
+        let writer: Box<dyn Write + Send> = match &self.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout()),
+        };
+        let mut writer = BufWriter::new(writer);
+        match to {
+            Format::Json => {
+                let objects = json(&self.columns, &nuclides)?;
+                serde_json::to_writer_pretty(&mut writer, &objects)?;
+                writeln!(writer)?;
+            }
+            Format::Jsonl => {
+                for object in json(&self.columns, &nuclides)? {
+                    serde_json::to_writer(&mut writer, &object)?;
+                    writeln!(writer)?;
+                }
+            }
+            Format::Csv => write_csv(&mut writer, &columns, &nuclides)?,
+            Format::Parquet => write_parquet(&mut writer, &columns, &nuclides)?,
+            Format::Sqlite => unreachable!("SQLite is written to a connection"),
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn scale(value: &Value, factor: f64) -> Value {
+    Value {
+        mean: crate::round(value.mean * factor),
+        uncertainty: crate::round(value.uncertainty * factor),
+        is_estimated: value.is_estimated,
+    }
+}
+
+// the nuclides as serialized with serde, with only the fields in `columns`, if there are any
+fn json(columns: &[Column], nuclides: &[Nuclide]) -> serde_json::Result<Vec<serde_json::Value>> {
+    nuclides
+        .iter()
+        .map(|nuc| {
+            let value = serde_json::to_value(nuc)?;
+            if columns.is_empty() {
+                return Ok(value);
+            }
+            let mut object = Map::new();
+            for &column in columns {
+                let field = match column {
+                    Column::A => Some(nuc.a().into()),
+                    column => value.get(column.name()).cloned(),
+                };
+                // like serde, a missing origin or decay mode is left out
+                if let Some(field) = field {
+                    object.insert(column.name().to_string(), field);
+                }
+            }
+            Ok(object.into())
+        })
+        .collect()
+}
+
+fn write_csv<W: Write>(writer: &mut W, columns: &[Column], nuclides: &[Nuclide]) -> io::Result<()> {
+    let cells: Vec<Cells> = columns.iter().flat_map(|column| column.cells()).collect();
+    let header: Vec<String> = cells.iter().map(|cells| cells.name()).collect();
+    writeln!(writer, "{}", header.join(","))?;
+    for nuc in nuclides {
+        let row: Vec<String> = cells
+            .iter()
+            .map(|cells| match cells.cell(nuc) {
+                Cell::UInt(x) => x.to_string(),
+                Cell::Float(x) => x.map(|x| x.to_string()).unwrap_or_default(),
+                Cell::Bool(x) => x.map(|x| x.to_string()).unwrap_or_default(),
+                Cell::Text(s) => s.map(quote).unwrap_or_default(),
+            })
+            .collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+// quotes a CSV field, if it needs it
+fn quote(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_parquet<W: Write + Send>(
+    writer: W,
+    columns: &[Column],
+    nuclides: &[Nuclide],
+) -> Result<(), Box<dyn Error>> {
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for &column in columns {
+        for cells in column.cells() {
+            fields.push(ArrowField::new(
+                cells.name(),
+                cells.data_type(),
+                column.is_nullable(),
+            ));
+            arrays.push(cells.array(nuclides));
+        }
+    }
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?;
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+fn write_sqlite(
+    connection: &mut Connection,
+    columns: &[Column],
+    nuclides: &[Nuclide],
+) -> Result<(), Box<dyn Error>> {
+    let cells: Vec<Cells> = columns.iter().flat_map(|column| column.cells()).collect();
+    let definitions: Vec<String> = cells
+        .iter()
+        .map(|cells| format!("{} {}", cells.name(), cells.sql_type()))
+        .collect();
+    let names: Vec<String> = cells.iter().map(|cells| cells.name()).collect();
+    let placeholders = vec!["?"; cells.len()].join(", ");
+
+    let transaction = connection.transaction()?;
+    // like the other formats, an existing table is overwritten
+    transaction.execute("DROP TABLE IF EXISTS nuclides", [])?;
+    transaction.execute(
+        &format!("CREATE TABLE nuclides ({})", definitions.join(", ")),
+        [],
+    )?;
+    {
+        let mut insert = transaction.prepare(&format!(
+            "INSERT INTO nuclides ({}) VALUES ({placeholders})",
+            names.join(", ")
+        ))?;
+        for nuc in nuclides {
+            let row: Vec<Cell> = cells.iter().map(|cells| cells.cell(nuc)).collect();
+            insert.execute(rusqlite::params_from_iter(row))?;
+        }
+    }
+    transaction.commit()?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use ame2020::{
+    diff::{self, Change, Delta},
+    Field, Nuclide,
+};
+use clap::Parser;
+use std::{error::Error, path::PathBuf};
+
+/// Compares two editions, such as AME2016 and AME2020
+///
+/// The nuclides that were added and removed are listed, and then the shifts of the mass excesses,
+/// in keV and in the uncertainties of both editions added in quadrature, from the largest to the
+/// smallest in σ. Estimated values are marked with `#`.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// File with the old edition, or `-` for the standard input.
+    old: PathBuf,
+    /// File with the new edition, or `-` for the standard input. By default, the `file` in the
+    /// config.
+    new: Option<PathBuf>,
+    /// Hide the shifts that are smaller than this many σ.
+    #[arg(long, default_value_t = 0.0)]
+    min_sigma: f64,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let old = crate::read(Some(&args.old))?;
+    let new = crate::read(args.new.as_deref())?;
+    let diff = diff::diff(&old, &new);
+    let mut shifts: Vec<(&Change, &Delta)> = diff
+        .changed
+        .iter()
+        .filter_map(|change| Some((change, change.delta(Field::MassExcess)?)))
+        .filter(|(_, delta)| delta.sigma.abs() >= args.min_sigma)
+        .collect();
+    shifts.sort_by(|(_, a), (_, b)| b.sigma.abs().total_cmp(&a.sigma.abs()));
+
+    println!("added: {}", diff.added.len());
+    for nuc in diff.added {
+        println!("  {}", name(nuc));
+    }
+    println!("removed: {}", diff.removed.len());
+    for nuc in diff.removed {
+        println!("  {}", name(nuc));
+    }
+    println!("shifted mass excess: {}", shifts.len());
+    for (change, delta) in shifts {
+        println!(
+            "  {}: {} → {} keV, shifted by {} keV, {:+.1}σ",
+            name(change.new),
+            crate::value(Some(&change.old.mass_excess)),
+            crate::value(Some(&change.new.mass_excess)),
+            crate::rounded(&delta.difference),
+            delta.sigma
+        );
+    }
+    Ok(())
+}
+
+// the name of a nuclide, such as `26Al`
+fn name(nuc: &Nuclide) -> String {
+    format!("{}{}", nuc.a(), nuc.element)
+}
@@ -0,0 +1,26 @@
+use crate::convert::Export;
+use ame2020::filter::Filter;
+use clap::Parser;
+use std::{error::Error, path::PathBuf};
+
+/// Writes the nuclides that match an expression, such as `z >= 82 && !estimated && s2n < 2000`
+///
+/// The names are `z`, `n`, `a`, `mass_excess`, `binding_energy_per_a`, `beta_decay_energy`, and
+/// `atomic_mass`, and their uncertainties, such as `mass_excess_uncertainty`, in keV and u. The
+/// derived quantities are the separation energies `sn`, `sp`, `s2n`, and `s2p`, and the α decay
+/// Q-value `qa`, in keV. `estimated` is whether any value is estimated. The nuclides are written
+/// as with `convert`.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Expression to match.
+    expression: Filter,
+    /// File to read from, or `-` for the standard input. By default, the `file` in the config.
+    file: Option<PathBuf>,
+    #[command(flatten)]
+    output: Export,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let dataset = crate::read(args.file.as_deref())?;
+    args.output.write(args.expression.apply(&dataset))
+}
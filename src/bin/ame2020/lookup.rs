@@ -0,0 +1,63 @@
+use ame2020::{element, Nuclide};
+use clap::Parser;
+use std::{error::Error, path::Path};
+
+/// Prints all the fields of nuclides, with units
+///
+/// Estimated values are marked with `#`.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Nuclides to look up, such as `26Al`, `Al26`, or `Al-26`, and then the file to read from,
+    /// or `-` for the standard input. By default, the file is the `file` in the config.
+    #[arg(required = true, value_name = "NAME")]
+    names: Vec<String>,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    // the last argument is the file, unless it is a nuclide and there is a file in the config
+    let (names, file) = match args.names.split_last() {
+        Some((last, names))
+            if element::nuclide(last).is_none() || crate::config::get()?.file.is_none() =>
+        {
+            (names, Some(Path::new(last)))
+        }
+        _ => (&args.names[..], None),
+    };
+    let dataset = crate::read(file)?;
+    for (i, name) in names.iter().enumerate() {
+        let (z, n) = element::nuclide(name).ok_or_else(|| format!("invalid nuclide {name}"))?;
+        let nuc = dataset
+            .get(z, n)
+            .ok_or_else(|| format!("{name} is not in the table"))?;
+        if i > 0 {
+            println!();
+        }
+        print(nuc);
+    }
+    Ok(())
+}
+
+fn print(nuc: &Nuclide) {
+    println!("{}-{}", nuc.element, nuc.a());
+    println!("  Z: {}", nuc.z);
+    println!("  N: {}", nuc.n);
+    println!("  A: {}", nuc.a());
+    println!("  origin: {}", nuc.origin.as_deref().unwrap_or("-"));
+    println!(
+        "  mass excess: {} keV",
+        crate::value(Some(&nuc.mass_excess))
+    );
+    println!(
+        "  binding energy per A: {} keV",
+        crate::value(nuc.binding_energy_per_a.as_ref())
+    );
+    match (nuc.decay_mode, &nuc.beta_decay_energy) {
+        (Some(mode), Some(energy)) => println!(
+            "  beta decay energy ({}): {} keV",
+            mode.label(),
+            crate::value(Some(energy))
+        ),
+        _ => println!("  beta decay energy: -"),
+    }
+    println!("  atomic mass: {} u", crate::value(Some(&nuc.atomic_mass)));
+}
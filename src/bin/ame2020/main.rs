@@ -0,0 +1,124 @@
+use ame2020::{
+    dataset::{Dataset, Duplicates},
+    Value,
+};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+#[cfg(feature = "tui")]
+mod browse;
+mod chart;
+mod config;
+mod convert;
+mod diff;
+mod filter;
+mod lookup;
+mod qvalue;
+
+/// Tools for the atomic mass evaluation
+///
+/// Defaults are read from a TOML config, which is `$AME2020_CONFIG`, or `ame2020/config.toml` in
+/// `$XDG_CONFIG_HOME` or `~/.config`. It can have the `file` to read from when none is given, the
+/// `format` to convert to, and the `energy` and `mass` units, such as `energy = "MeV"`.
+#[derive(Parser, Debug)]
+#[command(about, version, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[cfg(feature = "tui")]
+    Browse(browse::Args),
+    Chart(chart::Args),
+    /// Prints the completions for a shell
+    Completions {
+        /// Shell to complete in.
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    Convert(convert::Args),
+    Diff(diff::Args),
+    Filter(filter::Args),
+    Lookup(lookup::Args),
+    Qvalue(qvalue::Args),
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    match cli.command {
+        #[cfg(feature = "tui")]
+        Command::Browse(args) => browse::run(&args),
+        Command::Chart(args) => chart::run(&args),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "ame2020", &mut io::stdout());
+            Ok(())
+        }
+        Command::Convert(args) => convert::run(&args),
+        Command::Diff(args) => diff::run(&args),
+        Command::Filter(args) => filter::run(&args),
+        Command::Lookup(args) => lookup::run(&args),
+        Command::Qvalue(args) => qvalue::run(&args),
+    }
+}
+
+// reads the dataset in `path`, or the `file` in the config if there is no path, or the standard
+// input if it is `-`, where a nuclide listed twice is an error
+fn read(path: Option<&Path>) -> Result<Dataset, Box<dyn Error>> {
+    let path = match path {
+        Some(path) => path,
+        None => config::get()?
+            .file
+            .as_deref()
+            .ok_or("no file was given, and there is no `file` in the config")?,
+    };
+    let dataset = if path == Path::new("-") {
+        Dataset::read(io::stdin().lock(), Duplicates::Error)?
+    } else {
+        Dataset::read(BufReader::new(File::open(path)?), Duplicates::Error)?
+    };
+    Ok(dataset)
+}
+
+// rounds to 15 significant digits, which is more than the table has, so that the error from
+// converting the units isn't printed
+fn round(x: f64) -> f64 {
+    format!("{x:.14e}").parse().unwrap_or(x)
+}
+
+// formats a value as `mean ± uncertainty`, with `#` if it is estimated, or `-` if there is none
+fn value(value: Option<&Value>) -> String {
+    match value {
+        Some(value) => format!(
+            "{} ± {}{}",
+            round(value.mean),
+            round(value.uncertainty),
+            if value.is_estimated { "#" } else { "" }
+        ),
+        None => "-".to_string(),
+    }
+}
+
+// formats a computed value like `value`, but with two significant digits of the uncertainty, since
+// sums and differences have more digits than the values they are computed from
+fn rounded(value: &Value) -> String {
+    if value.uncertainty <= 0.0 {
+        return self::value(Some(value));
+    }
+    // the uncertainty isn't big enough for the cast to be lossy
+    #[allow(clippy::cast_possible_truncation)]
+    let decimals = usize::try_from(1 - value.uncertainty.log10().floor() as i32).unwrap_or(0);
+    format!(
+        "{:.decimals$} ± {:.decimals$}{}",
+        value.mean,
+        value.uncertainty,
+        if value.is_estimated { "#" } else { "" }
+    )
+}
@@ -0,0 +1,58 @@
+use ame2020::{element, reaction::Reaction, DecayMode};
+use clap::{Parser, ValueEnum};
+use std::{error::Error, path::PathBuf};
+
+/// Prints the Q-value of a reaction or decay, in keV
+///
+/// Reactions are written like `9Be(p,n)9B`, where the light particles are `n`, `p`, `d`, `t`,
+/// `h` (helion), `a` (alpha), and `g` (gamma), and other nuclides are named like `3He`. The
+/// residual nucleus can be left out. Estimated values are marked with `#`.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Reaction, such as `9Be(p,n)9B`, or with `--decay`, the nuclide that decays.
+    reaction: String,
+    /// File to read from, or `-` for the standard input. By default, the `file` in the config.
+    file: Option<PathBuf>,
+    /// Mode of the decay of the nuclide.
+    #[arg(long, value_enum)]
+    decay: Option<Decay>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+enum Decay {
+    Alpha,
+    BetaMinus,
+    BetaPlus,
+    Ec,
+}
+
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let reaction = match args.decay {
+        Some(decay) => {
+            let (z, n) = element::nuclide(&args.reaction)
+                .ok_or_else(|| format!("invalid nuclide {}", args.reaction))?;
+            let mode = match decay {
+                Decay::Alpha => DecayMode::Alpha,
+                Decay::BetaMinus => DecayMode::BetaMinus,
+                Decay::BetaPlus => DecayMode::BetaPlus,
+                Decay::Ec => DecayMode::ElectronCapture,
+            };
+            Reaction::decay(z, n, mode).ok_or_else(|| {
+                let decay = decay.to_possible_value().expect("no values are skipped");
+                format!("{} can't decay by {}", args.reaction, decay.get_name())
+            })?
+        }
+        None => Reaction::parse(&args.reaction).ok_or_else(|| {
+            format!(
+                "invalid reaction {}, or Z and N aren't conserved",
+                args.reaction
+            )
+        })?,
+    };
+    let dataset = crate::read(args.file.as_deref())?;
+    let q = dataset
+        .reaction_q_value(&reaction)
+        .ok_or("a nuclide is not in the table")?;
+    println!("{} keV", crate::rounded(&q));
+    Ok(())
+}
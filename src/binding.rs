@@ -0,0 +1,109 @@
+//! The curve of the binding energy per nucleon against A, and the most bound nuclides.
+//!
+//! For each A, the curve has the isobar with the largest binding energy per nucleon, which is the
+//! textbook plot that peaks around iron and nickel. [`write_csv`] writes it for plotting.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{binding, parse_str};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014
+//! 0  0    1    1    2 H         13135.722895    0.000015   1112.2831     0.0002  B-      *                    2 014101.777844    0.000015").unwrap();
+//! let curve = binding::curve(&data);
+//! assert_eq!(curve.len(), 2);
+//! assert_eq!(curve[1].nuclide.a(), 2);
+//! assert_eq!(binding::most_bound(&data, 1)[0].nuclide.a(), 2);
+//! ```
+use crate::{AmeError, Nuclide, Value};
+use std::{cmp::Ordering, collections::BTreeMap, io::Write};
+
+/// A nuclide, with its binding energy per nucleon.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Point<'a> {
+    /// The nuclide
+    pub nuclide: &'a Nuclide,
+    /// The binding energy per nucleon, in keV
+    pub binding_energy_per_a: Value,
+}
+
+/// Returns the isobar with the largest binding energy per nucleon for each A in `nuclides`, in
+/// order of A.
+///
+/// The nuclides without a binding energy are left out, and of isobars with the same binding
+/// energy, the first is kept.
+#[must_use]
+pub fn curve(nuclides: &[Nuclide]) -> Vec<Point<'_>> {
+    let mut isobars = BTreeMap::new();
+    for point in points(nuclides) {
+        isobars
+            .entry(point.nuclide.a())
+            .and_modify(|best: &mut Point| {
+                if point.binding_energy_per_a.mean > best.binding_energy_per_a.mean {
+                    *best = point.clone();
+                }
+            })
+            .or_insert(point);
+    }
+    isobars.into_values().collect()
+}
+
+/// Returns the `count` nuclides in `nuclides` with the largest binding energy per nucleon, the
+/// most bound first.
+///
+/// The nuclides without a binding energy are left out, and those with the same binding energy are
+/// in the order of `nuclides`.
+#[must_use]
+pub fn most_bound(nuclides: &[Nuclide], count: usize) -> Vec<Point<'_>> {
+    let mut points: Vec<_> = points(nuclides).collect();
+    points.sort_by(|a, b| {
+        b.binding_energy_per_a
+            .mean
+            .partial_cmp(&a.binding_energy_per_a.mean)
+            .unwrap_or(Ordering::Equal)
+    });
+    points.truncate(count);
+    points
+}
+
+/// Writes `points`, such as a [`curve`], to `writer` as CSV, with a header.
+///
+/// The columns are `a`, `z`, `n`, `element`, `binding_energy_per_a`, and
+/// `binding_energy_per_a_uncertainty`, in keV.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write_csv<W: Write>(mut writer: W, points: &[Point]) -> Result<(), AmeError> {
+    writeln!(
+        writer,
+        "a,z,n,element,binding_energy_per_a,binding_energy_per_a_uncertainty"
+    )?;
+    for point in points {
+        let nuc = point.nuclide;
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            nuc.a(),
+            nuc.z,
+            nuc.n,
+            nuc.element,
+            point.binding_energy_per_a.mean,
+            point.binding_energy_per_a.uncertainty
+        )?;
+    }
+    Ok(())
+}
+
+// the nuclides with a binding energy
+fn points(nuclides: &[Nuclide]) -> impl Iterator<Item = Point<'_>> {
+    nuclides.iter().filter_map(|nuc| {
+        Some(Point {
+            nuclide: nuc,
+            binding_energy_per_a: nuc.binding_energy_per_a.clone()?,
+        })
+    })
+}
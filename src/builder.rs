@@ -0,0 +1,242 @@
+//! Building [`Nuclide`]s that aren't read from a table, with [`NuclideBuilder`].
+use crate::{constants::Constants, element, DecayMode, Field, Nuclide, Value};
+use alloc::string::String;
+use arrayvec::ArrayString;
+use thiserror::Error;
+
+/// The error from [`NuclideBuilder::build`].
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The element symbol isn't that of Z
+    #[error("element {element} does not have Z = {z}")]
+    Element {
+        /// The element symbol
+        element: String,
+        /// The proton number
+        z: u32,
+    },
+    /// There is no element symbol, and Z is beyond the known elements
+    #[error("no element symbol for Z = {0}")]
+    UnknownElement(u32),
+    /// There is no mass excess
+    #[error("missing mass excess")]
+    MissingMassExcess,
+    /// The origin is longer than the 4 characters of the table
+    #[error("origin {0:?} is too long")]
+    Origin(String),
+    /// A value or uncertainty is NaN or infinite
+    #[error("non-finite value in {0}")]
+    NonFinite(Field),
+    /// An uncertainty is negative
+    #[error("negative value in {0}")]
+    NegativeUncertainty(Field),
+}
+
+/// A builder of a [`Nuclide`], such as for tests, the tables of models, or overrides.
+///
+/// Only Z, N, and the mass excess are needed. The element symbol is that of Z, unless it is set,
+/// in which case it is checked against Z. The binding energy per nucleon and the atomic mass are
+/// computed from the mass excess, with its uncertainty, unless they are set. The rest are `None`
+/// unless they are set.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{NuclideBuilder, Value};
+///
+/// let h2 = NuclideBuilder::new(1, 1)
+///     .mass_excess(Value {
+///         mean: 13_135.722_895,
+///         uncertainty: 0.000_015,
+///         is_estimated: false,
+///     })
+///     .build()
+///     .unwrap();
+/// assert_eq!(h2.element.as_str(), "H");
+/// let binding_energy_per_a = h2.binding_energy_per_a.unwrap();
+/// assert!((binding_energy_per_a.mean - 1_112.283_1).abs() < 1e-4);
+/// assert!((h2.atomic_mass.mean - 2.014_101_778).abs() < 1e-9);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct NuclideBuilder {
+    z: u32,
+    n: u32,
+    element: Option<String>,
+    origin: Option<String>,
+    mass_excess: Option<Value>,
+    binding_energy_per_a: Option<Value>,
+    decay_mode: Option<DecayMode>,
+    beta_decay_energy: Option<Value>,
+    atomic_mass: Option<Value>,
+    constants: Constants,
+}
+
+impl NuclideBuilder {
+    /// Creates a builder of the nuclide with `z` protons and `n` neutrons.
+    #[must_use]
+    pub fn new(z: u32, n: u32) -> Self {
+        Self {
+            z,
+            n,
+            element: None,
+            origin: None,
+            mass_excess: None,
+            binding_energy_per_a: None,
+            decay_mode: None,
+            beta_decay_energy: None,
+            atomic_mass: None,
+            constants: Constants::AME2020,
+        }
+    }
+
+    /// Sets the element symbol, which must be that of Z.
+    #[must_use]
+    pub fn element(mut self, element: &str) -> Self {
+        self.element = Some(element.into());
+        self
+    }
+
+    /// Sets the code for how the mass was derived, such as `-pp`.
+    #[must_use]
+    pub fn origin(mut self, origin: &str) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Sets the mass excess, in keV.
+    #[must_use]
+    pub fn mass_excess(mut self, mass_excess: Value) -> Self {
+        self.mass_excess = Some(mass_excess);
+        self
+    }
+
+    /// Sets the binding energy per nucleon, in keV, instead of computing it.
+    #[must_use]
+    pub fn binding_energy_per_a(mut self, binding_energy_per_a: Value) -> Self {
+        self.binding_energy_per_a = Some(binding_energy_per_a);
+        self
+    }
+
+    /// Sets the beta decay energy, in keV, and the decay it is labelled with.
+    #[must_use]
+    pub fn beta_decay_energy(mut self, decay_mode: DecayMode, energy: Value) -> Self {
+        self.decay_mode = Some(decay_mode);
+        self.beta_decay_energy = Some(energy);
+        self
+    }
+
+    /// Sets the atomic mass, in u, instead of computing it.
+    #[must_use]
+    pub fn atomic_mass(mut self, atomic_mass: Value) -> Self {
+        self.atomic_mass = Some(atomic_mass);
+        self
+    }
+
+    /// Sets the constants that the binding energy per nucleon and the atomic mass are computed
+    /// with, which are [`Constants::AME2020`] by default.
+    #[must_use]
+    pub fn constants(mut self, constants: Constants) -> Self {
+        self.constants = constants;
+        self
+    }
+
+    /// Builds the nuclide.
+    ///
+    /// # Errors
+    ///
+    /// If there is no mass excess, the element symbol isn't that of Z or there is none, the
+    /// origin is too long, or a value isn't finite or has a negative uncertainty, an error is
+    /// returned.
+    pub fn build(self) -> Result<Nuclide, BuildError> {
+        let Self { z, n, .. } = self;
+        let symbol = match self.element {
+            Some(element) if element::z(&element) == Some(z) => element,
+            Some(element) => return Err(BuildError::Element { element, z }),
+            None => element::symbol(z)
+                .ok_or(BuildError::UnknownElement(z))?
+                .into(),
+        };
+        let element = ArrayString::from(&symbol)
+            .ok()
+            .ok_or(BuildError::Element { element: symbol, z })?;
+        let origin = self
+            .origin
+            .map(|origin| {
+                ArrayString::from(&origin)
+                    .ok()
+                    .ok_or(BuildError::Origin(origin))
+            })
+            .transpose()?;
+
+        let mass_excess = self.mass_excess.ok_or(BuildError::MissingMassExcess)?;
+        check(
+            &mass_excess,
+            Field::MassExcess,
+            Field::MassExcessUncertainty,
+        )?;
+        let a = f64::from(z + n);
+        let binding_energy_per_a = match self.binding_energy_per_a {
+            Some(value) => Some(value),
+            None => (a > 0.0).then(|| {
+                let binding_energy = f64::from(z) * self.constants.hydrogen_mass_excess
+                    + f64::from(n) * self.constants.neutron_mass_excess
+                    - mass_excess.mean;
+                Value {
+                    mean: binding_energy / a,
+                    uncertainty: mass_excess.uncertainty / a,
+                    is_estimated: mass_excess.is_estimated,
+                }
+            }),
+        };
+        let atomic_mass = self.atomic_mass.unwrap_or_else(|| Value {
+            mean: a + self.constants.to_u(mass_excess.mean),
+            uncertainty: self.constants.to_u(mass_excess.uncertainty),
+            is_estimated: mass_excess.is_estimated,
+        });
+        if let Some(value) = &binding_energy_per_a {
+            check(
+                value,
+                Field::BindingEnergyPerA,
+                Field::BindingEnergyPerAUncertainty,
+            )?;
+        }
+        if let Some(value) = &self.beta_decay_energy {
+            check(
+                value,
+                Field::BetaDecayEnergy,
+                Field::BetaDecayEnergyUncertainty,
+            )?;
+        }
+        check(
+            &atomic_mass,
+            Field::AtomicMass,
+            Field::AtomicMassUncertainty,
+        )?;
+
+        Ok(Nuclide {
+            n,
+            z,
+            element,
+            origin,
+            mass_excess,
+            binding_energy_per_a,
+            decay_mode: self.decay_mode,
+            beta_decay_energy: self.beta_decay_energy,
+            atomic_mass,
+        })
+    }
+}
+
+// checks that `value` is finite, with a non-negative uncertainty
+fn check(value: &Value, field: Field, uncertainty: Field) -> Result<(), BuildError> {
+    if !value.mean.is_finite() {
+        Err(BuildError::NonFinite(field))
+    } else if !value.uncertainty.is_finite() {
+        Err(BuildError::NonFinite(uncertainty))
+    } else if value.uncertainty < 0.0 {
+        Err(BuildError::NegativeUncertainty(uncertainty))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! The Q-values of the capture reactions (n,γ), (p,γ), and (α,γ) on every nuclide, as CSV or
+//! JSON.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{capture, dataset::{Dataset, Duplicates}};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let q_values = capture::q_values(&dataset);
+//! // there is no H-2 in the dataset
+//! assert_eq!(q_values[1].neutron, None);
+//! let mut csv = Vec::new();
+//! capture::write_csv(&mut csv, &q_values).unwrap();
+//! assert!(String::from_utf8(csv).unwrap().starts_with("z,n,a,element,n_gamma,"));
+//! ```
+use crate::{dataset::Dataset, reaction::Reaction, AmeError, Nuclide, Value};
+use std::io::Write;
+
+/// The capture Q-values of a nuclide as the target, in keV.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CaptureQ<'a> {
+    /// The target
+    pub nuclide: &'a Nuclide,
+    /// The Q-value of (n,γ), if the product is in the dataset
+    pub neutron: Option<Value>,
+    /// The Q-value of (p,γ), if the product is in the dataset
+    pub proton: Option<Value>,
+    /// The Q-value of (α,γ), if the product and the α are in the dataset
+    pub alpha: Option<Value>,
+}
+
+/// Returns the capture Q-values of each nuclide in `dataset`, in its order.
+#[must_use]
+pub fn q_values(dataset: &Dataset) -> Vec<CaptureQ<'_>> {
+    let capture = |nuc: &Nuclide, (z, n): (u32, u32)| {
+        dataset.reaction_q_value(&Reaction {
+            initial: vec![(nuc.z, nuc.n), (z, n)],
+            products: vec![(nuc.z + z, nuc.n + n)],
+            positrons: 0,
+        })
+    };
+    dataset
+        .iter()
+        .map(|nuc| CaptureQ {
+            nuclide: nuc,
+            neutron: capture(nuc, (0, 1)),
+            proton: capture(nuc, (1, 0)),
+            alpha: capture(nuc, (2, 2)),
+        })
+        .collect()
+}
+
+/// Writes `q_values` to `writer` as CSV, with a header.
+///
+/// The columns are `z`, `n`, `a`, `element`, and the mean and uncertainty of each Q-value, such as
+/// `n_gamma` and `n_gamma_uncertainty`, which are empty if there is none.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write_csv<W: Write>(mut writer: W, q_values: &[CaptureQ]) -> Result<(), AmeError> {
+    writeln!(
+        writer,
+        "z,n,a,element,n_gamma,n_gamma_uncertainty,p_gamma,p_gamma_uncertainty,a_gamma,a_gamma_uncertainty"
+    )?;
+    for q in q_values {
+        let nuc = q.nuclide;
+        write!(writer, "{},{},{},{}", nuc.z, nuc.n, nuc.a(), nuc.element)?;
+        for value in [&q.neutron, &q.proton, &q.alpha] {
+            match value {
+                Some(value) => write!(writer, ",{},{}", value.mean, value.uncertainty)?,
+                None => write!(writer, ",,")?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes `q_values` to `writer` as a JSON array, with an object for each nuclide.
+///
+/// The keys are those of the columns of [`write_csv`], and the Q-values that there are none of are
+/// `null`.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write_json<W: Write>(mut writer: W, q_values: &[CaptureQ]) -> Result<(), AmeError> {
+    write!(writer, "[")?;
+    for (i, q) in q_values.iter().enumerate() {
+        let nuc = q.nuclide;
+        if i > 0 {
+            write!(writer, ",")?;
+        }
+        write!(
+            writer,
+            "\n  {{\"z\": {}, \"n\": {}, \"a\": {}, \"element\": \"{}\"",
+            nuc.z,
+            nuc.n,
+            nuc.a(),
+            nuc.element
+        )?;
+        for (name, value) in [
+            ("n_gamma", &q.neutron),
+            ("p_gamma", &q.proton),
+            ("a_gamma", &q.alpha),
+        ] {
+            match value {
+                Some(value) => write!(
+                    writer,
+                    ", \"{name}\": {}, \"{name}_uncertainty\": {}",
+                    value.mean, value.uncertainty
+                )?,
+                None => write!(writer, ", \"{name}\": null, \"{name}_uncertainty\": null")?,
+            }
+        }
+        write!(writer, "}}")?;
+    }
+    writeln!(writer, "\n]")?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Generating Rust source for a table of nuclides.
+//!
+//! This is meant to be used from a build script, so that the data does not need to be parsed at
+//! runtime. The generated file defines a `static` array of [`StaticNuclide`]s, which can be
+//! included with [`include!`]. A [`Nuclide`] can't be constructed in a `const` context, because of
+//! its element symbol and origin, so a `StaticNuclide` has them as `&'static str`s, and is
+//! converted with [`StaticNuclide::to_nuclide`].
+//!
+//! # Examples
+//!
+//! In `build.rs`, with `ame2020` as a build dependency:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::{env, path::PathBuf};
+//!
+//! let out = PathBuf::from(env::var("OUT_DIR")?).join("nuclides.rs");
+//! ame2020::codegen::generate("mass.mas20", out)?;
+//! println!("cargo:rerun-if-changed=mass.mas20");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! And in the crate, with `ame2020` as a regular dependency:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/nuclides.rs"));
+//!
+//! fn main() {
+//!     println!("{}", NUCLIDES.len());
+//!     let neutron = NUCLIDES[0].to_nuclide();
+//! }
+//! ```
+use crate::{AmeError, DecayMode, Iter, Nuclide, Value};
+use arrayvec::ArrayString;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// A [`Nuclide`] that can be constructed in a `const` context, as in the generated source.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StaticNuclide {
+    /// Neutron number
+    pub n: u32,
+    /// Proton number
+    pub z: u32,
+    /// Chemical symbol of the element, of at most 3 bytes
+    pub element: &'static str,
+    /// The code for how the mass was derived, such as `-pp`, if any, of at most 4 bytes
+    pub origin: Option<&'static str>,
+    /// Mass excess
+    pub mass_excess: Value,
+    /// Binding energy per nucleon, if it is given
+    pub binding_energy_per_a: Option<Value>,
+    /// The decay the beta decay energy is labelled with, if any
+    pub decay_mode: Option<DecayMode>,
+    /// Beta decay energy, if it is given
+    pub beta_decay_energy: Option<Value>,
+    /// Atomic Mass in atomic mass units
+    pub atomic_mass: Value,
+}
+
+impl StaticNuclide {
+    /// Converts this to a [`Nuclide`].
+    ///
+    /// # Panics
+    ///
+    /// If the element symbol is longer than 3 bytes, or the origin longer than 4, this panics.
+    /// They never are in the generated source, since it is written from `Nuclide`s.
+    #[must_use]
+    pub fn to_nuclide(&self) -> Nuclide {
+        Nuclide {
+            n: self.n,
+            z: self.z,
+            element: ArrayString::from(self.element).expect("the element symbol is too long"),
+            origin: self
+                .origin
+                .map(|origin| ArrayString::from(origin).expect("the origin is too long")),
+            mass_excess: self.mass_excess.clone(),
+            binding_energy_per_a: self.binding_energy_per_a.clone(),
+            decay_mode: self.decay_mode,
+            beta_decay_energy: self.beta_decay_energy.clone(),
+            atomic_mass: self.atomic_mass.clone(),
+        }
+    }
+}
+
+/// Reads the AME data from `input`, and writes a Rust source file defining `NUCLIDES` to `output`.
+///
+/// # Errors
+///
+/// If `input` fails to read or parse, or `output` fails to write, an error is returned.
+pub fn generate(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<(), AmeError> {
+    let reader = BufReader::new(File::open(input)?);
+    let nuclides = Iter::new(reader).collect::<Result<Vec<_>, _>>()?;
+    let mut writer = BufWriter::new(File::create(output)?);
+    write_static(&mut writer, "NUCLIDES", &nuclides)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes Rust source defining a `static` array named `name`, containing `nuclides` as
+/// [`StaticNuclide`]s.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{codegen::write_static, Iter};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047");
+/// let nuclides = Iter::new(reader).collect::<Result<Vec<_>, _>>().unwrap();
+///
+/// let mut source = Vec::new();
+/// write_static(&mut source, "NUCLIDES", &nuclides).unwrap();
+/// let source = String::from_utf8(source).unwrap();
+/// assert!(source.contains("[::ame2020::codegen::StaticNuclide; 1]"));
+/// ```
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write_static<W: Write>(mut writer: W, name: &str, nuclides: &[Nuclide]) -> io::Result<()> {
+    writeln!(writer, "#[allow(clippy::all, clippy::pedantic)]")?;
+    writeln!(
+        writer,
+        "pub static {name}: [::ame2020::codegen::StaticNuclide; {}] = [",
+        nuclides.len()
+    )?;
+    for nuclide in nuclides {
+        write_nuclide(&mut writer, nuclide)?;
+    }
+    writeln!(writer, "];")
+}
+
+fn write_nuclide<W: Write>(writer: &mut W, nuclide: &Nuclide) -> io::Result<()> {
+    writeln!(writer, "    ::ame2020::codegen::StaticNuclide {{")?;
+    writeln!(writer, "        n: {},", nuclide.n)?;
+    writeln!(writer, "        z: {},", nuclide.z)?;
+    writeln!(writer, "        element: {:?},", nuclide.element.as_str())?;
+    match &nuclide.origin {
+        Some(origin) => writeln!(writer, "        origin: Some({:?}),", origin.as_str())?,
+        None => writeln!(writer, "        origin: None,")?,
+    }
+    write!(writer, "        mass_excess: ")?;
+    write_value(writer, &nuclide.mass_excess)?;
+    write!(writer, "        binding_energy_per_a: ")?;
+    write_optional_value(writer, nuclide.binding_energy_per_a.as_ref())?;
+    match nuclide.decay_mode {
+        Some(mode) => writeln!(
+            writer,
+            "        decay_mode: Some(::ame2020::DecayMode::{mode:?}),"
+        )?,
+        None => writeln!(writer, "        decay_mode: None,")?,
+    }
+    write!(writer, "        beta_decay_energy: ")?;
+    write_optional_value(writer, nuclide.beta_decay_energy.as_ref())?;
+    write!(writer, "        atomic_mass: ")?;
+    write_value(writer, &nuclide.atomic_mass)?;
+    writeln!(writer, "    }},")
+}
+
+fn write_value<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+    write_value_inline(writer, value)?;
+    writeln!(writer, ",")
+}
+
+fn write_optional_value<W: Write>(writer: &mut W, value: Option<&Value>) -> io::Result<()> {
+    match value {
+        Some(value) => {
+            write!(writer, "Some(")?;
+            write_value_inline(writer, value)?;
+            writeln!(writer, "),")
+        }
+        None => writeln!(writer, "None,"),
+    }
+}
+
+fn write_value_inline<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+    write!(writer, "::ame2020::Value {{ mean: ")?;
+    write_f64(writer, value.mean)?;
+    write!(writer, ", uncertainty: ")?;
+    write_f64(writer, value.uncertainty)?;
+    write!(writer, ", is_estimated: {} }}", value.is_estimated)
+}
+
+fn write_f64<W: Write>(writer: &mut W, x: f64) -> io::Result<()> {
+    // `Debug` round-trips, and always includes a decimal point or exponent, so it is a valid float
+    // literal, except for the non-finite values.
+    if x.is_nan() {
+        write!(writer, "f64::NAN")
+    } else if x == f64::INFINITY {
+        write!(writer, "f64::INFINITY")
+    } else if x == f64::NEG_INFINITY {
+        write!(writer, "f64::NEG_INFINITY")
+    } else {
+        write!(writer, "{x:?}_f64")
+    }
+}
@@ -0,0 +1,81 @@
+//! Physical constants used in the evaluation.
+//!
+//! The values are those used in AME2020.
+
+/// The atomic mass unit, in keV
+pub const ATOMIC_MASS_UNIT: f64 = 931_494.102_42;
+
+/// The mass excess of the neutron, in keV
+pub const NEUTRON_MASS_EXCESS: f64 = 8_071.318_06;
+
+/// The mass excess of the hydrogen atom, in keV
+pub const HYDROGEN_MASS_EXCESS: f64 = 7_288.971_064;
+
+/// The mass of the electron, in keV
+pub const ELECTRON_MASS: f64 = 510.998_950;
+
+/// The elementary charge divided by the atomic mass unit, in C/kg
+pub const CHARGE_PER_MASS: f64 = 9.648_533_212e7;
+
+/// The physical constants of an evaluation, which all of the conversions between units use.
+///
+/// The preamble of `mass.mas20` doesn't state them, so the iterators don't read them. They are
+/// [`Constants::AME2020`] by default, and others can be given to [`Dataset`] with
+/// [`with_constants`], and to [`NuclideBuilder`] with [`constants`], such as for an evaluation that
+/// uses different values.
+///
+/// [`Dataset`]: crate::dataset::Dataset
+/// [`with_constants`]: crate::dataset::Dataset::with_constants
+/// [`NuclideBuilder`]: crate::NuclideBuilder
+/// [`constants`]: crate::NuclideBuilder::constants
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::constants::Constants;
+///
+/// let constants = Constants::AME2020;
+/// assert!((constants.to_u(constants.electron_mass) - 0.000_548_58).abs() < 1e-8);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Constants {
+    /// The atomic mass unit, in keV
+    pub atomic_mass_unit: f64,
+    /// The mass of the electron, in keV
+    pub electron_mass: f64,
+    /// The mass excess of the neutron, in keV
+    pub neutron_mass_excess: f64,
+    /// The mass excess of the hydrogen atom, in keV
+    pub hydrogen_mass_excess: f64,
+    /// The elementary charge divided by the atomic mass unit, in C/kg
+    pub charge_per_mass: f64,
+}
+
+impl Constants {
+    /// The constants of AME2020, which are the constants in this module
+    pub const AME2020: Self = Self {
+        atomic_mass_unit: ATOMIC_MASS_UNIT,
+        electron_mass: ELECTRON_MASS,
+        neutron_mass_excess: NEUTRON_MASS_EXCESS,
+        hydrogen_mass_excess: HYDROGEN_MASS_EXCESS,
+        charge_per_mass: CHARGE_PER_MASS,
+    };
+
+    /// Converts `mass`, in u, to keV.
+    #[must_use]
+    pub fn to_kev(&self, mass: f64) -> f64 {
+        mass * self.atomic_mass_unit
+    }
+
+    /// Converts `energy`, in keV, to u.
+    #[must_use]
+    pub fn to_u(&self, energy: f64) -> f64 {
+        energy / self.atomic_mass_unit
+    }
+}
+
+impl Default for Constants {
+    fn default() -> Self {
+        Self::AME2020
+    }
+}
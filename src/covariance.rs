@@ -0,0 +1,223 @@
+//! Correlations between the masses of pairs of nuclides.
+//!
+//! The masses in the evaluation come from a least-squares adjustment, so the masses of nuclides
+//! that are linked by the same measurements, which are often neighbors, are correlated. A
+//! [`Covariance`] holds the correlation coefficients of pairs of nuclides, which are set with
+//! [`Covariance::insert`] or read with [`read`], and the covariances are computed with the
+//! uncertainties of the mass excesses in a dataset.
+//!
+//! The format that [`read`] reads is specific to this crate, and isn't one published with the
+//! evaluation, so correlations from elsewhere, such as from the
+//! [AMDC](https://www-nds.iaea.org/amdc/), have to be converted to it first. It is a line for each
+//! pair, with the names of the two nuclides, such as `26Al` (as in [`element::nuclide`]), and the
+//! correlation coefficient, separated by whitespace. Blank lines and lines starting with `#` are
+//! skipped.
+//!
+//! With the `nalgebra` feature, the covariances of a set of nuclides are available as a matrix,
+//! and can be propagated to any linear combination of their masses.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::covariance;
+//!
+//! let covariance = covariance::read(&b"# nuclide nuclide correlation
+//! 1H 2H 0.25
+//! "[..]).unwrap();
+//! assert_eq!(covariance.correlation((1, 1), (1, 0)), 0.25);
+//! assert_eq!(covariance.correlation((1, 1), (1, 1)), 1.0);
+//! assert_eq!(covariance.correlation((1, 1), (1, 2)), 0.0);
+//! ```
+use crate::{dataset::Dataset, element, AmeError};
+#[cfg(feature = "nalgebra")]
+use nalgebra::{DMatrix, DVector};
+use std::{collections::BTreeMap, io::BufRead};
+
+// the (Z, N) of two nuclides
+type Pair = ((u32, u32), (u32, u32));
+
+/// The correlations between the mass excesses of pairs of nuclides, keyed by the (Z, N) of each.
+///
+/// A nuclide is fully correlated with itself, and the pairs that aren't in it are uncorrelated.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Covariance {
+    // the correlations, with the smaller (Z, N) first
+    correlations: BTreeMap<Pair, f64>,
+}
+
+impl Covariance {
+    /// Returns an empty `Covariance`, with all of the nuclides uncorrelated.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the correlation between the nuclides `a` and `b` to `correlation`, replacing the one
+    /// before, if any.
+    ///
+    /// The correlation of a nuclide with itself is always 1, so it isn't set.
+    pub fn insert(&mut self, a: (u32, u32), b: (u32, u32), correlation: f64) {
+        if a != b {
+            self.correlations.insert(key(a, b), correlation);
+        }
+    }
+
+    /// Returns the correlation between the nuclides `a` and `b`.
+    #[must_use]
+    pub fn correlation(&self, a: (u32, u32), b: (u32, u32)) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+        self.correlations.get(&key(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the covariance, in keV², between the mass excesses of the nuclides `a` and `b`,
+    /// with their uncertainties in `dataset`.
+    ///
+    /// If a nuclide isn't in the dataset, `None` is returned.
+    #[must_use]
+    pub fn covariance(&self, dataset: &Dataset, a: (u32, u32), b: (u32, u32)) -> Option<f64> {
+        let uncertainty = |(z, n)| Some(dataset.get(z, n)?.mass_excess.uncertainty);
+        Some(self.correlation(a, b) * uncertainty(a)? * uncertainty(b)?)
+    }
+
+    /// Returns the number of correlated pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.correlations.len()
+    }
+
+    /// Returns whether there are no correlated pairs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.correlations.is_empty()
+    }
+
+    /// Returns an iterator over the correlated pairs, with the correlation of each.
+    pub fn iter(&self) -> impl Iterator<Item = ((u32, u32), (u32, u32), f64)> + '_ {
+        self.correlations
+            .iter()
+            .map(|(&(a, b), &correlation)| (a, b, correlation))
+    }
+}
+
+// the key of a pair, which is the same in either order
+fn key(a: (u32, u32), b: (u32, u32)) -> Pair {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Reads the correlations in `reader`, in this crate's format, which is described in the
+/// [module documentation](self).
+///
+/// If a pair is listed more than once, the last correlation is kept.
+///
+/// # Errors
+///
+/// If reading fails, an error is returned. If a line doesn't have two nuclides and a correlation
+/// between -1 and 1, it is [`AmeError::InvalidColumn`], with the column that is invalid.
+pub fn read<R: BufRead>(reader: R) -> Result<Covariance, AmeError> {
+    let mut covariance = Covariance::new();
+    for (line, text) in (1..).zip(reader.lines()) {
+        let text = text?;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let mut fields = text.split_whitespace();
+        let invalid = |column| AmeError::InvalidColumn { line, column };
+        let mut nuclide = || {
+            fields
+                .next()
+                .and_then(element::nuclide)
+                .ok_or_else(|| invalid("nuclide"))
+        };
+        let (a, b) = (nuclide()?, nuclide()?);
+        let correlation = fields
+            .next()
+            .and_then(|field| field.parse::<f64>().ok())
+            .filter(|correlation| (-1.0..=1.0).contains(correlation))
+            .ok_or_else(|| invalid("correlation"))?;
+        covariance.insert(a, b, correlation);
+    }
+    Ok(covariance)
+}
+
+#[cfg(feature = "nalgebra")]
+impl Covariance {
+    /// Returns the covariance matrix, in keV², of the mass excesses of `nuclides`, with their
+    /// uncertainties in `dataset`, in the order of `nuclides`.
+    ///
+    /// If a nuclide isn't in the dataset, `None` is returned.
+    #[must_use]
+    pub fn matrix(&self, dataset: &Dataset, nuclides: &[(u32, u32)]) -> Option<DMatrix<f64>> {
+        let uncertainties = nuclides
+            .iter()
+            .map(|&(z, n)| Some(dataset.get(z, n)?.mass_excess.uncertainty))
+            .collect::<Option<Vec<f64>>>()?;
+        let correlations = self.correlation_matrix(nuclides);
+        Some(DMatrix::from_fn(nuclides.len(), nuclides.len(), |i, j| {
+            correlations[(i, j)] * uncertainties[i] * uncertainties[j]
+        }))
+    }
+
+    /// Returns the correlation matrix of `nuclides`, in their order.
+    #[must_use]
+    pub fn correlation_matrix(&self, nuclides: &[(u32, u32)]) -> DMatrix<f64> {
+        DMatrix::from_fn(nuclides.len(), nuclides.len(), |i, j| {
+            self.correlation(nuclides[i], nuclides[j])
+        })
+    }
+
+    /// Returns the values, in keV, of linear combinations of the mass excesses of `nuclides`, and
+    /// their covariance matrix, in keV².
+    ///
+    /// Each row of `jacobian` is a combination, with a column for each nuclide, in the order of
+    /// `nuclides`. The values are `jacobian` times the mass excesses, and the covariance matrix is
+    /// J Σ Jᵀ, where Σ is the [`matrix`](Self::matrix) of `nuclides`.
+    ///
+    /// If a nuclide isn't in the dataset, or `jacobian` doesn't have a column for each nuclide,
+    /// `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ame2020::{covariance::Covariance, dataset::Dataset};
+    /// use nalgebra::DMatrix;
+    ///
+    /// # let dataset = Dataset::default();
+    /// # let covariance = Covariance::new();
+    /// // S_n and S_2n of 134Sn, from the mass excesses of 132Sn, 133Sn, 134Sn, and the neutron
+    /// let nuclides = [(50, 82), (50, 83), (50, 84), (0, 1)];
+    /// let jacobian = DMatrix::from_row_slice(2, 4, &[
+    ///     0.0, 1.0, -1.0, 1.0,
+    ///     1.0, 0.0, -1.0, 2.0,
+    /// ]);
+    /// if let Some((values, covariance)) = covariance.propagate(&dataset, &nuclides, &jacobian) {
+    ///     println!("S_n = {} ± {} keV", values[0], covariance[(0, 0)].sqrt());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn propagate(
+        &self,
+        dataset: &Dataset,
+        nuclides: &[(u32, u32)],
+        jacobian: &DMatrix<f64>,
+    ) -> Option<(DVector<f64>, DMatrix<f64>)> {
+        if jacobian.ncols() != nuclides.len() {
+            return None;
+        }
+        let mass_excesses = nuclides
+            .iter()
+            .map(|&(z, n)| Some(dataset.get(z, n)?.mass_excess.mean))
+            .collect::<Option<Vec<f64>>>()?;
+        let covariance = self.matrix(dataset, nuclides)?;
+        Some((
+            jacobian * DVector::from_vec(mass_excesses),
+            jacobian * covariance * jacobian.transpose(),
+        ))
+    }
+}
@@ -0,0 +1,534 @@
+//! A table of nuclides, indexed by Z and N.
+//!
+//! Unlike a `Vec` collected from an [`Iter`], a [`Dataset`] has at most one nuclide for each
+//! (Z, N). A repeated nuclide usually means the file was corrupted, or several files were
+//! concatenated, so by default it is an error. [`Duplicates`] chooses what happens instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::dataset::{Dataset, Duplicates};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! assert_eq!(dataset.get(0, 1).map(|nuc| nuc.element.as_str()), Some("n"));
+//! ```
+use crate::{
+    constants::Constants, covariance::Covariance, overrides::Override, reaction::Reaction,
+    validate::Report, AmeError, Iter, Nuclide, SliceIter, Value,
+};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    io::BufRead,
+    slice,
+};
+
+/// What to do when a nuclide appears more than once.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum Duplicates {
+    /// Return [`AmeError::Duplicate`], with the lines of both
+    #[default]
+    Error,
+    /// Keep the first, and ignore the rest
+    KeepFirst,
+    /// Keep the last, in the position of the first
+    KeepLast,
+}
+
+/// Nuclides in the order they were read, with at most one for each (Z, N).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Dataset {
+    nuclides: Vec<Nuclide>,
+    // (z, n) to the index in `nuclides`
+    index: HashMap<(u32, u32), usize>,
+    // the duplicates that were kept out, by `Duplicates::KeepFirst` or `KeepLast`
+    duplicates: Vec<AmeError>,
+    // the correlations between the mass excesses, if they were loaded
+    covariance: Option<Covariance>,
+    // the constants for the conversions, which are AME2020's unless they were given
+    constants: Constants,
+    // the nuclides that were replaced or inserted by `apply_overrides`, in order
+    overrides: Vec<Override>,
+}
+
+impl Dataset {
+    /// Reads and parses all of the data in `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If reading or parsing fails, or there is a duplicate and `duplicates` is
+    /// [`Duplicates::Error`], the first error is returned.
+    pub fn read<R: BufRead>(reader: R, duplicates: Duplicates) -> Result<Self, AmeError> {
+        Self::collect(Iter::new(reader), Iter::line_number, duplicates)
+    }
+
+    /// Parses all of the data in `data`.
+    ///
+    /// # Errors
+    ///
+    /// If parsing fails, or there is a duplicate and `duplicates` is [`Duplicates::Error`], the
+    /// first error is returned.
+    pub fn from_slice(data: &[u8], duplicates: Duplicates) -> Result<Self, AmeError> {
+        Self::collect(SliceIter::new(data), SliceIter::line_number, duplicates)
+    }
+
+    fn collect<I: Iterator<Item = Result<Nuclide, AmeError>>>(
+        mut iter: I,
+        line_number: impl Fn(&I) -> usize,
+        duplicates: Duplicates,
+    ) -> Result<Self, AmeError> {
+        let mut dataset = Self::default();
+        // the line of each nuclide, for errors
+        let mut lines = Vec::new();
+        while let Some(nuclide) = iter.next() {
+            let nuclide = nuclide?;
+            let line = line_number(&iter);
+            match dataset.index.entry((nuclide.z, nuclide.n)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(dataset.nuclides.len());
+                    dataset.nuclides.push(nuclide);
+                    lines.push(line);
+                }
+                Entry::Occupied(entry) => {
+                    let i = *entry.get();
+                    let err = AmeError::Duplicate {
+                        line,
+                        first_line: lines[i],
+                        z: nuclide.z,
+                        n: nuclide.n,
+                    };
+                    match duplicates {
+                        Duplicates::Error => return Err(err),
+                        Duplicates::KeepFirst => {}
+                        Duplicates::KeepLast => dataset.nuclides[i] = nuclide,
+                    }
+                    dataset.duplicates.push(err);
+                }
+            }
+        }
+        Ok(dataset)
+    }
+
+    /// Returns the nuclide with `z` protons and `n` neutrons.
+    #[must_use]
+    pub fn get(&self, z: u32, n: u32) -> Option<&Nuclide> {
+        self.index.get(&(z, n)).map(|&i| &self.nuclides[i])
+    }
+
+    /// Returns the neutron.
+    #[must_use]
+    pub fn neutron(&self) -> Option<&Nuclide> {
+        self.get(0, 1)
+    }
+
+    /// Returns <sup>1</sup>H, for the proton.
+    ///
+    /// Like the rest of the nuclides, this is the atom, with the mass of an electron.
+    #[must_use]
+    pub fn proton(&self) -> Option<&Nuclide> {
+        self.get(1, 0)
+    }
+
+    /// Returns <sup>2</sup>H, for the deuteron.
+    #[must_use]
+    pub fn deuteron(&self) -> Option<&Nuclide> {
+        self.get(1, 1)
+    }
+
+    /// Returns <sup>3</sup>H, for the triton.
+    #[must_use]
+    pub fn triton(&self) -> Option<&Nuclide> {
+        self.get(1, 2)
+    }
+
+    /// Returns <sup>3</sup>He, for the helion.
+    #[must_use]
+    pub fn helion(&self) -> Option<&Nuclide> {
+        self.get(2, 1)
+    }
+
+    /// Returns <sup>4</sup>He, for the alpha particle.
+    #[must_use]
+    pub fn alpha(&self) -> Option<&Nuclide> {
+        self.get(2, 2)
+    }
+
+    /// Returns the nuclides, in the order they were read.
+    #[must_use]
+    pub fn nuclides(&self) -> &[Nuclide] {
+        &self.nuclides
+    }
+
+    /// Returns the number of nuclides.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nuclides.len()
+    }
+
+    /// Returns whether there are no nuclides.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nuclides.is_empty()
+    }
+
+    /// Returns the constants used for the conversions of the dataset, such as between u and keV.
+    ///
+    /// These are [`Constants::AME2020`], unless others were given with
+    /// [`with_constants`][Self::with_constants].
+    #[must_use]
+    pub fn constants(&self) -> &Constants {
+        &self.constants
+    }
+
+    /// Returns the dataset with `constants` used for its conversions, instead of
+    /// [`Constants::AME2020`].
+    #[must_use]
+    pub fn with_constants(self, constants: Constants) -> Self {
+        Self { constants, ..self }
+    }
+
+    /// Returns the duplicates that were kept out of the dataset, as [`AmeError::Duplicate`].
+    ///
+    /// This is only non-empty with [`Duplicates::KeepFirst`] or [`Duplicates::KeepLast`].
+    #[must_use]
+    pub fn duplicates(&self) -> &[AmeError] {
+        &self.duplicates
+    }
+
+    /// Checks the dataset for problems, for when data needs to be trusted.
+    ///
+    /// This includes the [`check_all`][crate::validate::check_all] inconsistencies, beta decay
+    /// energies that can't be checked, duplicates, and unknown elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::dataset::{Dataset, Duplicates};
+    ///
+    /// let data = br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+    /// let report = Dataset::from_slice(data, Duplicates::Error).unwrap().validate();
+    /// // H-1 isn't in the data, so the beta decay energy of the neutron can't be checked
+    /// assert_eq!(report.missing_neighbors, [(0, 1)]);
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Report {
+        Report::new(&self.nuclides, &self.duplicates, &self.constants)
+    }
+
+    /// Returns the dataset with the correlations between the mass excesses in `covariance`, which
+    /// are used for the uncertainties of [`q_value`][Self::q_value], and the methods that use it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ame2020::{covariance, dataset::{Dataset, Duplicates}};
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let covariance = covariance::read(BufReader::new(File::open("correlations.txt")?))?;
+    /// let dataset = Dataset::read(BufReader::new(File::open("mass.mas20")?), Duplicates::Error)?
+    ///     .with_covariance(covariance);
+    /// // S_n of 133Sn, with the correlation of 132Sn and 133Sn
+    /// let sn = dataset.separation_energy(50, 83, 0, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_covariance(self, covariance: Covariance) -> Self {
+        Self {
+            covariance: Some(covariance),
+            ..self
+        }
+    }
+
+    /// Returns the correlations between the mass excesses, if they were given with
+    /// [`with_covariance`][Self::with_covariance].
+    #[must_use]
+    pub fn covariance(&self) -> Option<&Covariance> {
+        self.covariance.as_ref()
+    }
+
+    /// Replaces the nuclides that are in the dataset with those with the same (Z, N) in
+    /// `nuclides`, such as newer measurements, and adds the rest at the end.
+    ///
+    /// Each nuclide is recorded in [`overrides`][Self::overrides], with the nuclide it replaced.
+    /// The values of the other nuclides aren't changed, and neither are the correlations from
+    /// [`with_covariance`][Self::with_covariance], so the beta decay energies that depend on an
+    /// overridden mass are those of the evaluation.
+    pub fn apply_overrides(&mut self, nuclides: impl IntoIterator<Item = Nuclide>) {
+        for nuclide in nuclides {
+            let (z, n) = (nuclide.z, nuclide.n);
+            let replaced = match self.index.entry((z, n)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(self.nuclides.len());
+                    self.nuclides.push(nuclide);
+                    None
+                }
+                Entry::Occupied(entry) => {
+                    Some(std::mem::replace(&mut self.nuclides[*entry.get()], nuclide))
+                }
+            };
+            self.overrides.push(Override { z, n, replaced });
+        }
+    }
+
+    /// Returns the nuclides that were replaced or inserted by
+    /// [`apply_overrides`][Self::apply_overrides], in order.
+    #[must_use]
+    pub fn overrides(&self) -> &[Override] {
+        &self.overrides
+    }
+
+    /// Returns the Q-value, in keV, of the reaction or decay from `initial` to `products`, which
+    /// are (Z, N).
+    ///
+    /// The Q-value is the sum of the mass excesses of `initial`, less those of `products`. The
+    /// uncertainties of different nuclides are added in quadrature, as if they were independent,
+    /// and those of a nuclide that is on both sides cancel. If there are correlations, from
+    /// [`with_covariance`][Self::with_covariance], their covariances are added too. The result is
+    /// estimated if any of the mass excesses are. Atomic masses are used, so the electrons are
+    /// accounted for, except when they are created or destroyed, as in β⁺ decay.
+    ///
+    /// If a nuclide isn't in the dataset, A isn't the same on both sides, or A overflows, `None` is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::dataset::{Dataset, Duplicates};
+    ///
+    /// let data = br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+    ///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+    /// let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+    /// // the β⁻ decay of the neutron
+    /// let q = dataset.q_value(&[(0, 1)], &[(1, 0)]).unwrap();
+    /// assert!((q.mean - 782.347).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn q_value(&self, initial: &[(u32, u32)], products: &[(u32, u32)]) -> Option<Value> {
+        let a = |side: &[(u32, u32)]| {
+            side.iter()
+                .try_fold(0_u32, |a, (z, n)| a.checked_add(z.checked_add(*n)?))
+        };
+        if a(initial)? != a(products)? {
+            return None;
+        }
+        // the number of times each nuclide is in the sum, so repeats are fully correlated
+        let mut counts: Vec<((u32, u32), f64)> = Vec::new();
+        for (side, sign) in [(initial, 1.0), (products, -1.0)] {
+            for &key in side {
+                match counts.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, count)) => *count += sign,
+                    None => counts.push((key, sign)),
+                }
+            }
+        }
+        let mut q = Value::default();
+        let mut variance = 0.0;
+        // the nuclides that are in the sum, with their count times their uncertainty
+        let mut terms = Vec::new();
+        for ((z, n), count) in counts {
+            let mass_excess = &self.get(z, n)?.mass_excess;
+            if count != 0.0 {
+                q.mean += count * mass_excess.mean;
+                variance += (count * mass_excess.uncertainty).powi(2);
+                q.is_estimated |= mass_excess.is_estimated;
+                terms.push(((z, n), count * mass_excess.uncertainty));
+            }
+        }
+        if let Some(covariance) = &self.covariance {
+            for (i, &(a, x)) in terms.iter().enumerate() {
+                for &(b, y) in &terms[i + 1..] {
+                    variance += 2.0 * covariance.correlation(a, b) * x * y;
+                }
+            }
+        }
+        // inconsistent correlations could make the variance negative
+        q.uncertainty = variance.max(0.0).sqrt();
+        Some(q)
+    }
+
+    /// Returns the Q-value, in keV, of `reaction`.
+    ///
+    /// This is the [`q_value`][Self::q_value] of the nuclides, less the masses of the positrons
+    /// that are created, and the electrons the atoms lose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::{dataset::{Dataset, Duplicates}, reaction::Reaction, DecayMode};
+    ///
+    /// let data = br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+    ///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+    /// let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+    /// let decay = Reaction::decay(0, 1, DecayMode::BetaMinus).unwrap();
+    /// let q = dataset.reaction_q_value(&decay).unwrap();
+    /// assert!((q.mean - 782.347).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn reaction_q_value(&self, reaction: &Reaction) -> Option<Value> {
+        let mut q = self.q_value(&reaction.initial, &reaction.products)?;
+        q.mean -= 2.0 * f64::from(reaction.positrons) * self.constants.electron_mass;
+        Some(q)
+    }
+
+    /// Returns the energy, in keV, needed to remove `protons` protons and `neutrons` neutrons
+    /// from the nuclide with `z` protons and `n` neutrons.
+    ///
+    /// This is the negative of the [`q_value`][Self::q_value] of the removal, so the protons are
+    /// hydrogen atoms, and the neutron and hydrogen need to be in the dataset. For example,
+    /// S<sub>2n</sub> is `separation_energy(z, n, 0, 2)`.
+    ///
+    /// If a nuclide isn't in the dataset, or there aren't enough protons or neutrons, `None` is
+    /// returned.
+    #[must_use]
+    pub fn separation_energy(&self, z: u32, n: u32, protons: u32, neutrons: u32) -> Option<Value> {
+        let mut products = vec![(z.checked_sub(protons)?, n.checked_sub(neutrons)?)];
+        products.extend((0..protons).map(|_| (1, 0)));
+        products.extend((0..neutrons).map(|_| (0, 1)));
+        let mut energy = self.q_value(&[(z, n)], &products)?;
+        energy.mean = -energy.mean;
+        Some(energy)
+    }
+
+    /// Returns an iterator over the nuclides, in the order they were read.
+    pub fn iter(&self) -> slice::Iter<'_, Nuclide> {
+        self.nuclides.iter()
+    }
+
+    /// Returns the isotopes of each element, by Z.
+    ///
+    /// Each group is in the order the nuclides were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::dataset::{Dataset, Duplicates};
+    ///
+    /// let data = br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+    ///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+    /// let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+    /// let elements = dataset.group_by_element();
+    /// assert_eq!(elements.keys().collect::<Vec<_>>(), [&0, &1]);
+    /// assert_eq!(elements[&1][0].element.as_str(), "H");
+    /// ```
+    #[must_use]
+    pub fn group_by_element(&self) -> BTreeMap<u32, Vec<&Nuclide>> {
+        self.group_by(|nuc| nuc.z)
+    }
+
+    /// Returns the isobars of each A, by A.
+    ///
+    /// Each group is in the order the nuclides were read.
+    #[must_use]
+    pub fn group_by_a(&self) -> BTreeMap<u32, Vec<&Nuclide>> {
+        self.group_by(Nuclide::a)
+    }
+
+    /// Returns the isotones of each N, by N.
+    ///
+    /// Each group is in the order the nuclides were read.
+    #[must_use]
+    pub fn group_by_n(&self) -> BTreeMap<u32, Vec<&Nuclide>> {
+        self.group_by(|nuc| nuc.n)
+    }
+
+    /// Returns the `k` nuclides nearest to Z = `z` and N = `n` on the chart, the nearest first.
+    ///
+    /// The distance is the Euclidean distance in (N, Z), and the nuclides at the same distance
+    /// are in order of Z, and then N. The nuclide at (`z`, `n`), if it is in the dataset, is the
+    /// first, at a distance of 0. To find, for example, the nearest measured nuclide, filter the
+    /// nuclides first with [`nearest_in`].
+    #[must_use]
+    pub fn nearest(&self, z: u32, n: u32, k: usize) -> Vec<&Nuclide> {
+        nearest_in(&self.nuclides, z, n, k)
+    }
+
+    /// Returns the nuclides within a distance of `radius` of Z = `z` and N = `n` on the chart,
+    /// the nearest first, as in [`nearest`](Self::nearest).
+    #[must_use]
+    pub fn neighbors_within(&self, z: u32, n: u32, radius: f64) -> Vec<&Nuclide> {
+        let mut neighbors = by_distance(&self.nuclides, z, n);
+        neighbors.retain(|&(distance, _)| distance <= radius);
+        neighbors.into_iter().map(|(_, nuc)| nuc).collect()
+    }
+
+    // the nuclides with each value of `key`
+    fn group_by(&self, key: impl Fn(&Nuclide) -> u32) -> BTreeMap<u32, Vec<&Nuclide>> {
+        let mut groups: BTreeMap<u32, Vec<&Nuclide>> = BTreeMap::new();
+        for nuc in &self.nuclides {
+            groups.entry(key(nuc)).or_default().push(nuc);
+        }
+        groups
+    }
+}
+
+/// Returns the `k` nuclides in `nuclides` nearest to Z = `z` and N = `n` on the chart, the
+/// nearest first, as in [`Dataset::nearest`].
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::dataset::{self, Dataset};
+///
+/// # let dataset = Dataset::default();
+/// // the measured nuclide nearest to 100Sn
+/// let measured = dataset.iter().filter(|nuc| !nuc.mass_excess.is_estimated);
+/// let nearest = dataset::nearest_in(measured, 50, 50, 1);
+/// ```
+pub fn nearest_in<'a>(
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+    z: u32,
+    n: u32,
+    k: usize,
+) -> Vec<&'a Nuclide> {
+    let mut nearest = by_distance(nuclides, z, n);
+    nearest.truncate(k);
+    nearest.into_iter().map(|(_, nuc)| nuc).collect()
+}
+
+// the nuclides with their distances from (z, n), the nearest first
+fn by_distance<'a>(
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+    z: u32,
+    n: u32,
+) -> Vec<(f64, &'a Nuclide)> {
+    let mut nuclides: Vec<_> = nuclides
+        .into_iter()
+        .map(|nuc| {
+            let distance = (f64::from(nuc.z) - f64::from(z)).hypot(f64::from(nuc.n) - f64::from(n));
+            (distance, nuc)
+        })
+        .collect();
+    nuclides.sort_by(|(a, a_nuc), (b, b_nuc)| {
+        a.total_cmp(b)
+            .then((a_nuc.z, a_nuc.n).cmp(&(b_nuc.z, b_nuc.n)))
+    });
+    nuclides
+}
+
+impl<'a> IntoIterator for &'a Dataset {
+    type Item = &'a Nuclide;
+    type IntoIter = slice::Iter<'a, Nuclide>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Dataset> for Vec<Nuclide> {
+    fn from(dataset: Dataset) -> Self {
+        dataset.nuclides
+    }
+}
@@ -0,0 +1,264 @@
+//! Graphs of the decays that the masses allow.
+//!
+//! A decay is allowed if its Q-value is positive. Each allowed decay lowers the mass, so the
+//! decays from a nuclide, and then from its daughters, form a graph with no cycles, which ends at
+//! the nuclides that can't decay in any of the modes, or whose daughters aren't in the dataset.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{
+//!     dataset::{Dataset, Duplicates},
+//!     decay::{self, Mode},
+//! };
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let graph = decay::graph(&dataset, 0, 1, &Mode::ALL);
+//! assert_eq!(graph.edges.len(), 1);
+//! assert_eq!(graph.edges[0].mode, Mode::BetaMinus);
+//! assert_eq!(graph.edges[0].daughter, (1, 0));
+//! ```
+use crate::{dataset::Dataset, element, reaction::Reaction, DecayMode, Value};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::Write,
+};
+
+/// A way for a nuclide to decay.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Mode {
+    /// α decay
+    Alpha,
+    /// β⁻ decay
+    BetaMinus,
+    /// β⁺ decay
+    BetaPlus,
+    /// Electron capture
+    ElectronCapture,
+    /// Emission of a proton
+    Proton,
+    /// Emission of a neutron
+    Neutron,
+}
+
+impl Mode {
+    /// All of the modes.
+    pub const ALL: [Self; 6] = [
+        Self::Alpha,
+        Self::BetaMinus,
+        Self::BetaPlus,
+        Self::ElectronCapture,
+        Self::Proton,
+        Self::Neutron,
+    ];
+
+    /// Returns the symbol of the mode, such as `β⁻`.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Alpha => "α",
+            Self::BetaMinus => "β⁻",
+            Self::BetaPlus => "β⁺",
+            Self::ElectronCapture => "EC",
+            Self::Proton => "p",
+            Self::Neutron => "n",
+        }
+    }
+
+    /// Returns the decay of the nuclide with `z` protons and `n` neutrons by this mode, with the
+    /// daughter first.
+    ///
+    /// If the nuclide can't decay by this mode, `None` is returned.
+    #[must_use]
+    pub fn reaction(self, z: u32, n: u32) -> Option<Reaction> {
+        let decay_mode = match self {
+            Self::Alpha => DecayMode::Alpha,
+            Self::BetaMinus => DecayMode::BetaMinus,
+            Self::BetaPlus => DecayMode::BetaPlus,
+            Self::ElectronCapture => DecayMode::ElectronCapture,
+            Self::Proton => {
+                return Some(Reaction {
+                    initial: vec![(z, n)],
+                    products: vec![(z.checked_sub(1)?, n), (1, 0)],
+                    positrons: 0,
+                })
+            }
+            Self::Neutron => {
+                return Some(Reaction {
+                    initial: vec![(z, n)],
+                    products: vec![(z, n.checked_sub(1)?), (0, 1)],
+                    positrons: 0,
+                })
+            }
+        };
+        Reaction::decay(z, n, decay_mode)
+    }
+}
+
+/// The Q-values of electron capture and β⁺ decay of a nuclide, from [`electron_capture`].
+///
+/// Both go to the same daughter, but β⁺ decay also creates a positron, and the atom loses the
+/// electron that is left over, so its Q-value is that of electron capture less twice the mass of
+/// an electron, about 1022 keV. A nuclide with a Q-value of electron capture below that can only
+/// decay by electron capture.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Capture {
+    /// The Q-value of electron capture, in keV
+    pub electron_capture: Value,
+    /// The Q-value of β⁺ decay, in keV
+    pub beta_plus: Value,
+}
+
+impl Capture {
+    /// Returns [`Mode::BetaPlus`] if β⁺ decay is allowed, in which case electron capture is too,
+    /// [`Mode::ElectronCapture`] if only electron capture is, and `None` if neither is.
+    #[must_use]
+    pub fn mode(&self) -> Option<Mode> {
+        if self.beta_plus.mean > 0.0 {
+            Some(Mode::BetaPlus)
+        } else if self.electron_capture.mean > 0.0 {
+            Some(Mode::ElectronCapture)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether electron capture is allowed, but β⁺ decay isn't.
+    #[must_use]
+    pub fn is_pure_electron_capture(&self) -> bool {
+        self.mode() == Some(Mode::ElectronCapture)
+    }
+}
+
+/// Returns the Q-values of electron capture and β⁺ decay of the nuclide with `z` protons and `n`
+/// neutrons, with the masses in `dataset`.
+///
+/// If the nuclide or its daughter isn't in the dataset, or it has no protons, `None` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{dataset::{Dataset, Duplicates}, decay::{self, Mode}};
+///
+/// let dataset = Dataset::from_slice(br"1
+/// 1
+/// 0  1    4    3    7 Li        14907.1047      0.0042     5606.4401     0.0006  B-      *                    7 016003.4343      0.0045
+///   -1    3    4    7 Be        15768.999       0.071      5371.5485     0.0101  B-      *                    7 016928.716       0.076", Duplicates::Error).unwrap();
+/// // 7Be only decays by electron capture
+/// let capture = decay::electron_capture(&dataset, 4, 3).unwrap();
+/// assert!((capture.electron_capture.mean - 861.894).abs() < 1e-3);
+/// assert!(capture.beta_plus.mean < 0.0);
+/// assert_eq!(capture.mode(), Some(Mode::ElectronCapture));
+/// ```
+#[must_use]
+pub fn electron_capture(dataset: &Dataset, z: u32, n: u32) -> Option<Capture> {
+    let q_value = |mode: Mode| dataset.reaction_q_value(&mode.reaction(z, n)?);
+    Some(Capture {
+        electron_capture: q_value(Mode::ElectronCapture)?,
+        beta_plus: q_value(Mode::BetaPlus)?,
+    })
+}
+
+/// An allowed decay, in a [`Graph`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Edge {
+    /// The (Z, N) of the nuclide that decays
+    pub parent: (u32, u32),
+    /// The (Z, N) of the nuclide it decays to
+    pub daughter: (u32, u32),
+    /// How it decays
+    pub mode: Mode,
+    /// The Q-value, in keV, which is positive
+    pub q_value: Value,
+}
+
+/// The allowed decays of a nuclide, and of its daughters, from [`graph`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Graph {
+    /// The decays, in order of the number of decays from the first nuclide
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Returns the decays of the nuclide with `z` protons and `n` neutrons.
+    pub fn decays(&self, z: u32, n: u32) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(move |edge| edge.parent == (z, n))
+    }
+
+    /// Returns all of the nuclides in the graph, as (Z, N).
+    #[must_use]
+    pub fn nuclides(&self) -> BTreeSet<(u32, u32)> {
+        self.edges
+            .iter()
+            .flat_map(|edge| [edge.parent, edge.daughter])
+            .collect()
+    }
+
+    /// Returns the graph in the DOT language of [Graphviz](https://graphviz.org), with the
+    /// nuclides named like `3He`, and the decays labelled with their mode and Q-value.
+    ///
+    /// ```
+    /// # let graph = ame2020::decay::Graph::default();
+    /// std::fs::write("decays.dot", graph.to_dot())?;
+    /// # std::fs::remove_file("decays.dot")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let name = |(z, n): (u32, u32)| match element::symbol(z) {
+            Some(symbol) => format!("{}{symbol}", z + n),
+            None => format!("Z={z} N={n}"),
+        };
+        let mut dot = String::from("digraph decays {\n");
+        for edge in &self.edges {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{} {:.1} keV\"];",
+                name(edge.parent),
+                name(edge.daughter),
+                edge.mode.symbol(),
+                edge.q_value.mean
+            )
+            .expect("writing to a string doesn't fail");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns the decays by `modes` that are allowed for the nuclide with `z` protons and `n`
+/// neutrons, and for its daughters.
+#[must_use]
+pub fn graph(dataset: &Dataset, z: u32, n: u32, modes: &[Mode]) -> Graph {
+    let mut graph = Graph::default();
+    let mut visited = BTreeSet::from([(z, n)]);
+    let mut queue = VecDeque::from([(z, n)]);
+    while let Some(parent) = queue.pop_front() {
+        for &mode in modes {
+            let Some(reaction) = mode.reaction(parent.0, parent.1) else {
+                continue;
+            };
+            let Some(q_value) = dataset.reaction_q_value(&reaction) else {
+                continue;
+            };
+            if q_value.mean <= 0.0 {
+                continue;
+            }
+            let daughter = reaction.products[0];
+            if visited.insert(daughter) {
+                queue.push_back(daughter);
+            }
+            graph.edges.push(Edge {
+                parent,
+                daughter,
+                mode,
+                q_value,
+            });
+        }
+    }
+    graph
+}
@@ -0,0 +1,111 @@
+//! Parsing the values exactly, as [`Decimal`]s instead of `f64`s.
+//!
+//! The values in the table are decimal, and most of them can't be represented exactly as an
+//! `f64`, so parsing them as floats loses the digits as written, such as the trailing zeros. As
+//! [`Decimal`]s, the mean and uncertainty are kept exactly, with the number of decimal places in
+//! the file, which is needed to compare values exactly, or to write the table out byte for byte.
+//!
+//! Each line is parsed, and checked, the same way as by [`SliceIter`](crate::SliceIter), and then
+//! the values are read again from the same columns as decimals. The atomic mass, which is given in
+//! micro-u, is converted to u by moving the decimal point, so it stays exact as well.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::decimal;
+//! use rust_decimal::Decimal;
+//!
+//! let data = decimal::parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let neutron = &data[0];
+//! assert_eq!(neutron.mass_excess.mean, Decimal::new(807_131_806, 5));
+//! // the trailing zero is kept
+//! assert_eq!(neutron.beta_decay_energy.as_ref().unwrap().mean.to_string(), "782.3470");
+//! assert_eq!(neutron.atomic_mass.mean.to_string(), "1.00866491590");
+//! ```
+use crate::{
+    parser::{parse_decimal_nuclide, Parser},
+    slice::Lines,
+    AmeError, Nuclide, ParseOptions,
+};
+use alloc::vec::Vec;
+use core::{iter::FusedIterator, ops::ControlFlow};
+use rust_decimal::Decimal;
+
+/// An iterator that reads AME2020 data from a byte slice, with the values as [`Decimal`]s.
+///
+/// Apart from the type of the values, this yields the same items as
+/// [`SliceIter`](crate::SliceIter), except that a value with more digits than a [`Decimal`] holds
+/// is an [`AmeError::Decimal`].
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::decimal::DecimalIter;
+///
+/// let data = br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+/// let mut iter = DecimalIter::new(data);
+/// assert!(iter.next().is_some());
+/// assert!(iter.next().is_none());
+/// ```
+pub struct DecimalIter<'a> {
+    lines: Lines<'a>,
+    parser: Parser,
+}
+
+impl<'a> DecimalIter<'a> {
+    /// Creates a new `DecimalIter` from `data`.
+    #[must_use]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::with_options(data, ParseOptions::default())
+    }
+
+    /// Creates a new `DecimalIter` from `data`, which parses according to `options`.
+    #[must_use]
+    pub fn with_options(data: &'a [u8], options: ParseOptions) -> Self {
+        Self {
+            lines: Lines::new(data),
+            parser: Parser::new(options),
+        }
+    }
+
+    /// Returns the number of the most recent line read, starting from 1.
+    ///
+    /// After an item is returned, this is the line it was on.
+    #[must_use]
+    pub fn line_number(&self) -> usize {
+        self.parser.line_number()
+    }
+}
+
+impl Iterator for DecimalIter<'_> {
+    type Item = Result<Nuclide<Decimal>, AmeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            match self.parser.parse_line(line) {
+                ControlFlow::Continue(()) => {}
+                ControlFlow::Break(res) => {
+                    return Some(res.and_then(|nuclide| {
+                        parse_decimal_nuclide(line, self.parser.line_number(), nuclide)
+                    }))
+                }
+            }
+        }
+    }
+}
+
+impl FusedIterator for DecimalIter<'_> {}
+
+/// Parses all of `s`, with the values as [`Decimal`]s.
+///
+/// # Errors
+///
+/// If any line fails to parse, the first error is returned.
+pub fn parse_str(s: &str) -> Result<Vec<Nuclide<Decimal>>, AmeError> {
+    DecimalIter::new(s.as_bytes()).collect()
+}
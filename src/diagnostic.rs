@@ -0,0 +1,113 @@
+//! Rich diagnostics with [miette](https://crates.io/crates/miette).
+//!
+//! A [`SourceError`] pairs an [`AmeError`] with the line it came from, so that the line can be
+//! shown with the bad field underlined. Tabs in the line are expanded to spaces, with a tab stop
+//! every 8 columns, the same as when it was parsed, so that the underline lines up with the field.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{diagnostic::SourceError, Iter};
+//!
+//! let source = r"1
+//! 1
+//! 0  1    1    0    1  n         8071a31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+//! let err = Iter::new(source.as_bytes()).next().unwrap().unwrap_err();
+//! let err = SourceError::new(err, source);
+//! // return `Err(miette::Report::new(err))` to have it rendered
+//! # let _ = err;
+//! ```
+use crate::{parser::expand_tabs, AmeError};
+use miette::{
+    Diagnostic, LabeledSpan, MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents,
+};
+use std::{error::Error, fmt};
+
+/// An [`AmeError`] with the line it came from.
+#[derive(Debug)]
+pub struct SourceError {
+    error: AmeError,
+    line: Option<Line>,
+}
+
+impl SourceError {
+    /// Pairs `error` with its line from `source`, the text that was parsed.
+    ///
+    /// If `error` isn't a parsing error, or its line isn't in `source`, no line will be shown.
+    #[must_use]
+    pub fn new(error: AmeError, source: &str) -> Self {
+        let line = error.line().and_then(|number| {
+            let source = source.lines().nth(number.checked_sub(1)?)?;
+            // only the tabs are replaced, so the text is still UTF-8
+            let text = String::from_utf8_lossy(&expand_tabs(source.as_bytes())).into_owned();
+            Some(Line { text, number })
+        });
+        Self { error, line }
+    }
+
+    /// Returns the underlying error.
+    #[must_use]
+    pub fn error(&self) -> &AmeError {
+        &self.error
+    }
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl Error for SourceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl Diagnostic for SourceError {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.line.as_ref().map(|line| line as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let line = self.line.as_ref()?;
+        let columns = self.error.columns()?;
+        // the columns are of the line with its tabs expanded, which is how it's shown. the field
+        // may be past the end of the line, if the line is too short.
+        let start = columns.start.min(line.text.len());
+        let end = columns.end.min(line.text.len());
+        let label = self.error.field().map(|field| field.to_string());
+        let span = LabeledSpan::new_with_span(label, SourceSpan::from(start..end));
+        Some(Box::new(std::iter::once(span)))
+    }
+}
+
+/// A single line of the source, which is shown with its line number.
+#[derive(Debug)]
+struct Line {
+    // the line with its tabs expanded
+    text: String,
+    number: usize,
+}
+
+impl SourceCode for Line {
+    // the whole line is always returned, since there is no other context
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        _context_lines_before: usize,
+        _context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        if span.offset() + span.len() > self.text.len() {
+            return Err(MietteError::OutOfBounds);
+        }
+        Ok(Box::new(MietteSpanContents::new(
+            self.text.as_bytes(),
+            SourceSpan::from(0..self.text.len()),
+            // `SpanContents` lines start from 0
+            self.number - 1,
+            0,
+            1,
+        )))
+    }
+}
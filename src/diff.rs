@@ -0,0 +1,147 @@
+//! Comparing two tables, such as two editions of the evaluation.
+//!
+//! [`diff`] finds the nuclides that were added and removed, and for the nuclides in both tables,
+//! how much each value changed, in keV (or u) and in σ, the uncertainties of both tables added in
+//! quadrature.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{
+//!     dataset::{Dataset, Duplicates},
+//!     diff::diff,
+//!     Field,
+//! };
+//!
+//! let old = Dataset::from_slice(br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047", Duplicates::Error).unwrap();
+//! let new = Dataset::from_slice(br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31706     0.00050       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047", Duplicates::Error).unwrap();
+//! let diff = diff(&old, &new);
+//! assert!(diff.added.is_empty() && diff.removed.is_empty());
+//! let delta = diff.changed[0].delta(Field::MassExcess).unwrap();
+//! assert!((delta.difference.mean + 0.001).abs() < 1e-9);
+//! assert!((delta.sigma + 1.5).abs() < 0.01);
+//! ```
+use crate::{dataset::Dataset, Field, Nuclide, Value};
+
+/// The differences between two tables, from [`diff`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Diff<'a> {
+    /// The nuclides that are only in the new table, in its order
+    pub added: Vec<&'a Nuclide>,
+    /// The nuclides that are only in the old table, in its order
+    pub removed: Vec<&'a Nuclide>,
+    /// The nuclides in both tables with a value that changed, in the order of the old table
+    pub changed: Vec<Change<'a>>,
+}
+
+/// A nuclide that is in both tables, and how its values changed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Change<'a> {
+    /// The nuclide in the old table
+    pub old: &'a Nuclide,
+    /// The nuclide in the new table
+    pub new: &'a Nuclide,
+    /// The values that changed, which are in both tables
+    pub deltas: Vec<Delta>,
+}
+
+impl Change<'_> {
+    /// Returns the change of `field`, if it changed.
+    #[must_use]
+    pub fn delta(&self, field: Field) -> Option<&Delta> {
+        self.deltas.iter().find(|delta| delta.field == field)
+    }
+}
+
+/// The change of one value of a nuclide.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Delta {
+    /// The value that changed: [`Field::MassExcess`], [`Field::BindingEnergyPerA`],
+    /// [`Field::BetaDecayEnergy`], or [`Field::AtomicMass`]
+    pub field: Field,
+    /// The new value minus the old value, with the uncertainties added in quadrature
+    ///
+    /// It is estimated if either value is.
+    pub difference: Value,
+    /// The difference in units of its uncertainty
+    ///
+    /// If the uncertainty is zero, this is infinite.
+    pub sigma: f64,
+}
+
+/// Compares the nuclides in `old` and `new`, which are matched by Z and N.
+#[must_use]
+pub fn diff<'a>(old: &'a Dataset, new: &'a Dataset) -> Diff<'a> {
+    let added = new
+        .iter()
+        .filter(|nuc| old.get(nuc.z, nuc.n).is_none())
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|nuc| new.get(nuc.z, nuc.n).is_none())
+        .collect();
+    let changed = old
+        .iter()
+        .filter_map(|old| {
+            let new = new.get(old.z, old.n)?;
+            let deltas: Vec<Delta> = [
+                (
+                    Field::MassExcess,
+                    Some(&old.mass_excess),
+                    Some(&new.mass_excess),
+                ),
+                (
+                    Field::BindingEnergyPerA,
+                    old.binding_energy_per_a.as_ref(),
+                    new.binding_energy_per_a.as_ref(),
+                ),
+                (
+                    Field::BetaDecayEnergy,
+                    old.beta_decay_energy.as_ref(),
+                    new.beta_decay_energy.as_ref(),
+                ),
+                (
+                    Field::AtomicMass,
+                    Some(&old.atomic_mass),
+                    Some(&new.atomic_mass),
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(field, old, new)| delta(field, old?, new?))
+            .collect();
+            (!deltas.is_empty()).then_some(Change { old, new, deltas })
+        })
+        .collect();
+    Diff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+// the change from `old` to `new`, if the mean changed
+fn delta(field: Field, old: &Value, new: &Value) -> Option<Delta> {
+    let mean = new.mean - old.mean;
+    if mean == 0.0 {
+        return None;
+    }
+    let uncertainty = old.uncertainty.hypot(new.uncertainty);
+    let sigma = if uncertainty > 0.0 {
+        mean / uncertainty
+    } else {
+        mean.signum() * f64::INFINITY
+    };
+    Some(Delta {
+        field,
+        difference: Value {
+            mean,
+            uncertainty,
+            is_estimated: old.is_estimated || new.is_estimated,
+        },
+        sigma,
+    })
+}
@@ -0,0 +1,80 @@
+//! The element symbols used in the table.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::element::{symbol, z};
+//!
+//! assert_eq!(symbol(26), Some("Fe"));
+//! assert_eq!(z("Fe"), Some(26));
+//! // the neutron is listed with Z = 0
+//! assert_eq!(symbol(0), Some("n"));
+//! ```
+//!
+//! Nuclides can be named with their mass number and symbol, such as `26Al`, which [`nuclide`]
+//! parses.
+
+/// The symbols of the elements, indexed by Z.
+///
+/// The neutron is at index 0, with the symbol "n", as in the table.
+pub const SYMBOLS: [&str; 119] = [
+    "n", "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S",
+    "Cl", "Ar", "K", "Ca", "Sc", "Ti", "V", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga", "Ge",
+    "As", "Se", "Br", "Kr", "Rb", "Sr", "Y", "Zr", "Nb", "Mo", "Tc", "Ru", "Rh", "Pd", "Ag", "Cd",
+    "In", "Sn", "Sb", "Te", "I", "Xe", "Cs", "Ba", "La", "Ce", "Pr", "Nd", "Pm", "Sm", "Eu", "Gd",
+    "Tb", "Dy", "Ho", "Er", "Tm", "Yb", "Lu", "Hf", "Ta", "W", "Re", "Os", "Ir", "Pt", "Au", "Hg",
+    "Tl", "Pb", "Bi", "Po", "At", "Rn", "Fr", "Ra", "Ac", "Th", "Pa", "U", "Np", "Pu", "Am", "Cm",
+    "Bk", "Cf", "Es", "Fm", "Md", "No", "Lr", "Rf", "Db", "Sg", "Bh", "Hs", "Mt", "Ds", "Rg", "Cn",
+    "Nh", "Fl", "Mc", "Lv", "Ts", "Og",
+];
+
+/// Returns the symbol of the element with atomic number `z`.
+#[must_use]
+pub fn symbol(z: u32) -> Option<&'static str> {
+    SYMBOLS.get(usize::try_from(z).ok()?).copied()
+}
+
+/// Returns the atomic number of the element with symbol `symbol`.
+///
+/// The comparison is case sensitive, as symbols are in the table.
+#[must_use]
+pub fn z(symbol: &str) -> Option<u32> {
+    (0..)
+        .zip(SYMBOLS)
+        .find(|&(_, s)| s == symbol)
+        .map(|(z, _)| z)
+}
+
+/// Returns Z and N of the nuclide named `name`, such as `26Al`, `Al26`, or `Al-26`.
+///
+/// The symbol is case sensitive, as with [`z`].
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::element::nuclide;
+///
+/// assert_eq!(nuclide("26Al"), Some((13, 13)));
+/// assert_eq!(nuclide("Al-26"), Some((13, 13)));
+/// assert_eq!(nuclide("1n"), Some((0, 1)));
+/// assert_eq!(nuclide("Al"), None);
+/// ```
+#[must_use]
+pub fn nuclide(name: &str) -> Option<(u32, u32)> {
+    let (a, symbol) = match name.find(|c: char| !c.is_ascii_digit()) {
+        // `26Al`
+        Some(i) if i > 0 => {
+            let (a, symbol) = name.split_at(i);
+            (a, symbol)
+        }
+        // `Al26` or `Al-26`
+        _ => {
+            let i = name.find(|c: char| c.is_ascii_digit())?;
+            let (symbol, a) = name.split_at(i);
+            (a, symbol.strip_suffix('-').unwrap_or(symbol))
+        }
+    };
+    let a: u32 = a.parse().ok()?;
+    let z = z(symbol)?;
+    Some((z, a.checked_sub(z)?))
+}
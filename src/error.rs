@@ -1,8 +1,12 @@
-use std::{
+use arrayvec::ArrayString;
+use core::{
     convert::Infallible,
-    io,
+    fmt,
     num::{ParseFloatError, ParseIntError},
+    ops::Range,
 };
+#[cfg(feature = "std")]
+use std::io;
 use thiserror::Error;
 
 /// A reading or parsing error.
@@ -10,24 +14,169 @@ use thiserror::Error;
 #[non_exhaustive]
 #[allow(clippy::module_name_repetitions)]
 pub enum AmeError {
+    #[cfg(feature = "std")]
     #[error("read error")]
     Io(io::ErrorKind),
-    #[error("int parsing error")]
-    ParseInt(#[from] ParseIntError),
-    #[error("float parsing error")]
-    ParseFloat(#[from] ParseFloatError),
-    #[error("line too short")]
-    TooShortLine,
-    #[error("string indexing error")]
-    StrIndex,
+    #[error("int parsing error in {field}, columns {columns:?}, on line {line}")]
+    ParseInt {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+        source: ParseIntError,
+    },
+    #[error("float parsing error in {field}, columns {columns:?}, on line {line}")]
+    ParseFloat {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+        source: ParseFloatError,
+    },
+    #[error("line {line} too short for {field}, columns {columns:?}")]
+    TooShortLine {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[error("string indexing error in {field}, columns {columns:?}, on line {line}")]
+    StrIndex {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[error("placeholder in {field}, which is required, columns {columns:?}, on line {line}")]
+    Placeholder {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[error("non-finite value in {field}, columns {columns:?}, on line {line}")]
+    NonFinite {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[error("negative value in {field}, columns {columns:?}, on line {line}")]
+    NegativeUncertainty {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[cfg(feature = "decimal")]
+    #[error("value in {field} does not fit in a decimal, columns {columns:?}, on line {line}")]
+    Decimal {
+        line: usize,
+        field: Field,
+        columns: Range<usize>,
+    },
+    #[error("mass number {a} is not N+Z ({expected}), columns {columns:?}, on line {line}")]
+    MassNumber {
+        line: usize,
+        columns: Range<usize>,
+        a: u32,
+        expected: u32,
+    },
+    #[error("element {element} does not have Z = {z}, columns {columns:?}, on line {line}")]
+    Element {
+        line: usize,
+        columns: Range<usize>,
+        element: ArrayString<3>,
+        z: u32,
+    },
+    #[error("unknown decay mode {mode:?}, columns {columns:?}, on line {line}")]
+    DecayMode {
+        line: usize,
+        columns: Range<usize>,
+        mode: ArrayString<2>,
+    },
+    #[error("Z = {z}, N = {n} on line {line} is a duplicate of line {first_line}")]
+    Duplicate {
+        line: usize,
+        first_line: usize,
+        z: u32,
+        n: u32,
+    },
+    #[error("missing column {0}")]
+    MissingColumn(&'static str),
+    #[error("invalid value in column {column}, on line {line}")]
+    InvalidColumn { line: usize, column: &'static str },
+    #[error("invalid snapshot")]
+    InvalidSnapshot,
+    #[error("unsupported snapshot version {0}")]
+    SnapshotVersion(u32),
 }
 
+impl AmeError {
+    /// Returns the number of the line the error is on, if it is a parsing error.
+    #[must_use]
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::ParseInt { line, .. }
+            | Self::ParseFloat { line, .. }
+            | Self::TooShortLine { line, .. }
+            | Self::StrIndex { line, .. }
+            | Self::Placeholder { line, .. }
+            | Self::NonFinite { line, .. }
+            | Self::NegativeUncertainty { line, .. }
+            | Self::MassNumber { line, .. }
+            | Self::Element { line, .. }
+            | Self::DecayMode { line, .. }
+            | Self::Duplicate { line, .. }
+            | Self::InvalidColumn { line, .. } => Some(*line),
+            #[cfg(feature = "decimal")]
+            Self::Decimal { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Returns the field the error is in, if it is a parsing error.
+    #[must_use]
+    pub fn field(&self) -> Option<Field> {
+        match self {
+            Self::ParseInt { field, .. }
+            | Self::ParseFloat { field, .. }
+            | Self::TooShortLine { field, .. }
+            | Self::StrIndex { field, .. }
+            | Self::Placeholder { field, .. }
+            | Self::NonFinite { field, .. }
+            | Self::NegativeUncertainty { field, .. } => Some(*field),
+            #[cfg(feature = "decimal")]
+            Self::Decimal { field, .. } => Some(*field),
+            Self::MassNumber { .. } => Some(Field::A),
+            Self::Element { .. } => Some(Field::Element),
+            Self::DecayMode { .. } => Some(Field::DecayMode),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte range of the field in the line, if it is a parsing error.
+    #[must_use]
+    pub fn columns(&self) -> Option<Range<usize>> {
+        match self {
+            Self::ParseInt { columns, .. }
+            | Self::ParseFloat { columns, .. }
+            | Self::TooShortLine { columns, .. }
+            | Self::StrIndex { columns, .. }
+            | Self::Placeholder { columns, .. }
+            | Self::NonFinite { columns, .. }
+            | Self::NegativeUncertainty { columns, .. }
+            | Self::MassNumber { columns, .. }
+            | Self::Element { columns, .. }
+            | Self::DecayMode { columns, .. } => Some(columns.clone()),
+            #[cfg(feature = "decimal")]
+            Self::Decimal { columns, .. } => Some(columns.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl From<io::Error> for AmeError {
     fn from(e: io::Error) -> Self {
         Self::Io(e.kind())
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::ErrorKind> for AmeError {
     fn from(k: io::ErrorKind) -> Self {
         Self::Io(k)
@@ -39,3 +188,45 @@ impl From<Infallible> for AmeError {
         unreachable!()
     }
 }
+
+/// A field of a line in the body.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Field {
+    N,
+    Z,
+    A,
+    Element,
+    Origin,
+    MassExcess,
+    MassExcessUncertainty,
+    BindingEnergyPerA,
+    BindingEnergyPerAUncertainty,
+    DecayMode,
+    BetaDecayEnergy,
+    BetaDecayEnergyUncertainty,
+    AtomicMass,
+    AtomicMassUncertainty,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::N => "N",
+            Self::Z => "Z",
+            Self::A => "A",
+            Self::Element => "element",
+            Self::Origin => "origin",
+            Self::MassExcess => "mass excess",
+            Self::MassExcessUncertainty => "mass excess uncertainty",
+            Self::BindingEnergyPerA => "binding energy per nucleon",
+            Self::BindingEnergyPerAUncertainty => "binding energy per nucleon uncertainty",
+            Self::DecayMode => "decay mode",
+            Self::BetaDecayEnergy => "beta decay energy",
+            Self::BetaDecayEnergyUncertainty => "beta decay energy uncertainty",
+            Self::AtomicMass => "atomic mass",
+            Self::AtomicMassUncertainty => "atomic mass uncertainty",
+        };
+        f.write_str(s)
+    }
+}
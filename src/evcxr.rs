@@ -0,0 +1,132 @@
+//! Rich display in Rust notebooks, with [evcxr](https://github.com/evcxr/evcxr).
+//!
+//! In a Jupyter notebook with the evcxr kernel, a [`Nuclide`], a [`Dataset`], or a [`Table`] at
+//! the end of a cell is shown as an HTML table, instead of with [`Debug`]. A `Table` is collected
+//! from the nuclides of a query, so that the results are shown the same way. At most
+//! [`MAX_ROWS`] rows are shown, followed by the number of rows that were left out.
+//!
+//! Values are shown as the mean ± the uncertainty, with `#` after estimated values, as in the
+//! table. Energies are in keV, and atomic masses are in atomic mass units.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{evcxr::Table, parse_str};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let table: Table = data.iter().filter(|nuc| nuc.z == 0).collect();
+//! assert!(table.to_html().contains("<td>8071.31806 ± 0.00044</td>"));
+//! ```
+use crate::{dataset::Dataset, Nuclide, Value};
+use std::fmt::Write;
+
+/// The most rows that are shown in a table.
+pub const MAX_ROWS: usize = 100;
+
+const HEADER: &str = "<tr><th>Z</th><th>N</th><th>A</th><th>Element</th><th>Origin</th>\
+    <th>Mass excess (keV)</th><th>Binding energy per A (keV)</th><th>Decay</th>\
+    <th>Beta decay energy (keV)</th><th>Atomic mass (u)</th></tr>";
+
+/// Nuclides, such as the results of a query, that are shown as an HTML table.
+#[derive(Clone, Debug, Default)]
+pub struct Table<'a>(pub Vec<&'a Nuclide>);
+
+impl<'a> FromIterator<&'a Nuclide> for Table<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a Nuclide>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Table<'_> {
+    /// Returns the nuclides as an HTML table.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        html(&self.0)
+    }
+
+    /// Prints the table for evcxr to show.
+    pub fn evcxr_display(&self) {
+        display(&self.to_html());
+    }
+}
+
+impl Nuclide {
+    /// Returns the nuclide as an HTML table, with one row.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        html(&[self])
+    }
+
+    /// Prints the nuclide for evcxr to show.
+    pub fn evcxr_display(&self) {
+        display(&self.to_html());
+    }
+}
+
+impl Dataset {
+    /// Returns the nuclides as an HTML table.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        html(&self.iter().collect::<Vec<_>>())
+    }
+
+    /// Prints the nuclides for evcxr to show.
+    pub fn evcxr_display(&self) {
+        display(&self.to_html());
+    }
+}
+
+fn display(html: &str) {
+    println!("EVCXR_BEGIN_CONTENT text/html\n{html}\nEVCXR_END_CONTENT");
+}
+
+fn html(nuclides: &[&Nuclide]) -> String {
+    let mut html = format!("<table>{HEADER}");
+    for nuc in nuclides.iter().take(MAX_ROWS) {
+        // writing to a `String` doesn't fail
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td><td>{}</td></tr>",
+            nuc.z,
+            nuc.n,
+            nuc.a(),
+            escape(&nuc.element),
+            escape(nuc.origin.as_deref().unwrap_or_default()),
+            value(Some(&nuc.mass_excess)),
+            value(nuc.binding_energy_per_a.as_ref()),
+            nuc.decay_mode.map_or("", |mode| mode.label()),
+            value(nuc.beta_decay_energy.as_ref()),
+            value(Some(&nuc.atomic_mass)),
+        );
+    }
+    if nuclides.len() > MAX_ROWS {
+        let _ = write!(
+            html,
+            r#"<tr><td colspan="10">… {} more</td></tr>"#,
+            nuclides.len() - MAX_ROWS
+        );
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn value(value: Option<&Value>) -> String {
+    match value {
+        Some(value) => format!(
+            "{} ± {}{}",
+            value.mean,
+            value.uncertainty,
+            if value.is_estimated { "#" } else { "" }
+        ),
+        None => String::new(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,343 @@
+//! A C interface, declared in `include/ame2020.h`.
+//!
+//! The header is generated with [cbindgen](https://crates.io/crates/cbindgen), with
+//! `cbindgen --output include/ame2020.h`. The library is built with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib`, which makes `libame2020.so`,
+//! or the equivalent for the platform.
+//!
+//! A table is read with [`ame2020_parse_file`], and freed with [`ame2020_free`]. Nuclides are
+//! looked up with [`ame2020_get`] or [`ame2020_get_index`], and their fields are read with the
+//! `ame2020_nuclide_*` functions. The pointers to nuclides are valid until the table is freed,
+//! and the strings are static.
+//!
+//! ```c
+//! #include "ame2020.h"
+//! #include <stdio.h>
+//!
+//! int main(void) {
+//!     Ame2020Table *table = ame2020_parse_file("mass.mas20");
+//!     if (!table) {
+//!         fprintf(stderr, "%s\n", ame2020_last_error());
+//!         return 1;
+//!     }
+//!     const Ame2020Nuclide *fe56 = ame2020_get(table, 26, 30);
+//!     if (fe56) {
+//!         Ame2020Value mass_excess = ame2020_nuclide_mass_excess(fe56);
+//!         printf("%s: %f keV\n", ame2020_nuclide_element(fe56), mass_excess.mean);
+//!     }
+//!     ame2020_free(table);
+//!     return 0;
+//! }
+//! ```
+use crate::{
+    dataset::{Dataset, Duplicates},
+    AmeError, Nuclide, Value,
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    ffi::{c_char, CStr, CString},
+    fs::File,
+    io::BufReader,
+    ptr,
+    sync::{Mutex, PoisonError},
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(err: &AmeError) {
+    // the messages don't have nul bytes, except from an invalid path
+    let message = CString::new(err.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// A value with a mean and uncertainty.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Ame2020Value {
+    /// The mean
+    pub mean: f64,
+    /// The uncertainty
+    pub uncertainty: f64,
+    /// Whether the value is estimated, rather than based on experimental data
+    pub is_estimated: bool,
+}
+
+impl From<&Value> for Ame2020Value {
+    fn from(value: &Value) -> Self {
+        Self {
+            mean: value.mean,
+            uncertainty: value.uncertainty,
+            is_estimated: value.is_estimated,
+        }
+    }
+}
+
+/// A nuclide, read with the `ame2020_nuclide_*` functions.
+///
+/// This is never constructed. A pointer to it is a pointer to a [`Nuclide`] in a table.
+pub struct Ame2020Nuclide {
+    _private: [u8; 0],
+}
+
+fn to_ffi(nuclide: &Nuclide) -> *const Ame2020Nuclide {
+    ptr::from_ref(nuclide).cast()
+}
+
+/// A table of nuclides, indexed by Z and N.
+pub struct Ame2020Table(Dataset);
+
+// returns `s` as a nul-terminated string that is never freed. each string is only allocated once,
+// and there are few of them, which are the element symbols, origins, and decay labels in the tables
+fn static_c_str(s: &str) -> &'static CStr {
+    static STRINGS: Mutex<BTreeMap<String, &'static CStr>> = Mutex::new(BTreeMap::new());
+
+    let mut strings = STRINGS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(c_str) = strings.get(s) {
+        return c_str;
+    }
+    // the strings are from text that was split on ASCII whitespace
+    let c_str = CString::new(s.replace('\0', "")).unwrap_or_default();
+    let c_str: &'static CStr = Box::leak(c_str.into_boxed_c_str());
+    strings.insert(s.to_string(), c_str);
+    c_str
+}
+
+/// Reads and parses the file at `path`.
+///
+/// Returns `NULL` if reading or parsing fails, with the error in [`ame2020_last_error`].
+///
+/// # Safety
+///
+/// `path` must be a valid, nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_parse_file(path: *const c_char) -> *mut Ame2020Table {
+    if path.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: `path` is a valid string, by the contract of the function
+    let path = unsafe { CStr::from_ptr(path) };
+    let result = path
+        .to_str()
+        .map_err(|_| AmeError::Io(std::io::ErrorKind::InvalidInput))
+        .and_then(|path| Ok(File::open(path)?))
+        .and_then(|file| Dataset::read(BufReader::new(file), Duplicates::Error));
+    match result {
+        Ok(dataset) => Box::into_raw(Box::new(Ame2020Table(dataset))),
+        Err(err) => {
+            set_last_error(&err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the message of the last error on this thread, or `NULL` if there hasn't been one.
+///
+/// The string is valid until the next error on this thread.
+#[no_mangle]
+pub extern "C" fn ame2020_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Frees `table`, which may be `NULL`.
+///
+/// # Safety
+///
+/// `table` must be from [`ame2020_parse_file`], and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_free(table: *mut Ame2020Table) {
+    if !table.is_null() {
+        // SAFETY: `table` is from `Box::into_raw`, by the contract of the function
+        drop(unsafe { Box::from_raw(table) });
+    }
+}
+
+/// Returns the number of nuclides in `table`.
+///
+/// # Safety
+///
+/// `table` must be a valid table.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_len(table: *const Ame2020Table) -> usize {
+    // SAFETY: `table` is valid, by the contract of the function
+    unsafe { table.as_ref() }.map_or(0, |table| table.0.len())
+}
+
+/// Returns the nuclide in `table` with `z` protons and `n` neutrons, or `NULL`.
+///
+/// # Safety
+///
+/// `table` must be a valid table.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_get(
+    table: *const Ame2020Table,
+    z: u32,
+    n: u32,
+) -> *const Ame2020Nuclide {
+    // SAFETY: `table` is valid, by the contract of the function
+    unsafe { table.as_ref() }
+        .and_then(|table| table.0.get(z, n))
+        .map_or(ptr::null(), to_ffi)
+}
+
+/// Returns the nuclide at `index` in `table`, in the order they were read, or `NULL`.
+///
+/// # Safety
+///
+/// `table` must be a valid table.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_get_index(
+    table: *const Ame2020Table,
+    index: usize,
+) -> *const Ame2020Nuclide {
+    // SAFETY: `table` is valid, by the contract of the function
+    unsafe { table.as_ref() }
+        .and_then(|table| table.0.nuclides().get(index))
+        .map_or(ptr::null(), to_ffi)
+}
+
+// SAFETY: the caller must pass a valid nuclide, which points to a `Nuclide`
+unsafe fn deref<'a>(nuclide: *const Ame2020Nuclide) -> &'a Nuclide {
+    unsafe { &*nuclide.cast::<Nuclide>() }
+}
+
+/// Returns the number of neutrons of `nuclide`.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_n(nuclide: *const Ame2020Nuclide) -> u32 {
+    unsafe { deref(nuclide) }.n
+}
+
+/// Returns the number of protons of `nuclide`.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_z(nuclide: *const Ame2020Nuclide) -> u32 {
+    unsafe { deref(nuclide) }.z
+}
+
+/// Returns the mass number of `nuclide`.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_a(nuclide: *const Ame2020Nuclide) -> u32 {
+    unsafe { deref(nuclide) }.a()
+}
+
+/// Returns the element symbol of `nuclide`.
+///
+/// The string is static.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_element(nuclide: *const Ame2020Nuclide) -> *const c_char {
+    static_c_str(&unsafe { deref(nuclide) }.element).as_ptr()
+}
+
+/// Returns the origin of the data of `nuclide`, or `NULL`.
+///
+/// The string is static.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_origin(nuclide: *const Ame2020Nuclide) -> *const c_char {
+    unsafe { deref(nuclide) }
+        .origin
+        .map_or(ptr::null(), |origin| static_c_str(&origin).as_ptr())
+}
+
+/// Returns the mass excess of `nuclide`, in keV.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_mass_excess(
+    nuclide: *const Ame2020Nuclide,
+) -> Ame2020Value {
+    (&unsafe { deref(nuclide) }.mass_excess).into()
+}
+
+/// Writes the binding energy per nucleon of `nuclide`, in keV, to `value`, and returns whether
+/// there is one.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide, and `value` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_binding_energy_per_a(
+    nuclide: *const Ame2020Nuclide,
+    value: *mut Ame2020Value,
+) -> bool {
+    let nuc = unsafe { deref(nuclide) };
+    unsafe { write_optional(nuc.binding_energy_per_a.as_ref(), value) }
+}
+
+/// Returns the label of the decay of `nuclide`, or `NULL`.
+///
+/// The string is static.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_decay_mode(
+    nuclide: *const Ame2020Nuclide,
+) -> *const c_char {
+    unsafe { deref(nuclide) }
+        .decay_mode
+        .map_or(ptr::null(), |mode| static_c_str(mode.label()).as_ptr())
+}
+
+/// Writes the beta decay energy of `nuclide`, in keV, to `value`, and returns whether there is
+/// one.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide, and `value` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_beta_decay_energy(
+    nuclide: *const Ame2020Nuclide,
+    value: *mut Ame2020Value,
+) -> bool {
+    let nuc = unsafe { deref(nuclide) };
+    unsafe { write_optional(nuc.beta_decay_energy.as_ref(), value) }
+}
+
+/// Returns the atomic mass of `nuclide`, in atomic mass units.
+///
+/// # Safety
+///
+/// `nuclide` must be a valid nuclide.
+#[no_mangle]
+pub unsafe extern "C" fn ame2020_nuclide_atomic_mass(
+    nuclide: *const Ame2020Nuclide,
+) -> Ame2020Value {
+    (&unsafe { deref(nuclide) }.atomic_mass).into()
+}
+
+// SAFETY: the caller must pass a `value` that is valid for writes
+unsafe fn write_optional(optional: Option<&Value>, value: *mut Ame2020Value) -> bool {
+    match optional {
+        Some(optional) => {
+            if !value.is_null() {
+                unsafe { value.write(optional.into()) };
+            }
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,677 @@
+//! Filtering nuclides with a small expression language, such as
+//! `z >= 82 && !estimated && s2n < 2000`.
+//!
+//! The names in an expression are:
+//!
+//! | Name | Value |
+//! |------|-------|
+//! | `z`, `n`, `a` | Z, N, and A |
+//! | `mass_excess`, `binding_energy_per_a`, `beta_decay_energy` | the values, in keV |
+//! | `atomic_mass` | the atomic mass, in atomic mass units |
+//! | `*_uncertainty` | the uncertainties of the values, such as `mass_excess_uncertainty` |
+//! | `sn`, `sp`, `s2n`, `s2p` | the separation energies, in keV, such as from [`Dataset::separation_energy`] |
+//! | `qa` | the Q-value of α decay, in keV |
+//! | `estimated` | whether any of the values are estimated |
+//! | `true`, `false` | |
+//!
+//! The operators are, from the highest precedence to the lowest:
+//!
+//! * unary `!` and `-`
+//! * `*` and `/`
+//! * `+` and `-`
+//! * comparisons: `<`, `<=`, `>`, `>=`, `==`, and `!=`
+//! * `&&`
+//! * `||`
+//!
+//! A value that isn't known, such as the separation energy of a nuclide whose neighbor isn't in
+//! the dataset, is NaN, so comparisons with it are false. The expression of a [`Filter`] must be a
+//! condition, so that `z + 1` is an error, but that of a [`Quantity`], such as for coloring a
+//! chart, must be a number.
+//!
+//! Filters can be combined with [`Filter::and`], [`Filter::or`], and `!`, such as with the common
+//! regions of the chart in [`Region`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{dataset::{Dataset, Duplicates}, filter::Filter};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let filter: Filter = "z >= 1 && !estimated".parse().unwrap();
+//! let hydrogen: Vec<_> = filter.apply(&dataset).collect();
+//! assert_eq!(hydrogen, [dataset.get(1, 0).unwrap()]);
+//! ```
+use crate::{dataset::Dataset, reaction::Reaction, DecayMode, Nuclide, ValueGetter, VALUE_COLUMNS};
+use std::{ops::Not, str::FromStr};
+use thiserror::Error;
+
+/// The error from parsing an expression.
+///
+/// The positions are byte offsets in the expression.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum FilterError {
+    /// A name that isn't one of those in the [module documentation](self)
+    #[error("unknown name `{name}` at {position}")]
+    UnknownName {
+        /// The position of the name
+        position: usize,
+        /// The name
+        name: String,
+    },
+    /// A character or the end of the expression that wasn't expected
+    #[error("unexpected {found} at {position}")]
+    Unexpected {
+        /// The position of what was found
+        position: usize,
+        /// What was found
+        found: String,
+    },
+    /// An operand or the expression that has the wrong type, such as `!z`
+    #[error("expected a {expected} at {position}")]
+    Type {
+        /// The position of the operand
+        position: usize,
+        /// The type that was expected, `number` or `condition`
+        expected: &'static str,
+    },
+}
+
+/// A parsed expression, for filtering nuclides.
+#[derive(Clone, Debug)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    /// Parses the expression in `s`.
+    ///
+    /// # Errors
+    ///
+    /// If the expression isn't valid, or isn't a condition, an error is returned.
+    pub fn parse(s: &str) -> Result<Self, FilterError> {
+        Ok(Self {
+            expr: parse(s, Type::Bool)?,
+        })
+    }
+
+    /// Returns whether `nuc` matches the expression, where the derived quantities, such as the
+    /// separation energies, are from `dataset`.
+    #[must_use]
+    pub fn matches(&self, dataset: &Dataset, nuc: &Nuclide) -> bool {
+        self.expr.eval(dataset, nuc) == Val::Bool(true)
+    }
+
+    /// Returns an iterator over the nuclides in `dataset` that match the expression.
+    pub fn apply<'a>(&'a self, dataset: &'a Dataset) -> impl Iterator<Item = &'a Nuclide> + 'a {
+        dataset.iter().filter(|nuc| self.matches(dataset, nuc))
+    }
+
+    /// Returns the filter of the nuclides in `region`.
+    #[must_use]
+    pub fn region(region: Region) -> Self {
+        match Self::parse(region.expression()) {
+            Ok(filter) => filter,
+            Err(_) => unreachable!("the expressions of the regions are valid"),
+        }
+    }
+
+    /// Returns the filter of the nuclides that match both this and `other`, as with `&&`.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        Self {
+            expr: Expr::Binary(Op::And, Box::new(self.expr), Box::new(other.expr)),
+        }
+    }
+
+    /// Returns the filter of the nuclides that match either this or `other`, as with `||`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            expr: Expr::Binary(Op::Or, Box::new(self.expr), Box::new(other.expr)),
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Returns the filter of the nuclides that don't match, as with `!`.
+impl Not for Filter {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self {
+            expr: Expr::Not(Box::new(self.expr)),
+        }
+    }
+}
+
+impl From<Region> for Filter {
+    fn from(region: Region) -> Self {
+        Self::region(region)
+    }
+}
+
+/// A common region of the chart, for [`Filter::region`].
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::filter::{Filter, Region};
+///
+/// let filter = Filter::region(Region::SdShell).and("!estimated".parse().unwrap());
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Region {
+    /// The light nuclei, with A up to 20, `a <= 20`
+    Light,
+    /// The sd shell, with Z and N from 8 to 20
+    SdShell,
+    /// The rare earths, the lanthanides from lanthanum to lutetium, with Z from 57 to 71
+    RareEarth,
+    /// The elements beyond uranium, `z > 92`
+    Transuranium,
+    /// The superheavy elements, from rutherfordium, `z >= 104`
+    Superheavy,
+    /// The nuclides with N and Z that differ by at most 2
+    NearNEqualsZ,
+}
+
+impl Region {
+    /// All of the regions.
+    pub const ALL: [Self; 6] = [
+        Self::Light,
+        Self::SdShell,
+        Self::RareEarth,
+        Self::Transuranium,
+        Self::Superheavy,
+        Self::NearNEqualsZ,
+    ];
+
+    /// Returns the expression of the region, in the language in the
+    /// [module documentation](self).
+    #[must_use]
+    pub fn expression(self) -> &'static str {
+        match self {
+            Self::Light => "a <= 20",
+            Self::SdShell => "z >= 8 && z <= 20 && n >= 8 && n <= 20",
+            Self::RareEarth => "z >= 57 && z <= 71",
+            Self::Transuranium => "z > 92",
+            Self::Superheavy => "z >= 104",
+            Self::NearNEqualsZ => "n - z <= 2 && z - n <= 2",
+        }
+    }
+}
+
+/// A parsed expression for a quantity, such as `s2n - s2p`, rather than a condition.
+#[derive(Clone, Debug)]
+pub struct Quantity {
+    expr: Expr,
+}
+
+impl Quantity {
+    /// Parses the expression in `s`.
+    ///
+    /// # Errors
+    ///
+    /// If the expression isn't valid, or isn't a number, an error is returned.
+    pub fn parse(s: &str) -> Result<Self, FilterError> {
+        Ok(Self {
+            expr: parse(s, Type::Number)?,
+        })
+    }
+
+    /// Returns the value of the expression for `nuc`, where the derived quantities are from
+    /// `dataset`.
+    ///
+    /// If the value isn't known, or isn't finite, `None` is returned.
+    #[must_use]
+    pub fn eval(&self, dataset: &Dataset, nuc: &Nuclide) -> Option<f64> {
+        match self.expr.eval(dataset, nuc) {
+            Val::Number(x) => Some(x).filter(|x| x.is_finite()),
+            Val::Bool(_) => unreachable!("the types are checked when parsing"),
+        }
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+// parses the expression in `s`, which must have the type `ty`
+fn parse(s: &str, ty: Type) -> Result<Expr, FilterError> {
+    let mut parser = Parser {
+        tokens: tokenize(s)?,
+        next: 0,
+        end: s.len(),
+    };
+    let (expr, expr_ty, position) = parser.or()?;
+    if let Some(&(position, ref token)) = parser.tokens.get(parser.next) {
+        return Err(FilterError::Unexpected {
+            position,
+            found: token.describe(),
+        });
+    }
+    expect(expr_ty, ty, position)?;
+    Ok(expr)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Type {
+    Number,
+    Bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Val {
+    Number(f64),
+    Bool(bool),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Var {
+    Z,
+    N,
+    A,
+    Mean(ValueGetter),
+    Uncertainty(ValueGetter),
+    // the protons and neutrons that are removed
+    Separation(u32, u32),
+    AlphaQ,
+    Estimated,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Op {
+    Or,
+    And,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Literal(Val),
+    Var(Var),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Var {
+    fn from_name(name: &str) -> Option<Self> {
+        let var = match name {
+            "z" => Self::Z,
+            "n" => Self::N,
+            "a" => Self::A,
+            "sn" => Self::Separation(0, 1),
+            "sp" => Self::Separation(1, 0),
+            "s2n" => Self::Separation(0, 2),
+            "s2p" => Self::Separation(2, 0),
+            "qa" => Self::AlphaQ,
+            "estimated" => Self::Estimated,
+            _ => {
+                return VALUE_COLUMNS.iter().find_map(|&(value, f)| {
+                    if name == value {
+                        Some(Self::Mean(f))
+                    } else if name.strip_suffix("_uncertainty") == Some(value) {
+                        Some(Self::Uncertainty(f))
+                    } else {
+                        None
+                    }
+                })
+            }
+        };
+        Some(var)
+    }
+
+    fn eval(self, dataset: &Dataset, nuc: &Nuclide) -> Val {
+        let number = |value: Option<f64>| Val::Number(value.unwrap_or(f64::NAN));
+        match self {
+            Self::Z => Val::Number(f64::from(nuc.z)),
+            Self::N => Val::Number(f64::from(nuc.n)),
+            Self::A => Val::Number(f64::from(nuc.a())),
+            Self::Mean(f) => number(f(nuc).map(|value| value.mean)),
+            Self::Uncertainty(f) => number(f(nuc).map(|value| value.uncertainty)),
+            Self::Separation(protons, neutrons) => number(
+                dataset
+                    .separation_energy(nuc.z, nuc.n, protons, neutrons)
+                    .map(|value| value.mean),
+            ),
+            Self::AlphaQ => number(
+                Reaction::decay(nuc.z, nuc.n, DecayMode::Alpha)
+                    .and_then(|decay| dataset.reaction_q_value(&decay))
+                    .map(|value| value.mean),
+            ),
+            Self::Estimated => Val::Bool(nuc.is_estimated()),
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, dataset: &Dataset, nuc: &Nuclide) -> Val {
+        match self {
+            Self::Literal(val) => *val,
+            Self::Var(var) => var.eval(dataset, nuc),
+            Self::Not(expr) => match expr.eval(dataset, nuc) {
+                Val::Bool(b) => Val::Bool(!b),
+                Val::Number(_) => unreachable!("the types are checked when parsing"),
+            },
+            Self::Neg(expr) => match expr.eval(dataset, nuc) {
+                Val::Number(x) => Val::Number(-x),
+                Val::Bool(_) => unreachable!("the types are checked when parsing"),
+            },
+            // `&&` and `||` short-circuit, so that the separation energies aren't always needed
+            Self::Binary(Op::And, left, right) => Val::Bool(
+                left.eval(dataset, nuc) == Val::Bool(true)
+                    && right.eval(dataset, nuc) == Val::Bool(true),
+            ),
+            Self::Binary(Op::Or, left, right) => Val::Bool(
+                left.eval(dataset, nuc) == Val::Bool(true)
+                    || right.eval(dataset, nuc) == Val::Bool(true),
+            ),
+            Self::Binary(op, left, right) => {
+                match (*op, left.eval(dataset, nuc), right.eval(dataset, nuc)) {
+                    (Op::Eq, left, right) => Val::Bool(left == right),
+                    (Op::Ne, left, right) => Val::Bool(left != right),
+                    (op, Val::Number(x), Val::Number(y)) => match op {
+                        Op::Lt => Val::Bool(x < y),
+                        Op::Le => Val::Bool(x <= y),
+                        Op::Gt => Val::Bool(x > y),
+                        Op::Ge => Val::Bool(x >= y),
+                        Op::Add => Val::Number(x + y),
+                        Op::Sub => Val::Number(x - y),
+                        Op::Mul => Val::Number(x * y),
+                        Op::Div => Val::Number(x / y),
+                        Op::Or | Op::And | Op::Eq | Op::Ne => unreachable!("matched above"),
+                    },
+                    _ => unreachable!("the types are checked when parsing"),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Op(Op),
+    Not,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Number(x) => format!("number {x}"),
+            Self::Name(name) => format!("name `{name}`"),
+            Self::Op(op) => format!("operator `{}`", op.symbol()),
+            Self::Not => "`!`".to_string(),
+            Self::Open => "`(`".to_string(),
+            Self::Close => "`)`".to_string(),
+        }
+    }
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Or => "||",
+            Self::And => "&&",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let mut followed_by = |next: char| chars.next_if(|&(_, c)| c == next).is_some();
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Sub),
+            '*' => Token::Op(Op::Mul),
+            '/' => Token::Op(Op::Div),
+            '<' if followed_by('=') => Token::Op(Op::Le),
+            '<' => Token::Op(Op::Lt),
+            '>' if followed_by('=') => Token::Op(Op::Ge),
+            '>' => Token::Op(Op::Gt),
+            '!' if followed_by('=') => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '=' if followed_by('=') => Token::Op(Op::Eq),
+            '&' if followed_by('&') => Token::Op(Op::And),
+            '|' if followed_by('|') => Token::Op(Op::Or),
+            c if c.is_ascii_digit() || c == '.' => {
+                let end = s[position..]
+                    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                    .map_or(s.len(), |i| position + i);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+                let number = &s[position..end];
+                Token::Number(number.parse().map_err(|_| FilterError::Unexpected {
+                    position,
+                    found: format!("`{number}`"),
+                })?)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let end = s[position..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .map_or(s.len(), |i| position + i);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+                Token::Name(s[position..end].to_string())
+            }
+            c => {
+                return Err(FilterError::Unexpected {
+                    position,
+                    found: format!("`{c}`"),
+                })
+            }
+        };
+        tokens.push((position, token));
+    }
+    Ok(tokens)
+}
+
+fn expect(ty: Type, expected: Type, position: usize) -> Result<(), FilterError> {
+    if ty == expected {
+        Ok(())
+    } else {
+        Err(FilterError::Type {
+            position,
+            expected: match expected {
+                Type::Number => "number",
+                Type::Bool => "condition",
+            },
+        })
+    }
+}
+
+// a recursive descent parser, where each level returns the expression, its type, and its position
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // the length of the expression, for errors at the end
+    end: usize,
+}
+
+type Parsed = Result<(Expr, Type, usize), FilterError>;
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    // takes the next token if it is one of `ops`
+    fn op(&mut self, ops: &[Op]) -> Option<Op> {
+        let op = match self.peek() {
+            Some(&Token::Op(op)) if ops.contains(&op) => op,
+            _ => return None,
+        };
+        self.next += 1;
+        Some(op)
+    }
+
+    // parses operands of `ty` separated by `ops`, which are left associative
+    fn binary(&mut self, ops: &[Op], ty: Type, operand: fn(&mut Self) -> Parsed) -> Parsed {
+        let (mut expr, mut expr_ty, position) = operand(self)?;
+        while let Some(op) = self.op(ops) {
+            expect(expr_ty, ty, position)?;
+            let (right, right_ty, right_position) = operand(self)?;
+            expect(right_ty, ty, right_position)?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+            expr_ty = ty;
+        }
+        Ok((expr, expr_ty, position))
+    }
+
+    fn or(&mut self) -> Parsed {
+        self.binary(&[Op::Or], Type::Bool, Self::and)
+    }
+
+    fn and(&mut self) -> Parsed {
+        self.binary(&[Op::And], Type::Bool, Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Parsed {
+        let (left, left_ty, position) = self.sum()?;
+        let Some(op) = self.op(&[Op::Lt, Op::Le, Op::Gt, Op::Ge, Op::Eq, Op::Ne]) else {
+            return Ok((left, left_ty, position));
+        };
+        let (right, right_ty, right_position) = self.sum()?;
+        if matches!(op, Op::Eq | Op::Ne) {
+            expect(right_ty, left_ty, right_position)?;
+        } else {
+            expect(left_ty, Type::Number, position)?;
+            expect(right_ty, Type::Number, right_position)?;
+        }
+        Ok((
+            Expr::Binary(op, Box::new(left), Box::new(right)),
+            Type::Bool,
+            position,
+        ))
+    }
+
+    fn sum(&mut self) -> Parsed {
+        self.binary(&[Op::Add, Op::Sub], Type::Number, Self::product)
+    }
+
+    fn product(&mut self) -> Parsed {
+        self.binary(&[Op::Mul, Op::Div], Type::Number, Self::unary)
+    }
+
+    fn unary(&mut self) -> Parsed {
+        let position = self.position();
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next += 1;
+                let (expr, ty, operand) = self.unary()?;
+                expect(ty, Type::Bool, operand)?;
+                Ok((Expr::Not(Box::new(expr)), Type::Bool, position))
+            }
+            Some(Token::Op(Op::Sub)) => {
+                self.next += 1;
+                let (expr, ty, operand) = self.unary()?;
+                expect(ty, Type::Number, operand)?;
+                Ok((Expr::Neg(Box::new(expr)), Type::Number, position))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Parsed {
+        let position = self.position();
+        let Some((_, token)) = self.tokens.get(self.next).cloned() else {
+            return Err(FilterError::Unexpected {
+                position,
+                found: "end".to_string(),
+            });
+        };
+        self.next += 1;
+        match token {
+            Token::Number(x) => Ok((Expr::Literal(Val::Number(x)), Type::Number, position)),
+            Token::Name(name) => match name.as_str() {
+                "true" => Ok((Expr::Literal(Val::Bool(true)), Type::Bool, position)),
+                "false" => Ok((Expr::Literal(Val::Bool(false)), Type::Bool, position)),
+                _ => {
+                    let var =
+                        Var::from_name(&name).ok_or(FilterError::UnknownName { position, name })?;
+                    let ty = if matches!(var, Var::Estimated) {
+                        Type::Bool
+                    } else {
+                        Type::Number
+                    };
+                    Ok((Expr::Var(var), ty, position))
+                }
+            },
+            Token::Open => {
+                let (expr, ty, _) = self.or()?;
+                match self.peek() {
+                    Some(Token::Close) => {
+                        self.next += 1;
+                        Ok((expr, ty, position))
+                    }
+                    _ => Err(self.unexpected()),
+                }
+            }
+            token => {
+                self.next -= 1;
+                Err(FilterError::Unexpected {
+                    position,
+                    found: token.describe(),
+                })
+            }
+        }
+    }
+
+    // the position of the next token, or the end
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map_or(self.end, |&(position, _)| position)
+    }
+
+    fn unexpected(&self) -> FilterError {
+        FilterError::Unexpected {
+            position: self.position(),
+            found: self
+                .peek()
+                .map_or_else(|| "end".to_string(), Token::describe),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Checking that data is an exact copy of a known file.
+//!
+//! Files are identified by their SHA-256 digest, which is computed with
+//! [sha2](https://crates.io/crates/sha2). A digest of the official `mass.mas20` isn't included;
+//! compute one from a trusted copy with [`sha256`], and check other copies against it with
+//! [`verify`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::fingerprint::{sha256, verify};
+//!
+//! let data: &[u8] = b"1\n1\n";
+//! let digest = sha256(data).unwrap();
+//! assert!(verify(data, &digest).unwrap());
+//! assert!(!verify(&b"1\n1\n0"[..], &digest).unwrap());
+//! ```
+use crate::AmeError;
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+/// Returns the SHA-256 digest of everything in `reader`.
+///
+/// # Errors
+///
+/// If reading fails, an error is returned.
+pub fn sha256<R: Read>(mut reader: R) -> Result<[u8; 32], AmeError> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut reader, &mut hasher)?;
+    Ok(hasher.finalize().into())
+}
+
+/// Returns whether the SHA-256 digest of everything in `reader` is `expected`.
+///
+/// # Errors
+///
+/// If reading fails, an error is returned.
+pub fn verify<R: Read>(reader: R, expected: &[u8; 32]) -> Result<bool, AmeError> {
+    Ok(sha256(reader)? == *expected)
+}
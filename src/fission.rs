@@ -0,0 +1,67 @@
+//! The fissility of nuclides, and the Q-value of splitting them in half.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::fission;
+//!
+//! // 238U is less fissile than 252Cf
+//! assert!(fission::fissility(92, 146) < fission::fissility(98, 154));
+//! let split = fission::symmetric(92, 146).unwrap();
+//! assert_eq!(split.products, [(46, 73), (46, 73)]);
+//! ```
+use crate::reaction::Reaction;
+#[cfg(feature = "std")]
+use crate::{dataset::Dataset, Value};
+use alloc::vec;
+
+/// The critical Z²/A of the liquid drop model, for N = Z, from Myers and Swiatecki, Nucl. Phys.
+/// 81, 1 (1966)
+pub const CRITICAL_Z2_OVER_A: f64 = 50.883;
+
+/// The coefficient of the asymmetry, I = (N-Z)/A, in the critical Z²/A
+pub const ASYMMETRY_COEFFICIENT: f64 = 1.7826;
+
+/// Returns the fissility parameter x of the nuclide with `z` protons and `n` neutrons.
+///
+/// It is Z²/A, the ratio of the Coulomb and surface energies, divided by its critical value at
+/// which a liquid drop has no barrier against fission,
+/// [`CRITICAL_Z2_OVER_A`] (1 - [`ASYMMETRY_COEFFICIENT`] I²). If A is zero, it is zero.
+#[must_use]
+pub fn fissility(z: u32, n: u32) -> f64 {
+    let (z, n) = (f64::from(z), f64::from(n));
+    let a = z + n;
+    if a == 0.0 {
+        return 0.0;
+    }
+    let asymmetry = (n - z) / a;
+    (z * z / a) / (CRITICAL_Z2_OVER_A * (1.0 - ASYMMETRY_COEFFICIENT * asymmetry * asymmetry))
+}
+
+/// Returns the fission of the nuclide with `z` protons and `n` neutrons into two equal fragments.
+///
+/// The fragments have half of A, and half of Z. If either is odd, the second fragment has the
+/// extra nucleon or proton. If there are fewer than two nucleons, `None` is returned.
+#[must_use]
+pub fn symmetric(z: u32, n: u32) -> Option<Reaction> {
+    let a = z + n;
+    if a < 2 {
+        return None;
+    }
+    let first = (z / 2, a / 2 - z / 2);
+    Some(Reaction {
+        initial: vec![(z, n)],
+        products: vec![first, (z - first.0, n - first.1)],
+        positrons: 0,
+    })
+}
+
+/// Returns the Q-value of the [`symmetric`] fission of the nuclide with `z` protons and `n`
+/// neutrons, in keV.
+///
+/// If a nuclide isn't in the dataset, or there are fewer than two nucleons, `None` is returned.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn symmetric_q_value(dataset: &Dataset, z: u32, n: u32) -> Option<Value> {
+    dataset.reaction_q_value(&symmetric(z, n)?)
+}
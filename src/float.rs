@@ -0,0 +1,74 @@
+use core::{fmt::Debug, num::ParseFloatError};
+
+/// A floating-point type that the values can be parsed into, which is `f64`, the default, or
+/// `f32`.
+///
+/// The iterators parse into any `Float`, such as with [`SliceIter::with_float`], so a table of
+/// `f32`s is parsed directly, without a table of `f64`s first. The derived quantities, such as in
+/// [`dataset`], are computed in `f64`, which the values can be converted to with
+/// [`Nuclide::map`] and [`Float::to_f64`].
+///
+/// This is sealed, so it can't be implemented outside of this crate.
+///
+/// [`SliceIter::with_float`]: crate::SliceIter::with_float
+/// [`dataset`]: crate::dataset
+/// [`Nuclide::map`]: crate::Nuclide::map
+pub trait Float:
+    Copy + PartialOrd + Default + Debug + Send + Sync + 'static + sealed::Sealed
+{
+    /// Converts the value to `f64`, which is exact.
+    fn to_f64(self) -> f64;
+}
+
+mod sealed {
+    use core::num::ParseFloatError;
+
+    // the operations the parser needs, which aren't part of the public trait
+    pub trait Sealed: Sized {
+        fn parse(s: &str) -> Result<Self, ParseFloatError>;
+        fn is_finite(&self) -> bool;
+        fn is_negative(&self) -> bool;
+        // `int` u, plus `micro` micro-u
+        fn from_micro(int: u16, micro: Self) -> Self;
+    }
+}
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl Float for $t {
+            fn to_f64(self) -> f64 {
+                self.into()
+            }
+        }
+
+        impl sealed::Sealed for $t {
+            #[cfg(not(feature = "fast-float"))]
+            fn parse(s: &str) -> Result<Self, ParseFloatError> {
+                s.parse()
+            }
+
+            #[cfg(feature = "fast-float")]
+            fn parse(s: &str) -> Result<Self, ParseFloatError> {
+                // on failure, parse again with std to get its error type. it accepts the same
+                // inputs.
+                fast_float2::parse(s).or_else(|_| s.parse())
+            }
+
+            fn is_finite(&self) -> bool {
+                <$t>::is_finite(*self)
+            }
+
+            fn is_negative(&self) -> bool {
+                // `-0.0` is allowed, like `0.0`
+                *self < 0.0
+            }
+
+            fn from_micro(int: u16, micro: Self) -> Self {
+                <$t>::from(int) + micro * 1e-6
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
@@ -0,0 +1,60 @@
+//! Values laid out on a chart of nuclides, as an [ndarray](https://crates.io/crates/ndarray)
+//! [`Array2`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::dataset::{Dataset, Duplicates};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let grid = dataset.grid(|nuc| Some(nuc.mass_excess.mean));
+//! assert_eq!(grid.dim(), (2, 2));
+//! assert!((grid[[1, 0]] - 8071.318_06).abs() < 1e-9);
+//! assert!(grid[[1, 1]].is_nan());
+//! ```
+use crate::{dataset::Dataset, Nuclide};
+use ndarray::Array2;
+
+impl Dataset {
+    /// Returns `quantity` of each nuclide, in an array indexed by `[[n, z]]`.
+    ///
+    /// The array is large enough for every nuclide in the dataset. The elements for nuclides that
+    /// aren't in the dataset, or for which `quantity` returns `None`, are NaN.
+    ///
+    /// `quantity` can use the dataset, such as for S<sub>2n</sub>:
+    ///
+    /// ```
+    /// use ame2020::dataset::{Dataset, Duplicates};
+    ///
+    /// let dataset = Dataset::from_slice(br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+    ///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014
+    /// 0  0    1    1    2 H         13135.722895    0.000015   1112.2831     0.0002  B-      *                    2 014101.777844    0.000015
+    /// 0  1    2    1    3 H         14949.81090     0.00008    2827.2654     0.0003  B-     18.59202    0.00006   3 016049.28132     0.00008", Duplicates::Error).unwrap();
+    /// let s2n = dataset.grid(|nuc| {
+    ///     let energy = dataset.separation_energy(nuc.z, nuc.n, 0, 2)?;
+    ///     Some(energy.mean)
+    /// });
+    /// // only 3H has both neutrons to separate
+    /// assert!((s2n[[2, 1]] - 8_481.797).abs() < 1e-3);
+    /// assert!(s2n[[1, 1]].is_nan());
+    /// ```
+    #[must_use]
+    pub fn grid(&self, mut quantity: impl FnMut(&Nuclide) -> Option<f64>) -> Array2<f64> {
+        let len = |max: Option<u32>| max.map_or(0, |max| max as usize + 1);
+        let n_len = len(self.iter().map(|nuc| nuc.n).max());
+        let z_len = len(self.iter().map(|nuc| nuc.z).max());
+        let mut grid = Array2::from_elem((n_len, z_len), f64::NAN);
+        for nuc in self {
+            if let Some(value) = quantity(nuc) {
+                grid[[nuc.n as usize, nuc.z as usize]] = value;
+            }
+        }
+        grid
+    }
+}
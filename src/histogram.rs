@@ -0,0 +1,140 @@
+//! Binning per-nuclide quantities, such as residuals or uncertainties.
+//!
+//! A [`Histogram`] has bins between edges, which are `[low, high)`, except for the last, which
+//! includes its upper edge, like `numpy.histogram`. Numbers outside of the edges are counted
+//! separately.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{histogram::Histogram, parse_str};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! let mut histogram = Histogram::uniform(0.0, 10_000.0, 2).unwrap();
+//! histogram.extend(data.iter().map(|nuc| nuc.mass_excess.mean));
+//! assert_eq!(histogram.counts, [0, 2]);
+//! assert_eq!(histogram.centers(), [2_500.0, 7_500.0]);
+//! ```
+use crate::Nuclide;
+use alloc::{vec, vec::Vec};
+
+/// Counts of numbers between edges.
+///
+/// There are always at least two edges, so there is no `Default`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Histogram {
+    // the edges of the bins, which are increasing
+    edges: Vec<f64>,
+    /// The count of each bin, between `edges[i]` and `edges[i + 1]`
+    pub counts: Vec<usize>,
+    /// The count of numbers below the first edge
+    pub underflow: usize,
+    /// The count of numbers above the last edge
+    pub overflow: usize,
+    /// The count of NaNs, which aren't in any bin
+    pub nan: usize,
+}
+
+impl Histogram {
+    /// Returns an empty histogram with bins between `edges`.
+    ///
+    /// If there are fewer than two edges, or they aren't finite and strictly increasing, `None` is
+    /// returned.
+    #[must_use]
+    pub fn new(edges: Vec<f64>) -> Option<Self> {
+        let valid = edges.len() >= 2
+            && edges.iter().all(|edge| edge.is_finite())
+            && edges.windows(2).all(|pair| pair[0] < pair[1]);
+        valid.then(|| Self {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+            nan: 0,
+        })
+    }
+
+    /// Returns an empty histogram with `bins` bins of the same width between `min` and `max`.
+    ///
+    /// If `bins` is zero, or `min` and `max` aren't finite with `min < max`, `None` is returned.
+    #[must_use]
+    pub fn uniform(min: f64, max: f64, bins: u32) -> Option<Self> {
+        let width = (max - min) / f64::from(bins);
+        let edges = (0..=bins)
+            .map(|i| {
+                if i == bins {
+                    max
+                } else {
+                    min + f64::from(i) * width
+                }
+            })
+            .collect();
+        Self::new(edges)
+    }
+
+    /// Returns the edges of the bins.
+    #[must_use]
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the center of each bin.
+    #[must_use]
+    pub fn centers(&self) -> Vec<f64> {
+        self.edges
+            .windows(2)
+            .map(|pair| f64::midpoint(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Counts `x` in its bin.
+    pub fn add(&mut self, x: f64) {
+        let last = self.edges.len() - 1;
+        if x.is_nan() {
+            self.nan += 1;
+        } else if x < self.edges[0] {
+            self.underflow += 1;
+        } else if x > self.edges[last] {
+            self.overflow += 1;
+        } else {
+            // the bin whose lower edge is the last one at or below `x`, where the last edge is
+            // in the last bin
+            let bin = self.edges.partition_point(|&edge| edge <= x).min(last);
+            self.counts[bin - 1] += 1;
+        }
+    }
+}
+
+impl Extend<f64> for Histogram {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+/// Bins `quantity` of each of `nuclides` between `edges`, leaving out the nuclides it is `None`
+/// for.
+///
+/// If the edges are invalid, as in [`Histogram::new`], `None` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::histogram::histogram;
+///
+/// let histogram = histogram(&[], |nuc| Some(nuc.mass_excess.uncertainty), vec![0.0, 1.0]);
+/// assert_eq!(histogram.unwrap().counts, [0]);
+/// ```
+pub fn histogram<'a>(
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+    quantity: impl Fn(&Nuclide) -> Option<f64>,
+    edges: Vec<f64>,
+) -> Option<Histogram> {
+    let mut histogram = Histogram::new(edges)?;
+    histogram.extend(nuclides.into_iter().filter_map(quantity));
+    Some(histogram)
+}
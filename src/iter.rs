@@ -0,0 +1,272 @@
+use crate::{parser::Parser, AmeError, Experimental, Float, Nuclide, ParseOptions, Warning};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, StdinLock},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::ControlFlow,
+    path::Path,
+};
+
+/// An iterator that reads AME2020 data.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::Iter;
+/// use std::io::Cursor;
+///
+/// // `Cursor` is a type that implements `BufRead`.
+/// // Consider using `BufReader` if you have a `File`.
+/// let data = Cursor::new(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047");
+/// let mut iter = Iter::new(data);
+/// assert!(iter.next().is_some());
+/// assert!(iter.next().is_none());
+///
+/// ```
+///
+/// The values are `f64`, unless another [`Float`] is chosen with [`with_float`][Self::with_float].
+///
+/// # Errors
+///
+/// If a line fails to parse, [`next`][Self::next] will return `Some(Err)`,
+/// and calling `next` again continues with the following line.
+///
+/// If there is any other reading error, `next` will return `Some(Err)`, and the iterator is
+/// finished. Once `next` has returned `None`, it will always return `None`.
+pub struct Iter<R: BufRead, F = f64> {
+    reader: R,
+    // reused between lines, to avoid allocating for each one
+    buf: Vec<u8>,
+    parser: Parser,
+    is_finished: bool,
+    progress: Progress,
+    on_progress: Option<Box<dyn FnMut(Progress) + Send>>,
+    // a function pointer, so the iterator is `Send`, `Sync`, and `Unpin` whatever `F` is
+    float: PhantomData<fn() -> F>,
+}
+
+/// How much of the data an [`Iter`] has read.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub struct Progress {
+    /// The number of bytes read, including line endings
+    pub bytes: u64,
+    /// The number of nuclides returned
+    pub nuclides: usize,
+}
+
+impl<R: BufRead> Iter<R> {
+    /// Creates a new `Iter` from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    /// Creates a new `Iter` from `reader`, which parses according to `options`.
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        Self::with_float(reader, options)
+    }
+}
+
+impl<R: BufRead, F: Float> Iter<R, F> {
+    /// Creates a new `Iter` from `reader`, which parses according to `options`, with the values as
+    /// `F`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::{Iter, Nuclide, ParseOptions};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047");
+    /// let data: Vec<Nuclide<f32>> = Iter::with_float(reader, ParseOptions::default())
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(data[0].mass_excess.mean, 8_071.318);
+    /// ```
+    pub fn with_float(reader: R, options: ParseOptions) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            parser: Parser::new(options),
+            is_finished: false,
+            progress: Progress::default(),
+            on_progress: None,
+            float: PhantomData,
+        }
+    }
+
+    /// Calls `f` with the progress after each line is read.
+    ///
+    /// This is useful for showing progress when the data is large or slow to read. The total size
+    /// isn't known by the iterator, but the size of a file can be compared with
+    /// [`Progress::bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::Iter;
+    /// use std::{io::Cursor, sync::mpsc};
+    ///
+    /// let reader = Cursor::new(r"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047");
+    /// let (sender, receiver) = mpsc::channel();
+    /// let iter = Iter::new(reader).on_progress(move |progress| sender.send(progress).unwrap());
+    /// assert_eq!(iter.count(), 1);
+    /// let last = receiver.iter().last().unwrap();
+    /// assert_eq!(last.nuclides, 1);
+    /// ```
+    #[must_use]
+    pub fn on_progress(mut self, f: impl FnMut(Progress) + Send + 'static) -> Self {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+
+    /// Returns how much of the data has been read so far.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// Skips lines that fail to parse, instead of returning errors for them.
+    ///
+    /// The errors for the skipped lines are available from [`skipped`][Self::skipped]. Other
+    /// reading errors are still returned.
+    #[must_use]
+    pub fn lenient(mut self) -> Self {
+        self.parser.set_lenient(true);
+        self
+    }
+
+    /// Yields only the experimental data, with estimated values as `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::Iter;
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    /// 1
+    /// 0  2    3    1    4 H    -n   24621.129     100.000      1720.4491    25.0000  B-  22196.2131   100.0000    4 026431.867     107.354
+    ///   -3    0    3    3 Li  -pp   28667#       2000#        -2267#       667#      B-      *                    3 030775#       2147#");
+    /// let data = Iter::new(reader).experimental().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert!(data[0].mass_excess.is_some());
+    /// assert!(data[1].mass_excess.is_none());
+    /// ```
+    #[must_use]
+    pub fn experimental(self) -> Experimental<Self> {
+        Experimental(self)
+    }
+
+    /// Returns the errors for the lines that have been skipped so far, in order.
+    ///
+    /// Lines are only skipped if [`lenient`][Self::lenient] was used.
+    pub fn skipped(&self) -> &[AmeError] {
+        self.parser.skipped()
+    }
+
+    /// Returns the warnings for the lines that have been read so far, in order.
+    #[must_use]
+    pub fn warnings(&self) -> &[Warning] {
+        self.parser.warnings()
+    }
+
+    /// Returns the number of the most recent line read, starting from 1.
+    ///
+    /// After an item is returned, this is the line it was on.
+    #[must_use]
+    pub fn line_number(&self) -> usize {
+        self.parser.line_number()
+    }
+}
+
+impl Iter<BufReader<File>> {
+    /// Opens the file at `path`, and creates a new `Iter` from it.
+    ///
+    /// # Errors
+    ///
+    /// If the file fails to open, an error is returned.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AmeError> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl Iter<StdinLock<'static>> {
+    /// Creates a new `Iter` that reads from the standard input, so that the data can be piped in,
+    /// such as from `curl`.
+    ///
+    /// The standard input is locked until the `Iter` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), ame2020::AmeError> {
+    /// use ame2020::{Iter, Nuclide};
+    ///
+    /// let data: Vec<Nuclide> = Iter::from_stdin().collect::<Result<_, _>>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_stdin() -> Self {
+        Self::new(io::stdin().lock())
+    }
+}
+
+impl<R: BufRead, F: Float> Iterator for Iter<R, F> {
+    type Item = Result<Nuclide<F>, AmeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished || self.parser.is_stopped() {
+            return None;
+        }
+        loop {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => {
+                    self.is_finished = true;
+                    return None;
+                }
+                Ok(len) => {
+                    // strip the line ending the same way as `BufRead::lines`
+                    let line = match self.buf.strip_suffix(b"\n") {
+                        Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+                        None => &self.buf,
+                    };
+                    let flow = self.parser.parse_line(line);
+                    self.progress.bytes += len as u64;
+                    if let ControlFlow::Break(Ok(_)) = flow {
+                        self.progress.nuclides += 1;
+                    }
+                    if let Some(f) = &mut self.on_progress {
+                        f(self.progress);
+                    }
+                    match flow {
+                        ControlFlow::Continue(()) => {}
+                        ControlFlow::Break(res) => return Some(res),
+                    }
+                }
+                Err(e) => {
+                    self.is_finished = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+    }
+
+    // the number of lines left isn't known, so this is only exact once the iterator is done
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_finished || self.parser.is_stopped() {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+impl<R: BufRead, F: Float> FusedIterator for Iter<R, F> {}
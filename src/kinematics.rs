@@ -0,0 +1,223 @@
+//! Relativistic kinematics of two-body reactions, with the masses in a dataset.
+//!
+//! A reaction is given as a [`Reaction`], such as `12C(d,p)13C`, with the target and the beam
+//! before, and the ejectile and the residual after, and the target is at rest. The energies are
+//! kinetic energies in keV, in the lab frame, and the angles are in radians, from the direction of
+//! the beam.
+//!
+//! The masses are the atomic masses, so the electrons cancel if the reaction conserves Z, and
+//! their binding energies are left out.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{
+//!     dataset::{Dataset, Duplicates},
+//!     kinematics::Kinematics,
+//!     reaction::Reaction,
+//! };
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! // elastic scattering of a 1 MeV neutron on hydrogen
+//! let reaction = Reaction::parse("1H(n,n)").unwrap();
+//! let kinematics = Kinematics::new(&dataset, &reaction, 1_000.0, 0.0).unwrap();
+//! let ejectile = kinematics.ejectile(std::f64::consts::FRAC_PI_4);
+//! // about half of the energy goes to each
+//! assert!((ejectile[0].energy - 500.0).abs() < 1.0);
+//! ```
+use crate::{dataset::Dataset, reaction::Reaction, Nuclide, Value};
+
+/// The kinematics of a two-body reaction at a beam energy.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Kinematics {
+    /// The mass of the target, in keV
+    pub target: f64,
+    /// The mass of the beam, in keV
+    pub beam: f64,
+    /// The mass of the ejectile, in keV
+    pub ejectile: f64,
+    /// The mass of the residual, in keV, including its excitation energy
+    pub residual: f64,
+    /// The kinetic energy of the beam, in keV
+    pub beam_energy: f64,
+}
+
+/// The ejectile and residual at an angle of the ejectile, from [`Kinematics::ejectile`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ejectile {
+    /// The kinetic energy of the ejectile, in keV
+    pub energy: f64,
+    /// The kinetic energy of the residual, in keV
+    pub residual_energy: f64,
+    /// The angle of the residual, in radians, on the other side of the beam from the ejectile
+    pub residual_angle: f64,
+}
+
+impl Kinematics {
+    /// Returns the kinematics of `reaction`, with the masses in `dataset`, a beam with a kinetic
+    /// energy of `beam_energy` keV, and the residual in a state with an excitation energy of
+    /// `excitation` keV.
+    ///
+    /// If the reaction doesn't have two nuclides before and two after, or has positrons, or a
+    /// nuclide isn't in the dataset, `None` is returned.
+    #[must_use]
+    pub fn new(
+        dataset: &Dataset,
+        reaction: &Reaction,
+        beam_energy: f64,
+        excitation: f64,
+    ) -> Option<Self> {
+        let ([target, beam], [ejectile, residual], 0) = (
+            reaction.initial.as_slice(),
+            reaction.products.as_slice(),
+            reaction.positrons,
+        ) else {
+            return None;
+        };
+        let mass = |&(z, n): &(u32, u32)| dataset.get(z, n).map(|nuc| mass(dataset, nuc));
+        Some(Self {
+            target: mass(target)?,
+            beam: mass(beam)?,
+            ejectile: mass(ejectile)?,
+            residual: mass(residual)? + excitation,
+            beam_energy,
+        })
+    }
+
+    /// Returns the Q-value, in keV, with the excitation energy of the residual.
+    #[must_use]
+    pub fn q_value(&self) -> f64 {
+        self.target + self.beam - self.ejectile - self.residual
+    }
+
+    /// Returns the ejectile at an angle of `angle` radians, and the residual that goes with it.
+    ///
+    /// There are no solutions if the reaction isn't possible at the beam energy, or the ejectile
+    /// can't go at `angle`, one in most cases, and two if the ejectile is forward of
+    /// [`max_angle`](Self::max_angle), in which case the one with more energy is first.
+    #[must_use]
+    pub fn ejectile(&self, angle: f64) -> Vec<Ejectile> {
+        let (energy, momentum) = self.total();
+        let s = energy * energy - momentum * momentum;
+        let (mass, residual_mass) = (self.ejectile, self.residual);
+        // the ejectile has E E_b - P p cos θ = a, from the invariant mass of the residual
+        let a = (s + mass * mass - residual_mass * residual_mass) / 2.0;
+        let cos = angle.cos();
+        let denominator = energy * energy - (momentum * cos).powi(2);
+        let discriminant = a * a - mass * mass * denominator;
+        if discriminant < 0.0 {
+            return Vec::new();
+        }
+        let root = energy * discriminant.sqrt();
+        let mut solutions = Vec::new();
+        for p in [
+            (a * momentum * cos + root) / denominator,
+            (a * momentum * cos - root) / denominator,
+        ] {
+            // squaring for the quadratic allows solutions with a negative energy
+            if p < 0.0 || a + momentum * cos * p < 0.0 {
+                continue;
+            }
+            if solutions.len() == 1 && root == 0.0 {
+                break;
+            }
+            let total = p.hypot(mass);
+            let (along, across) = (momentum - p * cos, p * angle.sin());
+            solutions.push(Ejectile {
+                energy: total - mass,
+                residual_energy: energy - total - residual_mass,
+                residual_angle: across.atan2(along),
+            });
+        }
+        solutions
+    }
+
+    /// Returns the largest angle of the ejectile, in radians.
+    ///
+    /// If the ejectile can go at any angle, or the reaction isn't possible at the beam energy,
+    /// `None` is returned.
+    #[must_use]
+    pub fn max_angle(&self) -> Option<f64> {
+        let (energy, momentum) = self.total();
+        let s = energy * energy - momentum * momentum;
+        let (mass, residual_mass) = (self.ejectile, self.residual);
+        let sum = (mass + residual_mass).powi(2);
+        let difference = (mass - residual_mass).powi(2);
+        if s < sum {
+            return None;
+        }
+        // the momentum of the ejectile in the center of mass frame
+        let p = ((s - sum) * (s - difference)).sqrt() / (2.0 * s.sqrt());
+        let sin = p * s.sqrt() / (mass * momentum);
+        (sin < 1.0).then(|| sin.asin())
+    }
+
+    // the total energy and momentum, in keV
+    fn total(&self) -> (f64, f64) {
+        let energy = self.beam_energy + self.beam + self.target;
+        let momentum = (self.beam_energy * (self.beam_energy + 2.0 * self.beam)).sqrt();
+        (energy, momentum)
+    }
+}
+
+/// Returns the kinetic energy of the beam, in keV, at which `reaction` becomes possible, with the
+/// masses in `dataset`.
+///
+/// This is 0 if the reaction is exothermic, and otherwise -Q (m<sub>i</sub> + m<sub>f</sub>) /
+/// 2m<sub>target</sub>, where m<sub>i</sub> and m<sub>f</sub> are the total masses before and
+/// after. The uncertainty is that of the Q-value, with the masses taken as exact. Unlike
+/// [`Kinematics`], there can be any number of products, and positrons.
+///
+/// If the reaction doesn't have two nuclides before, or a nuclide isn't in the dataset, `None` is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{dataset::{Dataset, Duplicates}, kinematics, reaction::Reaction};
+///
+/// let dataset = Dataset::from_slice(br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014
+///    1    4    3    7 Li        14907.1047      0.0042     5606.4401     0.0006  B-      *                    7 016003.4343      0.0045
+///   -1    3    4    7 Be        15768.999       0.071      5371.5485     0.0101  B-      *                    7 016928.716       0.076", Duplicates::Error).unwrap();
+/// let reaction = Reaction::parse("7Li(p,n)").unwrap();
+/// let threshold = kinematics::threshold_energy(&dataset, &reaction).unwrap();
+/// assert!((threshold.mean - 1880.638).abs() < 1e-3);
+/// ```
+#[must_use]
+pub fn threshold_energy(dataset: &Dataset, reaction: &Reaction) -> Option<Value> {
+    let &[target, _] = reaction.initial.as_slice() else {
+        return None;
+    };
+    let q = dataset.reaction_q_value(reaction)?;
+    if q.mean >= 0.0 {
+        return Some(Value {
+            mean: 0.0,
+            uncertainty: 0.0,
+            ..q
+        });
+    }
+    let initial = reaction
+        .initial
+        .iter()
+        .map(|&(z, n)| dataset.get(z, n).map(|nuc| mass(dataset, nuc)))
+        .sum::<Option<f64>>()?;
+    let target = mass(dataset, dataset.get(target.0, target.1)?);
+    Some(Value {
+        mean: -q.mean * (2.0 * initial - q.mean) / (2.0 * target),
+        // the derivative with respect to Q is -(m_i - Q) / m_target
+        uncertainty: q.uncertainty * (initial - q.mean) / target,
+        is_estimated: q.is_estimated,
+    })
+}
+
+// the atomic mass, in keV
+fn mass(dataset: &Dataset, nuc: &Nuclide) -> f64 {
+    dataset.constants().to_kev(f64::from(nuc.a())) + nuc.mass_excess.mean
+}
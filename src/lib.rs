@@ -16,61 +16,379 @@
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use ame2020::{Iter, Nuclide};
-//! use std::{fs::File, io::BufReader};
 //!
-//! let file = File::open("mass.mas20")?;
-//! let file = BufReader::new(file);
-//! let iter = Iter::new(file);
+//! let iter = Iter::from_path("mass.mas20")?;
 //! let data: Vec<Nuclide> = iter.collect::<Result<_, _>>()?;
 //! # Ok(())
 //! # }
 //! ```
 //!
+//! If the data is already in a string, [`parse_str`] parses all of it at once. To look up nuclides
+//! by Z and N, read the data into a [`Dataset`][dataset::Dataset]. The chart of nuclides can be
+//! written as an SVG image with [`svg`].
+//!
 //! # Features
 //!
+//! * `cli`: Build the `ame2020` command line program, which looks up, filters, and converts nuclides, computes Q-values, compares editions, and draws the chart of nuclides.
+//! * `datafusion`: Provide querying the data with SQL with [DataFusion](https://datafusion.apache.org), in [`sql`].
+//! * `decimal`: Provide parsing the values exactly, as [rust_decimal](https://crates.io/crates/rust_decimal) decimals, instead of floats, in [`decimal`].
+//! * `evcxr`: Provide showing nuclides as HTML tables in [evcxr](https://github.com/evcxr/evcxr) notebooks, in [`evcxr`].
+//! * `fast-float`: Parse floats with [fast-float2](https://crates.io/crates/fast-float2), which is faster than the standard library.
+//! * `ffi`: Provide a C interface, in [`ffi`], with a header in `include/ame2020.h`.
+//! * `fingerprint`: Provide checking files against a SHA-256 digest with [sha2](https://crates.io/crates/sha2), in [`fingerprint`].
+//! * `mat`: Provide writing the data as a MATLAB struct array in a `.mat` file, in [`mat`].
+//! * `miette`: Provide rich diagnostics for parsing errors with [miette](https://crates.io/crates/miette), in [`diagnostic`].
+//! * `nalgebra`: Provide covariance matrices of the masses as [nalgebra](https://nalgebra.rs) matrices, in [`covariance`].
+//! * `npz`: Provide writing the data as `NumPy` arrays in an `.npz` file with [zip](https://crates.io/crates/zip), in [`npz`].
+//! * `plot`: Provide drawing the chart of nuclides with [plotters](https://crates.io/crates/plotters), in [`plot`].
+//! * `proptest`: Provide strategies for generating realistic nuclides with [proptest](https://crates.io/crates/proptest), in [`strategy`].
+//! * `python`: Provide the `pyame2020` Python module with [pyo3](https://pyo3.rs), described in [`python`].
+//! * `ndarray`: Provide values on a chart of nuclides, as an [ndarray](https://crates.io/crates/ndarray) `Array2`, in [`grid`].
+//! * `rayon`: Provide parsing in parallel with [rayon](https://crates.io/crates/rayon), in [`par`].
+//! * `std` (default): Provide [`Iter`], for reading from `std::io`, and the modules that need the
+//!   standard library. Without it, the crate is `no_std`, but needs `alloc`, and the data can be
+//!   parsed from memory with [`SliceIter`] and [`parse_str`].
+//! * `rkyv`: Provide zero-copy serialization with [rkyv](https://rkyv.org), described in [`archive`].
 //! * `serde`: Provide `Serialize` and `Deserialize` implementations for [serde](https://serde.rs).
 //! * `arbitrary`: Provide `Arbitrary` implementations for [arbitrary](https://crates.io/crates/arbitrary), useful for fuzzing.
+//! * `tokio`: Provide [`AsyncIter`], a `Stream` that reads from a `tokio::io::AsyncBufRead`.
+//! * `tui`: Add the `browse` subcommand to the `ame2020` program, for browsing the chart of nuclides in the terminal with [ratatui](https://ratatui.rs).
+//! * `uom`: Provide values as [uom](https://crates.io/crates/uom) quantities, in [`quantity`].
+//! * `wasm`: Provide loading from `fetch()` and `Uint8Array` in the browser, and bindings for JavaScript, in [`wasm`].
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+// so the generated source in the tests can refer to `::ame2020`
+#[cfg(test)]
+extern crate self as ame2020;
+
+use alloc::vec::Vec;
 #[cfg(feature = "arbitrary")]
 use arbitrary::{Arbitrary, Unstructured};
 use arrayvec::ArrayString;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use core::ops::Not;
+use core::{cmp::Ordering, iter::FusedIterator};
 #[cfg(feature = "serde")]
-use std::ops::Not;
-use std::{
-    cmp::Ordering,
-    io::{BufRead, Lines},
-    ops::{ControlFlow, Range},
-};
+use serde::{Deserialize, Serialize};
 
-pub use crate::error::AmeError;
+#[cfg(feature = "tokio")]
+pub use crate::async_iter::AsyncIter;
+#[cfg(feature = "std")]
+pub use crate::iter::{Iter, Progress};
+pub use crate::{
+    builder::NuclideBuilder,
+    error::{AmeError, Field},
+    float::Float,
+    options::ParseOptions,
+    slice::SliceIter,
+    slice_ext::NuclideSliceExt,
+    warning::Warning,
+};
 
+#[cfg(feature = "std")]
+pub mod abundance;
+#[cfg(feature = "std")]
+pub mod alpha;
+#[cfg(feature = "rkyv")]
+pub mod archive;
+#[cfg(feature = "tokio")]
+mod async_iter;
+#[cfg(feature = "std")]
+pub mod binding;
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod capture;
+#[cfg(feature = "std")]
+pub mod codegen;
+pub mod constants;
+#[cfg(feature = "std")]
+pub mod covariance;
+#[cfg(feature = "std")]
+pub mod dataset;
+#[cfg(feature = "std")]
+pub mod decay;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+#[cfg(feature = "miette")]
+pub mod diagnostic;
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod element;
 mod error;
-#[cfg(test)]
+#[cfg(feature = "evcxr")]
+pub mod evcxr;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod filter;
+#[cfg(feature = "fingerprint")]
+pub mod fingerprint;
+pub mod fission;
+mod float;
+#[cfg(feature = "ndarray")]
+pub mod grid;
+pub mod histogram;
+#[cfg(feature = "std")]
+mod iter;
+#[cfg(feature = "std")]
+pub mod kinematics;
+#[cfg(feature = "std")]
+pub mod livechart;
+#[cfg(feature = "mat")]
+pub mod mat;
+pub mod metadata;
+#[cfg(feature = "std")]
+pub mod model;
+#[cfg(feature = "std")]
+pub mod molecule;
+#[cfg(feature = "std")]
+pub mod network;
+#[cfg(feature = "npz")]
+pub mod npz;
+pub mod options;
+#[cfg(feature = "std")]
+pub mod overrides;
+#[cfg(feature = "rayon")]
+pub mod par;
+mod parser;
+#[cfg(feature = "std")]
+pub mod penning;
+#[cfg(feature = "plot")]
+pub mod plot;
+#[cfg(feature = "std")]
+pub mod process;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "uom")]
+pub mod quantity;
+pub mod reaction;
+mod slice;
+mod slice_ext;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "datafusion")]
+pub mod sql;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod summary;
+#[cfg(feature = "std")]
+pub mod surface;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "std")]
+pub mod talys;
+#[cfg(all(test, feature = "std"))]
 mod tests;
+#[cfg(feature = "std")]
+pub mod theory;
+#[cfg(feature = "std")]
+pub mod validate;
+mod warning;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// A value that has a mean and uncertainty.
 ///
 /// The data may be an estimate (indicated by `is_estimated`).
 /// If not, they are based on experimental data.
+///
+/// The mean and uncertainty are `f64` by default. The iterators can parse them as another
+/// [`Float`], such as `f32` to save memory, and the helpers that only compare values, such as
+/// [`NuclideSliceExt`], take any `Float`. The derived quantities, such as in [`dataset`], are
+/// computed in `f64`. Values of any type can be converted with [`map`](Self::map).
 #[derive(Clone, PartialEq, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
-pub struct Value {
-    pub mean: f64,
-    pub uncertainty: f64,
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
+)]
+pub struct Value<F = f64> {
+    pub mean: F,
+    pub uncertainty: F,
     #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Not::not"))]
     pub is_estimated: bool,
 }
 
-impl PartialOrd for Value {
+impl<F> Value<F> {
+    /// Returns the value with `f` applied to the mean and the uncertainty, such as to convert
+    /// them to another type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::Value;
+    ///
+    /// let value = Value {
+    ///     mean: 8_071.318_06,
+    ///     uncertainty: 0.000_44,
+    ///     is_estimated: false,
+    /// };
+    /// let single: Value<f32> = value.map(|x| x as f32);
+    /// assert_eq!(single.mean, 8_071.318);
+    /// ```
+    #[must_use]
+    pub fn map<G>(self, mut f: impl FnMut(F) -> G) -> Value<G> {
+        Value {
+            mean: f(self.mean),
+            uncertainty: f(self.uncertainty),
+            is_estimated: self.is_estimated,
+        }
+    }
+}
+
+impl<F: PartialOrd> PartialOrd for Value<F> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.mean.partial_cmp(&other.mean)
     }
 }
 
+impl From<Value<f32>> for Value {
+    fn from(value: Value<f32>) -> Self {
+        value.map(f64::from)
+    }
+}
+
+/// The decay that the beta decay energy column is labelled with.
+///
+/// In `mass.mas20`, every line is labelled `B-`, because the column is always the β⁻ decay energy,
+/// to the nuclide with Z+1 and N-1. The other modes are accepted for edited tables.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
+)]
+#[non_exhaustive]
+pub enum DecayMode {
+    /// β⁻ decay, `B-`
+    BetaMinus,
+    /// β⁺ decay, `B+`
+    BetaPlus,
+    /// α decay, `A`
+    Alpha,
+    /// Electron capture, `EC`
+    ElectronCapture,
+}
+
+impl DecayMode {
+    /// Returns the label used in the table.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BetaMinus => "B-",
+            Self::BetaPlus => "B+",
+            Self::Alpha => "A",
+            Self::ElectronCapture => "EC",
+        }
+    }
+
+    /// Returns the mode with the label `label`, as used in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::DecayMode;
+    ///
+    /// assert_eq!(DecayMode::from_label("B-"), Some(DecayMode::BetaMinus));
+    /// assert_eq!(DecayMode::from_label("X"), None);
+    /// ```
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "B-" => Some(Self::BetaMinus),
+            "B+" => Some(Self::BetaPlus),
+            "A" => Some(Self::Alpha),
+            "EC" => Some(Self::ElectronCapture),
+            _ => None,
+        }
+    }
+}
+
+/// A [`Nuclide`] with only experimental data.
+///
+/// Each value that is estimated in the `Nuclide` is `None`, so that estimates can't be used by
+/// mistake. It is created with [`From<Nuclide>`], or with [`Iter::experimental`] and
+/// [`SliceIter::experimental`]. The values are of type `F`, as in [`Value`].
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
+)]
+pub struct ExperimentalNuclide<F = f64> {
+    /// Neutron number
+    pub n: u32,
+    /// Proton number
+    pub z: u32,
+    /// Chemical symbol of the element
+    #[cfg_attr(feature = "rkyv", rkyv(with = archive::AsString))]
+    pub element: ArrayString<3>,
+    /// The code for how the mass was derived, such as `-pp`, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Map<archive::AsString>))]
+    pub origin: Option<ArrayString<4>>,
+    /// Mass excess, if it is experimental
+    pub mass_excess: Option<Value<F>>,
+    /// Binding energy per nucleon, if it is given and experimental
+    pub binding_energy_per_a: Option<Value<F>>,
+    /// The decay the beta decay energy is labelled with, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub decay_mode: Option<DecayMode>,
+    /// Beta decay energy, if it is given and experimental
+    pub beta_decay_energy: Option<Value<F>>,
+    /// Atomic Mass in atomic mass units, if it is experimental
+    pub atomic_mass: Option<Value<F>>,
+}
+
+impl<F> From<Nuclide<F>> for ExperimentalNuclide<F> {
+    fn from(nuclide: Nuclide<F>) -> Self {
+        let experimental = |value: Value<F>| (!value.is_estimated).then_some(value);
+        Self {
+            n: nuclide.n,
+            z: nuclide.z,
+            element: nuclide.element,
+            origin: nuclide.origin,
+            mass_excess: experimental(nuclide.mass_excess),
+            binding_energy_per_a: nuclide.binding_energy_per_a.and_then(experimental),
+            decay_mode: nuclide.decay_mode,
+            beta_decay_energy: nuclide.beta_decay_energy.and_then(experimental),
+            atomic_mass: experimental(nuclide.atomic_mass),
+        }
+    }
+}
+
+/// An iterator that yields [`ExperimentalNuclide`]s, created by [`Iter::experimental`] and
+/// [`SliceIter::experimental`].
+#[derive(Debug)]
+pub struct Experimental<I>(pub(crate) I);
+
+impl<F, I: Iterator<Item = Result<Nuclide<F>, AmeError>>> Iterator for Experimental<I> {
+    type Item = Result<ExperimentalNuclide<F>, AmeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|res| res.map(ExperimentalNuclide::from))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<F, I: FusedIterator<Item = Result<Nuclide<F>, AmeError>>> FusedIterator for Experimental<I> {}
+
 /// A type holding the nuclide data.
 ///
+/// The values are of type `F`, as in [`Value`], and [`map`](Self::map) converts them to another.
+///
 /// # Examples
 ///
 /// ```
@@ -89,27 +407,116 @@ impl PartialOrd for Value {
 /// ```
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct Nuclide {
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
+)]
+pub struct Nuclide<F = f64> {
     /// Neutron number
     pub n: u32,
     /// Proton number
     pub z: u32,
     /// Chemical symbol of the element
+    #[cfg_attr(feature = "rkyv", rkyv(with = archive::AsString))]
     pub element: ArrayString<3>,
+    /// The code for how the mass was derived, such as `-pp`, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Map<archive::AsString>))]
+    pub origin: Option<ArrayString<4>>,
     /// Mass excess
     ///
     /// The difference between the mass in atomic mass units and the atomic mass number (N+Z).
-    pub mass_excess: Value,
-    /// Binding energy per nucleon
-    pub binding_energy_per_a: Value,
+    pub mass_excess: Value<F>,
+    /// Binding energy per nucleon, if any
+    pub binding_energy_per_a: Option<Value<F>>,
+    /// The decay the beta decay energy is labelled with, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub decay_mode: Option<DecayMode>,
     /// Beta decay energy, if any
-    pub beta_decay_energy: Option<Value>,
+    pub beta_decay_energy: Option<Value<F>>,
     /// Atomic Mass in atomic mass units
-    pub atomic_mass: Value,
+    pub atomic_mass: Value<F>,
+}
+
+/// A function that returns one of the values of a [`Nuclide`], if it has it.
+pub type ValueGetter = fn(&Nuclide) -> Option<&Value>;
+
+/// The names of the values of a [`Nuclide`], which are those of their fields, in order, with a
+/// function that returns each.
+///
+/// These are the values in the columns of the tabular formats, such as a mean, uncertainty, and
+/// whether it is estimated for each.
+pub const VALUE_COLUMNS: [(&str, ValueGetter); 4] = [
+    ("mass_excess", |nuc| Some(&nuc.mass_excess)),
+    ("binding_energy_per_a", |nuc| {
+        nuc.binding_energy_per_a.as_ref()
+    }),
+    ("beta_decay_energy", |nuc| nuc.beta_decay_energy.as_ref()),
+    ("atomic_mass", |nuc| Some(&nuc.atomic_mass)),
+];
+
+impl<F> Nuclide<F> {
+    /// Returns the mass number, A = N+Z.
+    #[must_use]
+    pub fn a(&self) -> u32 {
+        self.n + self.z
+    }
+
+    /// Returns whether any of the values are estimated, rather than experimental.
+    #[must_use]
+    pub fn is_estimated(&self) -> bool {
+        self.mass_excess.is_estimated
+            || self.atomic_mass.is_estimated
+            || [&self.binding_energy_per_a, &self.beta_decay_energy]
+                .into_iter()
+                .flatten()
+                .any(|value| value.is_estimated)
+    }
+
+    /// Returns the nuclide with `f` applied to the means and uncertainties of all of its values,
+    /// such as to convert them to another type, as with [`Value::map`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::{parse_str, Nuclide};
+    ///
+    /// let data = parse_str(r"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+    /// let single: Vec<Nuclide<f32>> = data.into_iter().map(|nuc| nuc.map(|x| x as f32)).collect();
+    /// assert_eq!(single[0].mass_excess.mean, 8_071.318);
+    /// ```
+    #[must_use]
+    pub fn map<G>(self, mut f: impl FnMut(F) -> G) -> Nuclide<G> {
+        Nuclide {
+            n: self.n,
+            z: self.z,
+            element: self.element,
+            origin: self.origin,
+            mass_excess: self.mass_excess.map(&mut f),
+            binding_energy_per_a: self.binding_energy_per_a.map(|value| value.map(&mut f)),
+            decay_mode: self.decay_mode,
+            beta_decay_energy: self.beta_decay_energy.map(|value| value.map(&mut f)),
+            atomic_mass: self.atomic_mass.map(&mut f),
+        }
+    }
+}
+
+impl From<Nuclide<f32>> for Nuclide {
+    fn from(nuclide: Nuclide<f32>) -> Self {
+        nuclide.map(f64::from)
+    }
 }
 
 #[cfg(feature = "arbitrary")]
-impl<'a> Arbitrary<'a> for Nuclide {
+impl Arbitrary<'_> for Nuclide {
     fn arbitrary(u: &mut Unstructured) -> arbitrary::Result<Self> {
         // this is adapted from arbitrary's implementation of Arbitrary for &str
         fn array_string<const CAP: usize>(
@@ -136,8 +543,14 @@ impl<'a> Arbitrary<'a> for Nuclide {
         let n = u.arbitrary()?;
         let z = u.arbitrary()?;
         let element = array_string(u)?;
+        let origin = if u.arbitrary()? {
+            Some(array_string(u)?)
+        } else {
+            None
+        };
         let mass_excess = u.arbitrary()?;
         let binding_energy_per_a = u.arbitrary()?;
+        let decay_mode = u.arbitrary()?;
         let beta_decay_energy = u.arbitrary()?;
         let atomic_mass = u.arbitrary()?;
 
@@ -145,154 +558,30 @@ impl<'a> Arbitrary<'a> for Nuclide {
             n,
             z,
             element,
+            origin,
             mass_excess,
             binding_energy_per_a,
+            decay_mode,
             beta_decay_energy,
             atomic_mass,
         })
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-enum ReadState {
-    Start,
-    Preamble,
-    Headers,
-    Body,
-}
-
-/// An iterator that reads AME2020 data.
+/// Parses all of the data in `s`.
 ///
 /// # Examples
 ///
 /// ```
-/// use ame2020::Iter;
-/// use std::io::Cursor;
-///
-/// // `Cursor` is a type that implements `BufRead`.
-/// // Consider using `BufReader` if you have a `File`.
-/// let data = Cursor::new(r"1
+/// let data = ame2020::parse_str(r"1
 /// 1
-/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047");
-/// let mut iter = Iter::new(data);
-/// assert!(iter.next().is_some());
-/// assert!(iter.next().is_none());
-///
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+/// assert_eq!(data.len(), 1);
 /// ```
 ///
 /// # Errors
 ///
-/// If a line fails to parse or there is a reading error, [`next`][Self::next] will return `Some(Err)`.
-/// Calling `next` again may return `Some`, but the validity of the data is not guaranteed.
-pub struct Iter<R: BufRead> {
-    lines: Lines<R>,
-    state: ReadState,
-}
-
-impl<R: BufRead> Iter<R> {
-    /// Creates a new `Iter` from `reader`.
-    pub fn new(reader: R) -> Self {
-        let lines = reader.lines();
-        Self {
-            lines,
-            state: ReadState::Start,
-        }
-    }
-
-    fn parse_line(&mut self, line: &str) -> ControlFlow<Result<Nuclide, AmeError>> {
-        fn range_err(line: &str, range: Range<usize>) -> Result<&str, AmeError> {
-            if line.len() < range.end {
-                Err(AmeError::TooShortLine)
-            } else {
-                Ok(line.get(range).ok_or(AmeError::StrIndex)?.trim())
-            }
-        }
-
-        fn parse_value(
-            (s_mean, r_mean): (&str, Range<usize>),
-            (s_unc, r_unc): (&str, Range<usize>),
-        ) -> Result<Value, AmeError> {
-            let mean = range_err(&s_mean.replace('#', "."), r_mean)?.parse()?;
-            let uncertainty = range_err(&s_unc.replace('#', "."), r_unc)?.parse()?;
-            let is_estimated = s_mean.contains('#');
-            Ok(Value {
-                mean,
-                uncertainty,
-                is_estimated,
-            })
-        }
-
-        fn inner(line: &str) -> Result<Nuclide, AmeError> {
-            let n = range_err(line, 4..9)?.parse()?;
-            let z = range_err(line, 9..14)?.parse()?;
-            let element = ArrayString::from(range_err(line, 20..23)?)
-                .expect("the range is 3 and the capacity is 3");
-            let mass_excess = parse_value((line, 28..42), (line, 42..54))?;
-            let binding_energy_per_a = parse_value((line, 54..67), (line, 68..78))?;
-            let beta_decay_energy = (range_err(line, 87..88)? != "*")
-                .then(|| parse_value((line, 81..94), (line, 94..105)))
-                .transpose()?;
-
-            // the value is given in micro-u, with a space before the 1e6 place.
-            // this makes it inconvenient to parse in u.
-            //
-            // lines don't have the same length, so use `line.len()`. you could use a RangeFrom,
-            // but that would require rewriting `parse_value` and `range_err` to be generic, and it
-            // would lead to more complicated bounds checks.
-            let mut atomic_mass = parse_value((line, 110..123), (line, 123..(line.len())))?;
-            atomic_mass.mean *= 1e-6;
-            atomic_mass.uncertainty *= 1e-6;
-            atomic_mass.mean += f64::from(range_err(line, 106..109)?.parse::<u16>()?);
-
-            Ok(Nuclide {
-                n,
-                z,
-                element,
-                mass_excess,
-                binding_energy_per_a,
-                beta_decay_energy,
-                atomic_mass,
-            })
-        }
-
-        match self.state {
-            ReadState::Start => {
-                if line.starts_with('1') {
-                    self.state = ReadState::Preamble;
-                }
-                ControlFlow::Continue(())
-            }
-            ReadState::Preamble => {
-                if line.starts_with('1') {
-                    self.state = ReadState::Headers;
-                }
-                ControlFlow::Continue(())
-            }
-            ReadState::Headers => {
-                if line.starts_with('0') {
-                    self.state = ReadState::Body;
-                    ControlFlow::Break(inner(line))
-                } else {
-                    ControlFlow::Continue(())
-                }
-            }
-            ReadState::Body => ControlFlow::Break(inner(line)),
-        }
-    }
-}
-
-impl<R: BufRead> Iterator for Iter<R> {
-    type Item = Result<Nuclide, AmeError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.lines.next()? {
-                Ok(line) => match self.parse_line(&line) {
-                    ControlFlow::Continue(()) => continue,
-                    ControlFlow::Break(res) => return Some(res),
-                },
-                Err(e) => return Some(Err(e.into())),
-            }
-        }
-    }
+/// If any line fails to parse, the first error is returned.
+pub fn parse_str(s: &str) -> Result<Vec<Nuclide>, AmeError> {
+    SliceIter::new(s.as_bytes()).collect()
 }
@@ -0,0 +1,166 @@
+//! Reading the CSV from the [IAEA Live Chart of Nuclides] API, as [`Nuclide`]s.
+//!
+//! The ground states are returned by `https://nds.iaea.org/relnsd/v1/data?fields=ground_states&nuclides=all`,
+//! with a header line naming the columns. The columns that are read are:
+//!
+//! | Column | Field |
+//! |--------|-------|
+//! | `z`, `n` | [`Nuclide::z`], [`Nuclide::n`] |
+//! | `massexcess`, `unc_me` | [`Nuclide::mass_excess`], in keV |
+//! | `binding`, `unc_ba` | [`Nuclide::binding_energy_per_a`], in keV |
+//! | `qbm`, `unc_qbm` | [`Nuclide::beta_decay_energy`], in keV, labelled as β⁻ decay |
+//! | `atomic_mass`, `unc_am` | [`Nuclide::atomic_mass`], converted from micro-u |
+//! | `me_systematics` | whether the values are estimated, if it is `Y` |
+//!
+//! The other columns are ignored, and the element symbol is the one for Z. The API has no origin,
+//! so it is `None`. The API also has nuclides that aren't in AME2020, which have no mass excess,
+//! and are skipped.
+//!
+//! [IAEA Live Chart of Nuclides]: https://www-nds.iaea.org/relnsd/vcharthtml/VChartHTML.html
+//!
+//! # Examples
+//!
+//! ```
+//! let csv = "z,n,symbol,massexcess,unc_me,binding,unc_ba,qbm,unc_qbm,atomic_mass,unc_am,me_systematics
+//! 0,1,N,8071.31806,0.00044,0,0,782.347,0.0004,1008664.9159,0.00047,N
+//! ";
+//! let data = ame2020::livechart::read(csv.as_bytes()).unwrap();
+//! assert_eq!(&data[0].element, "n");
+//! assert!((data[0].atomic_mass.mean - 1.008_664_915_9).abs() < 1e-12);
+//! ```
+use crate::{element, AmeError, DecayMode, Nuclide, Value};
+use arrayvec::ArrayString;
+use std::io::BufRead;
+
+const Z: &str = "z";
+const N: &str = "n";
+const MASS_EXCESS: &str = "massexcess";
+const MASS_EXCESS_UNCERTAINTY: &str = "unc_me";
+const BINDING_ENERGY_PER_A: &str = "binding";
+const BINDING_ENERGY_PER_A_UNCERTAINTY: &str = "unc_ba";
+const BETA_DECAY_ENERGY: &str = "qbm";
+const BETA_DECAY_ENERGY_UNCERTAINTY: &str = "unc_qbm";
+const ATOMIC_MASS: &str = "atomic_mass";
+const ATOMIC_MASS_UNCERTAINTY: &str = "unc_am";
+const SYSTEMATICS: &str = "me_systematics";
+
+// the index of a column in the header, and its name
+type Column = (usize, &'static str);
+
+/// Reads the nuclides from the Live Chart CSV in `reader`.
+///
+/// # Errors
+///
+/// If reading fails, a column is missing from the header, or a value fails to parse, an error is
+/// returned.
+pub fn read<R: BufRead>(reader: R) -> Result<Vec<Nuclide>, AmeError> {
+    let mut lines = reader.lines();
+    let Some(header) = lines.next().transpose()? else {
+        return Ok(Vec::new());
+    };
+    let header = split(&header);
+    let column = |name: &'static str| {
+        header
+            .iter()
+            .position(|column| column == name)
+            .map(|i| (i, name))
+            .ok_or(AmeError::MissingColumn(name))
+    };
+    let z = column(Z)?;
+    let n = column(N)?;
+    let mass_excess = (column(MASS_EXCESS)?, column(MASS_EXCESS_UNCERTAINTY)?);
+    let binding_energy_per_a = (
+        column(BINDING_ENERGY_PER_A)?,
+        column(BINDING_ENERGY_PER_A_UNCERTAINTY)?,
+    );
+    let beta_decay_energy = (
+        column(BETA_DECAY_ENERGY)?,
+        column(BETA_DECAY_ENERGY_UNCERTAINTY)?,
+    );
+    let atomic_mass = (column(ATOMIC_MASS)?, column(ATOMIC_MASS_UNCERTAINTY)?);
+    let systematics = column(SYSTEMATICS)?;
+
+    let mut nuclides = Vec::new();
+    // the header is line 1
+    for (line, text) in (2..).zip(lines) {
+        let text = text?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let fields = split(&text);
+        let field = |(i, _): Column| fields.get(i).map_or("", |field| field.trim());
+        let invalid = |(_, column): Column| AmeError::InvalidColumn { line, column };
+        let parse = |column: Column| {
+            field(column)
+                .parse::<f64>()
+                .ok()
+                .filter(|x| x.is_finite())
+                .ok_or_else(|| invalid(column))
+        };
+
+        let is_estimated = field(systematics) == "Y";
+        let value = |(mean, uncertainty): (Column, Column)| -> Result<Option<Value>, AmeError> {
+            if field(mean).is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Value {
+                mean: parse(mean)?,
+                uncertainty: if field(uncertainty).is_empty() {
+                    0.0
+                } else {
+                    parse(uncertainty)?
+                },
+                is_estimated,
+            }))
+        };
+
+        let Some(mass_excess) = value(mass_excess)? else {
+            continue;
+        };
+        let element = field(z)
+            .parse()
+            .ok()
+            .and_then(|z| Some((z, ArrayString::from(element::symbol(z)?).ok()?)));
+        let (z, element) = element.ok_or_else(|| invalid(z))?;
+        let n: u32 = field(n).parse().map_err(|_| invalid(n))?;
+        let beta_decay_energy = value(beta_decay_energy)?;
+        let atomic_mass = value(atomic_mass)?
+            .map(|value| Value {
+                mean: value.mean * 1e-6,
+                uncertainty: value.uncertainty * 1e-6,
+                ..value
+            })
+            .ok_or_else(|| invalid(atomic_mass.0))?;
+        nuclides.push(Nuclide {
+            n,
+            z,
+            element,
+            origin: None,
+            mass_excess,
+            binding_energy_per_a: value(binding_energy_per_a)?,
+            decay_mode: beta_decay_energy.as_ref().map(|_| DecayMode::BetaMinus),
+            beta_decay_energy,
+            atomic_mass,
+        });
+    }
+    Ok(nuclides)
+}
+
+// splits a line on commas, except within double quotes
+fn split(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().expect("there is a field").push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().expect("there is a field").push(c),
+        }
+    }
+    fields
+}
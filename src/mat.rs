@@ -0,0 +1,183 @@
+//! Writing the data as a MATLAB struct array, in a `.mat` file.
+//!
+//! The file is in the Level 5 MAT-file format, which MATLAB and Octave read with `load`, and
+//! `scipy.io.loadmat` reads in Python. It has one variable, a 1×N struct array, with a field for
+//! each field of [`Nuclide`]. Values are split into three fields, for the mean, the uncertainty,
+//! and whether the value is estimated, such as `mass_excess`, `mass_excess_uncertainty`, and
+//! `mass_excess_is_estimated`. Numbers are doubles, and are NaN when there is no value, and
+//! strings are empty when there is none.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::{mat, Iter, Nuclide};
+//! use std::fs::File;
+//!
+//! let data: Vec<Nuclide> = Iter::from_path("mass.mas20")?.collect::<Result<_, _>>()?;
+//! mat::write(File::create("mass.mat")?, "ame2020", &data)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```matlab
+//! load("mass.mat");
+//! fe = ame2020([ame2020.z] == 26);
+//! ```
+use crate::{AmeError, Nuclide, VALUE_COLUMNS};
+use std::io::Write;
+
+// data types
+const MI_INT8: u32 = 1;
+const MI_INT32: u32 = 5;
+const MI_UINT8: u32 = 2;
+const MI_UINT16: u32 = 4;
+const MI_UINT32: u32 = 6;
+const MI_DOUBLE: u32 = 9;
+const MI_MATRIX: u32 = 14;
+
+// array classes
+const MX_STRUCT_CLASS: u32 = 2;
+const MX_CHAR_CLASS: u32 = 4;
+const MX_DOUBLE_CLASS: u32 = 6;
+const MX_UINT8_CLASS: u32 = 9;
+const LOGICAL: u32 = 0x0200;
+
+// the longest field name, with the nul terminator
+const FIELD_NAME_LEN: usize = 64;
+
+enum Field {
+    Double(f64),
+    Logical(bool),
+    Char(String),
+}
+
+/// Writes `nuclides` to `writer`, as a `.mat` file with the struct array in the variable `name`.
+///
+/// `name` should be a valid MATLAB identifier.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = Vec::new();
+/// ame2020::mat::write(&mut buf, "nuclides", &[]).unwrap();
+/// assert!(buf.starts_with(b"MATLAB 5.0 MAT-file"));
+/// ```
+pub fn write<W: Write>(mut writer: W, name: &str, nuclides: &[Nuclide]) -> Result<(), AmeError> {
+    let mut names = vec![
+        "z".to_string(),
+        "n".to_string(),
+        "a".to_string(),
+        "element".to_string(),
+        "origin".to_string(),
+        "decay_mode".to_string(),
+    ];
+    for (name, _) in VALUE_COLUMNS {
+        names.push(name.to_string());
+        names.push(format!("{name}_uncertainty"));
+        names.push(format!("{name}_is_estimated"));
+    }
+    let fields = |nuc: &Nuclide| {
+        let mut fields = vec![
+            Field::Double(nuc.z.into()),
+            Field::Double(nuc.n.into()),
+            Field::Double(nuc.a().into()),
+            Field::Char(nuc.element.to_string()),
+            Field::Char(
+                nuc.origin
+                    .map(|origin| origin.to_string())
+                    .unwrap_or_default(),
+            ),
+            Field::Char(nuc.decay_mode.map_or("", |mode| mode.label()).to_string()),
+        ];
+        for (_, f) in VALUE_COLUMNS {
+            let value = f(nuc);
+            fields.push(Field::Double(value.map_or(f64::NAN, |value| value.mean)));
+            fields.push(Field::Double(
+                value.map_or(f64::NAN, |value| value.uncertainty),
+            ));
+            fields.push(Field::Logical(
+                value.is_some_and(|value| value.is_estimated),
+            ));
+        }
+        fields
+    };
+
+    let mut body = Vec::new();
+    array_header(&mut body, MX_STRUCT_CLASS, len(nuclides.len()), name);
+    element(&mut body, MI_INT32, &len(FIELD_NAME_LEN).to_le_bytes());
+    let mut field_names = vec![0; names.len() * FIELD_NAME_LEN];
+    for (name, chunk) in names.iter().zip(field_names.chunks_mut(FIELD_NAME_LEN)) {
+        chunk[..name.len()].copy_from_slice(name.as_bytes());
+    }
+    element(&mut body, MI_INT8, &field_names);
+    for nuc in nuclides {
+        for field in fields(nuc) {
+            field.write(&mut body);
+        }
+    }
+
+    let mut header = [b' '; 128];
+    let text = b"MATLAB 5.0 MAT-file, written by ame2020";
+    header[..text.len()].copy_from_slice(text);
+    // no subsystem data
+    header[116..124].fill(0);
+    header[124..126].copy_from_slice(&0x0100_u16.to_le_bytes());
+    header[126..128].copy_from_slice(b"IM");
+    writer.write_all(&header)?;
+    let mut matrix = Vec::new();
+    element(&mut matrix, MI_MATRIX, &body);
+    writer.write_all(&matrix)?;
+    Ok(())
+}
+
+impl Field {
+    // writes the field as a matrix element, with no name
+    fn write(&self, out: &mut Vec<u8>) {
+        let mut body = Vec::new();
+        match self {
+            Self::Double(x) => {
+                array_header(&mut body, MX_DOUBLE_CLASS, 1, "");
+                element(&mut body, MI_DOUBLE, &x.to_le_bytes());
+            }
+            Self::Logical(b) => {
+                array_header(&mut body, MX_UINT8_CLASS | LOGICAL, 1, "");
+                element(&mut body, MI_UINT8, &[u8::from(*b)]);
+            }
+            Self::Char(s) => {
+                let chars: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+                array_header(&mut body, MX_CHAR_CLASS, len(chars.len() / 2), "");
+                element(&mut body, MI_UINT16, &chars);
+            }
+        }
+        element(out, MI_MATRIX, &body);
+    }
+}
+
+// the sizes are at most a few thousand
+fn len(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+// writes the flags, the dimensions of a 1×`columns` array, and the name
+fn array_header(out: &mut Vec<u8>, flags: u32, columns: u32, name: &str) {
+    let mut array_flags = flags.to_le_bytes().to_vec();
+    array_flags.extend([0; 4]);
+    element(out, MI_UINT32, &array_flags);
+    let mut dimensions = 1_u32.to_le_bytes().to_vec();
+    dimensions.extend(columns.to_le_bytes());
+    element(out, MI_INT32, &dimensions);
+    element(out, MI_INT8, name.as_bytes());
+}
+
+// writes a data element, padded to 8 bytes
+fn element(out: &mut Vec<u8>, data_type: u32, data: &[u8]) {
+    out.extend(data_type.to_le_bytes());
+    out.extend(len(data.len()).to_le_bytes());
+    out.extend(data);
+    out.resize(out.len().next_multiple_of(8), 0);
+}
@@ -0,0 +1,128 @@
+//! User data for nuclides, such as experiment IDs, tags, or the values of a model, kept beside a
+//! table.
+//!
+//! A [`Metadata`] maps the (Z, N) of nuclides to any data, so the nuclides can still be looked up,
+//! filtered, and sorted in the usual ways, and the data joined to them afterwards.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{metadata::Metadata, parse_str};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! let mut runs = Metadata::new();
+//! runs.insert(1, 0, "run 42");
+//! let joined: Vec<_> = runs.join(&data).map(|(nuc, run)| (nuc.z, *run)).collect();
+//! assert_eq!(joined, [(1, "run 42")]);
+//! ```
+use crate::Nuclide;
+use alloc::collections::{btree_map, BTreeMap};
+
+/// Data of type `T` for nuclides, keyed by (Z, N).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Metadata<T> {
+    data: BTreeMap<(u32, u32), T>,
+}
+
+impl<T> Metadata<T> {
+    /// Returns an empty `Metadata`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            data: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the data of the nuclide with `z` protons and `n` neutrons to `value`, and returns the
+    /// data before, if any.
+    pub fn insert(&mut self, z: u32, n: u32, value: T) -> Option<T> {
+        self.data.insert((z, n), value)
+    }
+
+    /// Removes the data of the nuclide with `z` protons and `n` neutrons, and returns it.
+    pub fn remove(&mut self, z: u32, n: u32) -> Option<T> {
+        self.data.remove(&(z, n))
+    }
+
+    /// Returns the data of the nuclide with `z` protons and `n` neutrons.
+    #[must_use]
+    pub fn get(&self, z: u32, n: u32) -> Option<&T> {
+        self.data.get(&(z, n))
+    }
+
+    /// Returns the data of the nuclide with `z` protons and `n` neutrons, to change it.
+    pub fn get_mut(&mut self, z: u32, n: u32) -> Option<&mut T> {
+        self.data.get_mut(&(z, n))
+    }
+
+    /// Returns the data of `nuc`.
+    #[must_use]
+    pub fn of<F>(&self, nuc: &Nuclide<F>) -> Option<&T> {
+        self.get(nuc.z, nuc.n)
+    }
+
+    /// Returns the number of nuclides with data.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether there are no nuclides with data.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns an iterator over the (Z, N) of the nuclides with data, and the data, in order of Z,
+    /// and then N.
+    pub fn iter(&self) -> impl Iterator<Item = ((u32, u32), &T)> + '_ {
+        self.data.iter().map(|(&key, value)| (key, value))
+    }
+
+    /// Returns an iterator over the nuclides in `nuclides` that have data, with the data, in the
+    /// order of `nuclides`.
+    ///
+    /// This works with anything that yields nuclides, such as a
+    /// [`Dataset`](crate::dataset::Dataset), or the nuclides from a
+    /// [`Filter`](crate::filter::Filter).
+    pub fn join<'a, F: 'a>(
+        &'a self,
+        nuclides: impl IntoIterator<Item = &'a Nuclide<F>> + 'a,
+    ) -> impl Iterator<Item = (&'a Nuclide<F>, &'a T)> + 'a {
+        nuclides
+            .into_iter()
+            .filter_map(|nuc| Some((nuc, self.of(nuc)?)))
+    }
+}
+
+impl<T> Default for Metadata<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<((u32, u32), T)> for Metadata<T> {
+    fn from_iter<I: IntoIterator<Item = ((u32, u32), T)>>(iter: I) -> Self {
+        Self {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<((u32, u32), T)> for Metadata<T> {
+    fn extend<I: IntoIterator<Item = ((u32, u32), T)>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Metadata<T> {
+    type Item = ((u32, u32), T);
+    type IntoIter = btree_map::IntoIter<(u32, u32), T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
@@ -0,0 +1,151 @@
+//! Reading the tables of mass models, such as FRDM2012, HFB-24, and WS4, as [`Nuclide`]s.
+//!
+//! The tables are columns separated by whitespace, and a [`Layout`] says which columns are Z,
+//! N or A, and the mass excess. There are layouts for the published tables, and others can be
+//! made for other tables, or for files that have been edited. Lines that don't start with a digit,
+//! such as headers and comments, are skipped.
+//!
+//! The mass excess is converted to keV, and the binding energy per nucleon and the atomic mass are
+//! computed from it. The values have no uncertainty, and are estimated, since they aren't
+//! measured. There is no origin or beta decay energy, and the nuclides beyond Og, which have no
+//! element symbol, are skipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::model::{self, Layout};
+//!
+//! let table = "  Z    N    A     M_th
+//!   8    8   16   -4.737
+//! ";
+//! let layout = Layout {
+//!     mass_excess: 3,
+//!     ..Layout::FRDM2012
+//! };
+//! let data = model::read(table.as_bytes(), &layout).unwrap();
+//! assert_eq!(&data[0].element, "O");
+//! assert_eq!(data[0].mass_excess.mean, -4_737.0);
+//! ```
+use crate::{constants::Constants, element, AmeError, Nuclide, Value};
+use arrayvec::ArrayString;
+use std::io::BufRead;
+
+/// Which columns of a table are which, counted from 0.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Layout {
+    /// The column of Z
+    pub z: usize,
+    /// The column of N or A
+    pub nucleons: Nucleons,
+    /// The column of the mass excess
+    pub mass_excess: usize,
+    /// The number of keV in the unit of the mass excess in the table, such as 1000 for
+    /// mega-electronvolts
+    pub unit: f64,
+}
+
+/// The column that has the number of neutrons or nucleons.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Nucleons {
+    /// The column of N
+    N(usize),
+    /// The column of A
+    A(usize),
+}
+
+impl Layout {
+    /// The FRDM2012 table of Möller et al., At. Data Nucl. Data Tables 109–110, 1 (2016)
+    ///
+    /// The columns are Z, N, A, ε₂, ε₃, ε₄, ε₆, β₂, β₃, β₄, β₆, E<sub>s+p</sub>,
+    /// E<sub>mic</sub>, E<sub>bind</sub>, M<sub>th</sub>, and more, in mega-electronvolts.
+    pub const FRDM2012: Self = Self {
+        z: 0,
+        nucleons: Nucleons::N(1),
+        mass_excess: 14,
+        unit: 1000.0,
+    };
+
+    /// The HFB tables of the Brussels-Montreal models, such as HFB-24, from BRUSLIB
+    ///
+    /// The columns are Z, A, β₂, β₄, R<sub>ch</sub>, E<sub>def</sub>, M<sub>th</sub>, and more,
+    /// in mega-electronvolts.
+    pub const HFB: Self = Self {
+        z: 0,
+        nucleons: Nucleons::A(1),
+        mass_excess: 6,
+        unit: 1000.0,
+    };
+
+    /// The WS4 table of Wang et al., Phys. Lett. B 734, 215 (2014)
+    ///
+    /// The columns are A, Z, β₂, β₄, β₆, E<sub>sh</sub>, M<sub>exp</sub>, M<sub>th</sub>, and
+    /// M<sub>th</sub> with the radial basis function correction, in mega-electronvolts.
+    pub const WS4: Self = Self {
+        z: 1,
+        nucleons: Nucleons::A(0),
+        mass_excess: 7,
+        unit: 1000.0,
+    };
+}
+
+/// Reads the nuclides from the table in `reader`, with the columns in `layout`.
+///
+/// # Errors
+///
+/// If reading fails, or a column is missing or fails to parse, an error is returned.
+pub fn read<R: BufRead>(reader: R, layout: &Layout) -> Result<Vec<Nuclide>, AmeError> {
+    let constants = Constants::AME2020;
+    let mut nuclides = Vec::new();
+    for (line, text) in (1..).zip(reader.lines()) {
+        let text = text?;
+        if !text.trim_start().starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        let invalid = |column| AmeError::InvalidColumn { line, column };
+        let int = |i: usize, column| -> Result<u32, AmeError> {
+            fields
+                .get(i)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| invalid(column))
+        };
+
+        let z = int(layout.z, "Z")?;
+        let n = match layout.nucleons {
+            Nucleons::N(i) => int(i, "N")?,
+            Nucleons::A(i) => int(i, "A")?.checked_sub(z).ok_or_else(|| invalid("A"))?,
+        };
+        let mass_excess = fields
+            .get(layout.mass_excess)
+            .and_then(|field| field.parse::<f64>().ok())
+            .filter(|x| x.is_finite())
+            .ok_or_else(|| invalid("mass excess"))?
+            * layout.unit;
+        let Some(element) = element::symbol(z).and_then(|symbol| ArrayString::from(symbol).ok())
+        else {
+            continue;
+        };
+
+        let value = |mean| Value {
+            mean,
+            uncertainty: 0.0,
+            is_estimated: true,
+        };
+        let a = z + n;
+        let binding_energy = f64::from(z) * constants.hydrogen_mass_excess
+            + f64::from(n) * constants.neutron_mass_excess
+            - mass_excess;
+        nuclides.push(Nuclide {
+            n,
+            z,
+            element,
+            origin: None,
+            mass_excess: value(mass_excess),
+            binding_energy_per_a: (a > 0).then(|| value(binding_energy / f64::from(a))),
+            decay_mode: None,
+            beta_decay_energy: None,
+            atomic_mass: value(f64::from(a) + constants.to_u(mass_excess)),
+        });
+    }
+    Ok(nuclides)
+}
@@ -0,0 +1,151 @@
+//! The masses of molecules and their ions, from a formula such as `12C 16O2`.
+//!
+//! A formula is a list of nuclides separated by spaces, each written with A before the symbol, and
+//! followed by the number of atoms of it if there is more than one, such as `1H2 16O` for water. A
+//! nuclide that is listed twice has the atoms of both.
+//!
+//! The mass of a molecule is the sum of the atomic masses, and that of its ion with a charge of q
+//! (in units of e) is less q electrons. The binding energies of the electrons, and of the molecule
+//! itself, which are at most a few keV, are left out. The uncertainties of the atoms of the same
+//! nuclide add, and those of different nuclides add in quadrature.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{dataset::{Dataset, Duplicates}, molecule::Molecule};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let molecule = Molecule::parse("1H2").unwrap();
+//! assert_eq!(molecule.atoms, [((1, 0), 2)]);
+//! // the ion H₂⁺
+//! let mass = molecule.mass(&dataset, 1).unwrap();
+//! assert!((mass.mean - 2.015_101_48).abs() < 1e-8);
+//! ```
+use crate::{constants::Constants, dataset::Dataset, element, Nuclide, Value};
+use std::fmt;
+
+/// A molecule, or a single atom.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Molecule {
+    /// The (Z, N) of the nuclides of the atoms, with the number of atoms of each, in the order of
+    /// the formula
+    pub atoms: Vec<((u32, u32), u32)>,
+}
+
+impl Molecule {
+    /// Parses a formula like `12C 16O2`, as described in the [module documentation](self).
+    ///
+    /// If it isn't a formula, or it is empty, `None` is returned.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut molecule = Self::default();
+        for token in s.split_whitespace() {
+            if !token.starts_with(|c: char| c.is_ascii_digit()) {
+                return None;
+            }
+            let end = token.rfind(|c: char| c.is_ascii_alphabetic())? + 1;
+            let (name, count) = token.split_at(end);
+            let count = match count {
+                "" => 1,
+                count => count.parse().ok().filter(|&count| count > 0)?,
+            };
+            molecule.add(element::nuclide(name)?, count);
+        }
+        (!molecule.atoms.is_empty()).then_some(molecule)
+    }
+
+    /// Adds `count` atoms of the nuclide with (Z, N) of `nuclide`.
+    pub fn add(&mut self, nuclide: (u32, u32), count: u32) {
+        match self.atoms.iter_mut().find(|(atom, _)| *atom == nuclide) {
+            Some((_, total)) => *total += count,
+            None => self.atoms.push((nuclide, count)),
+        }
+    }
+
+    /// Returns the mass, in u, of the ion of the molecule with a charge of `charge`, in units of
+    /// e, with the atomic masses in `dataset`.
+    ///
+    /// A negative charge adds electrons. If a nuclide isn't in the dataset, `None` is returned.
+    #[must_use]
+    pub fn mass(&self, dataset: &Dataset, charge: i32) -> Option<Value> {
+        Some(ion_mass(
+            &self.nuclides(dataset)?,
+            charge,
+            dataset.constants(),
+        ))
+    }
+
+    /// Returns the mass-to-charge ratio, in u/e, of the ion of the molecule with a charge of
+    /// `charge`, with the atomic masses in `dataset`.
+    ///
+    /// If `charge` is 0, or a nuclide isn't in the dataset, `None` is returned.
+    #[must_use]
+    pub fn mass_to_charge(&self, dataset: &Dataset, charge: i32) -> Option<Value> {
+        mass_to_charge(&self.nuclides(dataset)?, charge, dataset.constants())
+    }
+
+    // the nuclides of the atoms in `dataset`
+    fn nuclides<'a>(&self, dataset: &'a Dataset) -> Option<Vec<(&'a Nuclide, u32)>> {
+        self.atoms
+            .iter()
+            .map(|&((z, n), count)| Some((dataset.get(z, n)?, count)))
+            .collect()
+    }
+}
+
+/// Writes the formula, such as `12C 16O2`, which [`Molecule::parse`] parses.
+impl fmt::Display for Molecule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &((z, n), count)) in self.atoms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}{}", z + n, element::symbol(z).unwrap_or("?"))?;
+            if count > 1 {
+                write!(f, "{count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// the mass of the ion of a molecule of `atoms`
+pub(crate) fn ion_mass(atoms: &[(&Nuclide, u32)], charge: i32, constants: &Constants) -> Value {
+    let mut mass = 0.0;
+    let mut variance = 0.0;
+    let mut is_estimated = false;
+    for (nuc, count) in atoms {
+        let count = f64::from(*count);
+        mass += count * nuc.atomic_mass.mean;
+        variance += (count * nuc.atomic_mass.uncertainty).powi(2);
+        is_estimated |= nuc.atomic_mass.is_estimated;
+    }
+    let electrons = constants.to_u(f64::from(charge) * constants.electron_mass);
+    Value {
+        mean: mass - electrons,
+        uncertainty: variance.sqrt(),
+        is_estimated,
+    }
+}
+
+// the mass-to-charge ratio of the ion of a molecule of `atoms`
+pub(crate) fn mass_to_charge(
+    atoms: &[(&Nuclide, u32)],
+    charge: i32,
+    constants: &Constants,
+) -> Option<Value> {
+    if charge == 0 {
+        return None;
+    }
+    let mass = ion_mass(atoms, charge, constants);
+    let charge = f64::from(charge);
+    Some(Value {
+        mean: mass.mean / charge,
+        uncertainty: mass.uncertainty / charge.abs(),
+        is_estimated: mass.is_estimated,
+    })
+}
@@ -0,0 +1,141 @@
+//! Writing the inputs of astrophysical reaction network codes, in the formats of
+//! [JINA REACLIB](https://reaclib.jinaweb.org).
+//!
+//! The nuclides are named as in REACLIB, with the lowercase symbol and A, such as `he4`, except
+//! for `n`, `p`, `d`, and `t`.
+//!
+//! [`write_winvn`] writes the nuclear data, as in the `winvn` file. It starts with the temperatures
+//! of the partition functions, in GK, and the names of the nuclides, one per line. Then each
+//! nuclide has a line with its name, A, Z, N, spin, mass excess in mega-electronvolts, and source,
+//! and three lines of the partition function at the 24 temperatures. The evaluation has no spins
+//! or excited states, so the spins are given by a function, and the partition functions are 1, as
+//! for the ground state alone.
+//!
+//! [`write_q_values`] writes the Q-values of reactions in mega-electronvolts, as REACLIB has them,
+//! with the names of the nuclides, such as `p c12 -> n13 1.94350`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{network, parse_str};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let mut buf = Vec::new();
+//! network::write_winvn(&mut buf, &data, |_| 0.5).unwrap();
+//! let winvn = String::from_utf8(buf).unwrap();
+//! assert!(winvn.contains("\nn           1.000   0   1   0.5     8.071 ame20\n"));
+//! ```
+use crate::{dataset::Dataset, element, reaction::Reaction, AmeError, Nuclide};
+use std::io::Write;
+
+/// The temperatures of the partition functions, in GK
+pub const TEMPERATURES: [f64; 24] = [
+    0.1, 0.15, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0, 4.5, 5.0,
+    6.0, 7.0, 8.0, 9.0, 10.0,
+];
+
+// the label of the source of the masses
+const SOURCE: &str = "ame20";
+
+/// Returns the REACLIB name of the nuclide with `z` protons and `n` neutrons, such as `he4`.
+///
+/// If there is no element with `z` protons, `None` is returned.
+#[must_use]
+pub fn name(z: u32, n: u32) -> Option<String> {
+    Some(match (z, n) {
+        (0, 1) => "n".to_string(),
+        (1, 0) => "p".to_string(),
+        (1, 1) => "d".to_string(),
+        (1, 2) => "t".to_string(),
+        _ => format!("{}{}", element::symbol(z)?.to_lowercase(), z + n),
+    })
+}
+
+/// Writes `nuclides` to `writer`, as a `winvn` file, with the spin of each nuclide from `spin`.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write_winvn<W: Write>(
+    mut writer: W,
+    nuclides: &[Nuclide],
+    spin: impl Fn(&Nuclide) -> f64,
+) -> Result<(), AmeError> {
+    let names: Vec<String> = nuclides
+        .iter()
+        .map(|nuc| name(nuc.z, nuc.n).unwrap_or_else(|| nuc.element.to_lowercase()))
+        .collect();
+    for t9 in TEMPERATURES {
+        write!(writer, "{t9:>5}")?;
+    }
+    writeln!(writer)?;
+    for name in &names {
+        writeln!(writer, "{name:>5}")?;
+    }
+    for (nuc, name) in nuclides.iter().zip(&names) {
+        writeln!(
+            writer,
+            "{name:<5}{:>12.3}{:>4}{:>4}{:>6.1}{:>10.3} {SOURCE}",
+            f64::from(nuc.a()),
+            nuc.z,
+            nuc.n,
+            spin(nuc),
+            nuc.mass_excess.mean / 1000.0,
+        )?;
+        for line in TEMPERATURES.chunks(8) {
+            for _ in line {
+                write!(writer, "{:>12.3E}", 1.0)?;
+            }
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the Q-values of `reactions` with the masses in `dataset` to `writer`, one per line,
+/// with the names of the nuclides before and after, and the Q-value in mega-electronvolts.
+///
+/// The nuclides before are in the reverse order of [`Reaction::initial`], so that the projectile
+/// is before the target.
+///
+/// The reactions with a nuclide that isn't in the dataset, or has no name, are left out.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use ame2020::{dataset::Dataset, network, reaction::Reaction};
+/// # let dataset = Dataset::default();
+/// let reactions = [Reaction::parse("12C(p,g)13N").unwrap()];
+/// network::write_q_values(std::io::stdout(), &dataset, &reactions)?;
+/// # Ok::<_, ame2020::AmeError>(())
+/// ```
+pub fn write_q_values<W: Write>(
+    mut writer: W,
+    dataset: &Dataset,
+    reactions: &[Reaction],
+) -> Result<(), AmeError> {
+    let names = |side: &[(u32, u32)]| -> Option<Vec<String>> {
+        side.iter().map(|&(z, n)| name(z, n)).collect()
+    };
+    for reaction in reactions {
+        let (Some(initial), Some(products), Some(q)) = (
+            names(&reaction.initial),
+            names(&reaction.products),
+            dataset.reaction_q_value(reaction),
+        ) else {
+            continue;
+        };
+        // the projectile is first, as in REACLIB, and the target after it
+        let initial = initial.iter().rev().map(String::as_str);
+        let products = products.iter().map(String::as_str);
+        let nuclides: Vec<&str> = initial.chain(["->"]).chain(products).collect();
+        writeln!(writer, "{} {:.5}", nuclides.join(" "), q.mean / 1000.0)?;
+    }
+    Ok(())
+}
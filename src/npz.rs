@@ -0,0 +1,127 @@
+//! Writing the data as `NumPy` arrays, in an `.npz` file, so that it can be read with `numpy.load`.
+//!
+//! Each field is a column, with an array for each of the mean, the uncertainty, and whether the
+//! value is estimated:
+//!
+//! | Array | Type |
+//! |-------|------|
+//! | `z`, `n`, `a` | `uint32` |
+//! | `element`, `origin`, `decay_mode` | Unicode strings, empty when there is none |
+//! | `mass_excess`, `binding_energy_per_a`, `beta_decay_energy`, `atomic_mass` | `float64`, NaN when there is none |
+//! | `*_uncertainty` | `float64`, NaN when there is none |
+//! | `*_is_estimated` | `bool` |
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::{npz, Iter, Nuclide};
+//! use std::fs::File;
+//!
+//! let data: Vec<Nuclide> = Iter::from_path("mass.mas20")?.collect::<Result<_, _>>()?;
+//! npz::write(File::create("mass.npz")?, &data)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```python
+//! import numpy as np
+//!
+//! table = np.load("mass.npz")
+//! print(table["element"], table["mass_excess"])
+//! ```
+use crate::{AmeError, Nuclide, Value, VALUE_COLUMNS};
+use std::io::{self, Seek, Write};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+/// Writes `nuclides` to `writer`, as an `.npz` file.
+///
+/// The arrays are stored uncompressed.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write<W: Write + Seek>(writer: W, nuclides: &[Nuclide]) -> Result<(), AmeError> {
+    let mut zip = ZipWriter::new(writer);
+    let mut array = |name: &str, descr: &str, data: Vec<u8>| -> io::Result<()> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        zip.start_file(format!("{name}.npy"), options)?;
+        write_npy(&mut zip, descr, nuclides.len(), &data)
+    };
+
+    let uints = |f: fn(&Nuclide) -> u32| -> Vec<u8> {
+        nuclides
+            .iter()
+            .flat_map(|nuc| f(nuc).to_le_bytes())
+            .collect()
+    };
+    array("z", "<u4", uints(|nuc| nuc.z))?;
+    array("n", "<u4", uints(|nuc| nuc.n))?;
+    array("a", "<u4", uints(Nuclide::a))?;
+
+    let strings = |len: usize, f: fn(&Nuclide) -> &str| -> Vec<u8> {
+        let mut data = Vec::with_capacity(nuclides.len() * len * 4);
+        for nuc in nuclides {
+            let mut chars = f(nuc).chars();
+            for _ in 0..len {
+                data.extend(chars.next().map_or(0, u32::from).to_le_bytes());
+            }
+        }
+        data
+    };
+    array("element", "<U3", strings(3, |nuc| &nuc.element))?;
+    array(
+        "origin",
+        "<U4",
+        strings(4, |nuc| nuc.origin.as_deref().unwrap_or_default()),
+    )?;
+    array(
+        "decay_mode",
+        "<U2",
+        strings(2, |nuc| nuc.decay_mode.map_or("", |mode| mode.label())),
+    )?;
+
+    for (name, f) in VALUE_COLUMNS {
+        let floats = |g: fn(&Value) -> f64| -> Vec<u8> {
+            nuclides
+                .iter()
+                .flat_map(|nuc| f(nuc).map_or(f64::NAN, g).to_le_bytes())
+                .collect()
+        };
+        array(name, "<f8", floats(|value| value.mean))?;
+        array(
+            &format!("{name}_uncertainty"),
+            "<f8",
+            floats(|value| value.uncertainty),
+        )?;
+        let estimated = nuclides
+            .iter()
+            .map(|nuc| u8::from(f(nuc).is_some_and(|value| value.is_estimated)))
+            .collect();
+        array(&format!("{name}_is_estimated"), "|b1", estimated)?;
+    }
+
+    zip.finish().map_err(io::Error::from)?;
+    Ok(())
+}
+
+// writes a 1-D array in the .npy format, version 1.0
+fn write_npy<W: Write>(mut writer: W, descr: &str, len: usize, data: &[u8]) -> io::Result<()> {
+    const MAGIC: &[u8] = b"\x93NUMPY\x01\x00";
+
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': ({len},), }}");
+    // the header is padded with spaces and a newline, so that the data is aligned to 64 bytes
+    let total = (MAGIC.len() + 2 + header.len() + 1).next_multiple_of(64);
+    let header_len = total - MAGIC.len() - 2;
+    while header.len() < header_len - 1 {
+        header.push(' ');
+    }
+    header.push('\n');
+    let header_len = u16::try_from(header_len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "header too long"))?;
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&header_len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(data)
+}
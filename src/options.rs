@@ -0,0 +1,155 @@
+//! Configuration of the parsing, with [`ParseOptions`].
+use crate::Nuclide;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Which checks are done on each line, beyond parsing the fields.
+///
+/// The A column and the element symbol are redundant with N and Z, so a mismatch usually means
+/// that the columns of the line are shifted.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum Validation {
+    /// Only parse the fields that are stored. The A column isn't read.
+    #[default]
+    Off,
+    /// Check that the A column is N+Z, and that the element symbol matches Z, and add a
+    /// [`Warning`] for each mismatch. The A column must still be an integer.
+    ///
+    /// [`Warning`]: crate::Warning
+    Warn,
+    /// Check that the A column is N+Z, and that the element symbol matches Z, and return an error
+    /// for a mismatch
+    CrossCheck,
+}
+
+/// What a placeholder ('*') in an optional value does.
+///
+/// A placeholder in a required value is always [`AmeError::Placeholder`][crate::AmeError::Placeholder].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum Placeholders {
+    /// The value is `None`
+    #[default]
+    None,
+    /// Return [`AmeError::Placeholder`][crate::AmeError::Placeholder]
+    Error,
+}
+
+/// What happens to nuclides with estimated values.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum Estimated {
+    /// Keep them, with [`Value::is_estimated`][crate::Value::is_estimated] set
+    #[default]
+    Keep,
+    /// Skip any nuclide that has an estimated value
+    Skip,
+}
+
+/// The configuration of the iterators, for [`Iter::with_options`][crate::Iter::with_options] and
+/// the like.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{options::Estimated, Iter, ParseOptions};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014");
+/// let options = ParseOptions::new()
+///     .estimated(Estimated::Skip)
+///     .stop_after(|nuc| nuc.z == 0);
+/// let data = Iter::with_options(reader, options).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(data.len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    pub(crate) lenient: bool,
+    pub(crate) validation: Validation,
+    pub(crate) placeholders: Placeholders,
+    pub(crate) estimated: Estimated,
+    pub(crate) concatenated: bool,
+    #[allow(clippy::type_complexity)]
+    pub(crate) stop_after: Option<Arc<dyn Fn(&Nuclide) -> bool + Send + Sync>>,
+}
+
+impl ParseOptions {
+    /// Creates the default options, which are the same as [`Iter::new`][crate::Iter::new].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether lines that fail to parse are skipped, as with
+    /// [`Iter::lenient`][crate::Iter::lenient].
+    #[must_use]
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets which checks are done on each line.
+    #[must_use]
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Sets what a placeholder in an optional value does.
+    #[must_use]
+    pub fn placeholders(mut self, placeholders: Placeholders) -> Self {
+        self.placeholders = placeholders;
+        self
+    }
+
+    /// Sets what happens to nuclides with estimated values.
+    #[must_use]
+    pub fn estimated(mut self, estimated: Estimated) -> Self {
+        self.estimated = estimated;
+        self
+    }
+
+    /// Sets whether the data can be several tables, one after another.
+    ///
+    /// A line of the body that starts with a page feed ('1') is then the start of a new preamble,
+    /// and the parser reads it as it did the first, with a [`Warning::NewTable`] at the boundary.
+    /// Otherwise, it is a nuclide that fails to parse. The tables may have nuclides in common,
+    /// which [`Dataset`][crate::dataset::Dataset] can detect.
+    ///
+    /// [`Warning::NewTable`]: crate::Warning::NewTable
+    #[must_use]
+    pub fn concatenated(mut self, concatenated: bool) -> Self {
+        self.concatenated = concatenated;
+        self
+    }
+
+    /// Stops the iterator after the first nuclide for which `predicate` returns `true`.
+    ///
+    /// That nuclide is still returned. This is useful when only the start of the table is needed,
+    /// since it is sorted by A.
+    #[must_use]
+    pub fn stop_after(
+        mut self,
+        predicate: impl Fn(&Nuclide) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.stop_after = Some(Arc::new(predicate));
+        self
+    }
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("lenient", &self.lenient)
+            .field("validation", &self.validation)
+            .field("placeholders", &self.placeholders)
+            .field("estimated", &self.estimated)
+            .field("concatenated", &self.concatenated)
+            .field("stop_after", &self.stop_after.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
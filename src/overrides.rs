@@ -0,0 +1,96 @@
+//! Overriding the nuclides of a dataset, such as with newer measurements.
+//!
+//! [`Dataset::apply_overrides`] replaces the nuclides that are in the dataset, and inserts the
+//! rest, and records each in an [`Override`]. The overrides can be any [`Nuclide`]s, or they can
+//! be read from a patch file with [`read`].
+//!
+//! The format of a patch file is a line for each nuclide, with the name of the nuclide, such as
+//! `26Al` (as in [`element::nuclide`]), its mass excess, and the uncertainty, in keV, separated by
+//! whitespace. A `#` after the mass excess marks it as estimated, as in the table. Blank lines and
+//! lines starting with `#` are skipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{dataset::{Dataset, Duplicates}, overrides};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+//! let mut dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! let patch = overrides::read(&b"# our new masses
+//! 1n 8071.3171 0.0005
+//! 2H 13135.722895 0.000015
+//! "[..]).unwrap();
+//! dataset.apply_overrides(patch);
+//! assert_eq!(dataset.len(), 2);
+//! assert_eq!(dataset.overrides().len(), 2);
+//! // the neutron was replaced, and H-2 inserted
+//! assert!(dataset.overrides()[0].replaced.is_some());
+//! assert!(dataset.overrides()[1].replaced.is_none());
+//! ```
+#[cfg(doc)]
+use crate::dataset::Dataset;
+use crate::{element, AmeError, Nuclide, NuclideBuilder, Value};
+use std::io::BufRead;
+
+/// A nuclide that was replaced or inserted by [`Dataset::apply_overrides`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Override {
+    /// Proton number
+    pub z: u32,
+    /// Neutron number
+    pub n: u32,
+    /// The nuclide that was replaced, or `None` if it was inserted
+    pub replaced: Option<Nuclide>,
+}
+
+/// Reads the nuclides in `reader`, in the format in the [module documentation](self).
+///
+/// The binding energy per nucleon and the atomic mass are computed from the mass excess, with its
+/// uncertainty, as by [`NuclideBuilder`]. There is no origin or beta decay energy.
+///
+/// # Errors
+///
+/// If reading fails, an error is returned. If a line doesn't have a nuclide with an element
+/// symbol, a mass excess, and a non-negative uncertainty, it is [`AmeError::InvalidColumn`], with
+/// the column that is invalid.
+pub fn read<R: BufRead>(reader: R) -> Result<Vec<Nuclide>, AmeError> {
+    let mut nuclides = Vec::new();
+    for (line, text) in (1..).zip(reader.lines()) {
+        let text = text?;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let mut fields = text.split_whitespace();
+        let invalid = |column| AmeError::InvalidColumn { line, column };
+        let (z, n) = fields
+            .next()
+            .and_then(element::nuclide)
+            .ok_or_else(|| invalid("nuclide"))?;
+        let mass_excess = fields.next().ok_or_else(|| invalid("mass excess"))?;
+        let is_estimated = mass_excess.ends_with('#');
+        let mass_excess = mass_excess
+            .trim_end_matches('#')
+            .parse::<f64>()
+            .ok()
+            .filter(|x| x.is_finite())
+            .ok_or_else(|| invalid("mass excess"))?;
+        let uncertainty = fields
+            .next()
+            .and_then(|field| field.trim_end_matches('#').parse::<f64>().ok())
+            .filter(|x| x.is_finite() && *x >= 0.0)
+            .ok_or_else(|| invalid("uncertainty"))?;
+        let nuclide = NuclideBuilder::new(z, n)
+            .mass_excess(Value {
+                mean: mass_excess,
+                uncertainty,
+                is_estimated,
+            })
+            .build()
+            .map_err(|_| invalid("nuclide"))?;
+        nuclides.push(nuclide);
+    }
+    Ok(nuclides)
+}
@@ -0,0 +1,107 @@
+//! Parsing in parallel with [rayon](https://crates.io/crates/rayon).
+//!
+//! The preamble and headers are read in order, and then the body is parsed in parallel, in chunks
+//! of lines. Each chunk is parsed as if it is in the body, which is checked as the chunks are
+//! joined in order, and a chunk that isn't, such as one that starts in the headers of a
+//! [concatenated][ParseOptions::concatenated] table, is parsed again in order. The results are the
+//! same as from [`SliceIter`][crate::SliceIter] with the same options.
+use crate::{parser::Parser, slice::Lines, AmeError, Nuclide, ParseOptions};
+use rayon::prelude::*;
+use std::ops::ControlFlow;
+
+// the number of lines in each chunk of the body
+const CHUNK_LINES: usize = 256;
+
+/// Parses all of the data in `data` in parallel.
+///
+/// # Examples
+///
+/// ```
+/// let data = br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+/// let nuclides = ame2020::par::parse_slice(data).unwrap();
+/// assert_eq!(nuclides.len(), 1);
+/// ```
+///
+/// # Errors
+///
+/// If any line fails to parse, the first error is returned.
+pub fn parse_slice(data: &[u8]) -> Result<Vec<Nuclide>, AmeError> {
+    parse_slice_with_options(data, &ParseOptions::default())
+}
+
+/// Parses all of the data in `data` in parallel, according to `options`.
+///
+/// The nuclides are those that [`SliceIter::with_options`][crate::SliceIter::with_options] would
+/// yield. Like [`parse_str`][crate::parse_str], the warnings and the lines skipped by
+/// [`lenient`][ParseOptions::lenient] aren't returned.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::ParseOptions;
+///
+/// let data = br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+///  not a nuclide";
+/// let options = ParseOptions::new().lenient(true);
+/// let nuclides = ame2020::par::parse_slice_with_options(data, &options).unwrap();
+/// assert_eq!(nuclides.len(), 1);
+/// ```
+///
+/// # Errors
+///
+/// If any line fails to parse, and `options` isn't lenient, the first error is returned.
+pub fn parse_slice_with_options(
+    data: &[u8],
+    options: &ParseOptions,
+) -> Result<Vec<Nuclide>, AmeError> {
+    let mut lines = Lines::new(data);
+    let mut parser = Parser::new(options.clone());
+    let mut nuclides = Vec::new();
+    while !parser.in_body() && !parser.is_stopped() {
+        let Some(line) = lines.next() else {
+            return Ok(nuclides);
+        };
+        if let ControlFlow::Break(res) = parser.parse_line(line) {
+            nuclides.push(res?);
+        }
+    }
+
+    let first_line = parser.line_number();
+    let rest = lines.collect::<Vec<_>>();
+    let chunks = rest
+        .par_chunks(CHUNK_LINES)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut chunk_parser =
+                Parser::in_body_after(options.clone(), first_line + i * CHUNK_LINES);
+            let res = parse_lines(&mut chunk_parser, chunk);
+            (chunk_parser, res)
+        })
+        .collect::<Vec<_>>();
+    for ((chunk_parser, res), chunk) in chunks.into_iter().zip(rest.chunks(CHUNK_LINES)) {
+        if parser.is_stopped() {
+            break;
+        } else if parser.in_body() {
+            parser = chunk_parser;
+            nuclides.extend(res?);
+        } else {
+            nuclides.extend(parse_lines(&mut parser, chunk)?);
+        }
+    }
+    Ok(nuclides)
+}
+
+// parses `lines` in order, up to the first error
+fn parse_lines(parser: &mut Parser, lines: &[&[u8]]) -> Result<Vec<Nuclide>, AmeError> {
+    let mut nuclides = Vec::new();
+    for line in lines {
+        if let ControlFlow::Break(res) = parser.parse_line(line) {
+            nuclides.push(res?);
+        }
+    }
+    Ok(nuclides)
+}
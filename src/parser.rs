@@ -0,0 +1,710 @@
+use crate::{
+    options::{Estimated, Placeholders, Validation},
+    AmeError, DecayMode, Field, Float, Nuclide, ParseOptions, Value, Warning,
+};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use arrayvec::ArrayString;
+use core::{
+    num::{ParseFloatError, ParseIntError},
+    ops::{ControlFlow, Range},
+    str::FromStr,
+};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum ReadState {
+    Start,
+    Preamble,
+    Headers,
+    Body,
+    // a nuclide matched `ParseOptions::stop_after`
+    Stopped,
+}
+
+/// The line-by-line parsing shared by the iterators.
+#[derive(Clone, Debug)]
+pub(crate) struct Parser {
+    state: ReadState,
+    // the number of lines seen so far, which is the number of the most recent line
+    line: usize,
+    options: ParseOptions,
+    skipped: Vec<AmeError>,
+    warnings: Vec<Warning>,
+    // the number of lines in the headers so far
+    headers: usize,
+}
+
+impl Parser {
+    pub(crate) fn new(options: ParseOptions) -> Self {
+        Self {
+            state: ReadState::Start,
+            line: 0,
+            options,
+            skipped: Vec::new(),
+            warnings: Vec::new(),
+            headers: 0,
+        }
+    }
+
+    /// Creates a parser that has read `line` lines, and is in the body.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn in_body_after(options: ParseOptions, line: usize) -> Self {
+        Self {
+            state: ReadState::Body,
+            line,
+            ..Self::new(options)
+        }
+    }
+
+    /// Sets whether bad lines are skipped, instead of being returned as errors.
+    pub(crate) fn set_lenient(&mut self, lenient: bool) {
+        self.options.lenient = lenient;
+    }
+
+    /// Returns the errors for the lines that were skipped.
+    pub(crate) fn skipped(&self) -> &[AmeError] {
+        &self.skipped
+    }
+
+    /// Returns the warnings so far.
+    pub(crate) fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Returns the number of the most recent line, starting from 1.
+    pub(crate) fn line_number(&self) -> usize {
+        self.line
+    }
+
+    /// Returns whether the next line is in the body.
+    pub(crate) fn in_body(&self) -> bool {
+        self.state == ReadState::Body
+    }
+
+    /// Returns whether every following line is a nuclide, and is returned.
+    pub(crate) fn in_body_exact(&self) -> bool {
+        self.in_body()
+            && !self.options.lenient
+            && self.options.estimated == Estimated::Keep
+            && self.options.stop_after.is_none()
+            && !self.options.concatenated
+    }
+
+    /// Returns whether no more nuclides will be returned, because of `ParseOptions::stop_after`.
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.state == ReadState::Stopped
+    }
+
+    /// Parses a line, without its line ending.
+    ///
+    /// Lines are bytes rather than `str`, so that invalid UTF-8 outside of the fields, such as in
+    /// the preamble, is ignored.
+    pub(crate) fn parse_line<F: Float>(
+        &mut self,
+        line: &[u8],
+    ) -> ControlFlow<Result<Nuclide<F>, AmeError>> {
+        self.line += 1;
+        match self.state {
+            ReadState::Start => {
+                if line.starts_with(b"1") {
+                    self.state = ReadState::Preamble;
+                }
+                ControlFlow::Continue(())
+            }
+            ReadState::Preamble => {
+                if line.starts_with(b"1") {
+                    self.state = ReadState::Headers;
+                }
+                ControlFlow::Continue(())
+            }
+            ReadState::Headers => {
+                if line.starts_with(b"0") {
+                    self.state = ReadState::Body;
+                    let res = parse_nuclide(line, self.line, &self.options, &mut self.warnings);
+                    self.line_result(res)
+                } else {
+                    // the first is the units
+                    self.headers += 1;
+                    if self.headers > 1 {
+                        self.warnings.push(Warning::ExtraHeader { line: self.line });
+                    }
+                    ControlFlow::Continue(())
+                }
+            }
+            ReadState::Body if self.options.concatenated && line.starts_with(b"1") => {
+                // the page feed that starts the preamble of the next table
+                self.state = ReadState::Preamble;
+                self.headers = 0;
+                self.warnings.push(Warning::NewTable { line: self.line });
+                ControlFlow::Continue(())
+            }
+            ReadState::Body if is_blank(line) => {
+                self.warnings.push(Warning::BlankLine { line: self.line });
+                ControlFlow::Continue(())
+            }
+            ReadState::Body => {
+                let res = parse_nuclide(line, self.line, &self.options, &mut self.warnings);
+                self.line_result(res)
+            }
+            ReadState::Stopped => ControlFlow::Continue(()),
+        }
+    }
+
+    fn line_result<F: Float>(
+        &mut self,
+        res: Result<Nuclide<F>, AmeError>,
+    ) -> ControlFlow<Result<Nuclide<F>, AmeError>> {
+        match res {
+            Err(e) if self.options.lenient => {
+                self.skipped.push(e);
+                ControlFlow::Continue(())
+            }
+            Ok(nuclide) if self.options.estimated == Estimated::Skip && has_estimated(&nuclide) => {
+                ControlFlow::Continue(())
+            }
+            Ok(nuclide) => {
+                if self
+                    .options
+                    .stop_after
+                    .as_ref()
+                    // the predicate takes `f64`s, which every `Float` converts to exactly
+                    .is_some_and(|stop| stop(&nuclide.clone().map(Float::to_f64)))
+                {
+                    self.state = ReadState::Stopped;
+                }
+                ControlFlow::Break(Ok(nuclide))
+            }
+            res => ControlFlow::Break(res),
+        }
+    }
+}
+
+/// Returns whether a line has only whitespace, so it can't be a nuclide.
+pub(crate) fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+fn has_estimated<F>(nuclide: &Nuclide<F>) -> bool {
+    [
+        Some(&nuclide.mass_excess),
+        nuclide.binding_energy_per_a.as_ref(),
+        nuclide.beta_decay_energy.as_ref(),
+        Some(&nuclide.atomic_mass),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|value| value.is_estimated)
+}
+
+/// An error in a field, before the line number is attached.
+#[derive(Debug)]
+struct LineError {
+    field: Field,
+    columns: Range<usize>,
+    kind: LineErrorKind,
+}
+
+#[derive(Debug)]
+enum LineErrorKind {
+    ParseInt(ParseIntError),
+    ParseFloat(ParseFloatError),
+    TooShortLine,
+    StrIndex,
+    Placeholder,
+    NonFinite,
+    NegativeUncertainty,
+    #[cfg(feature = "decimal")]
+    Decimal,
+    MassNumber {
+        a: u32,
+        expected: u32,
+    },
+    Element {
+        element: ArrayString<3>,
+        z: u32,
+    },
+    DecayMode {
+        mode: ArrayString<2>,
+    },
+}
+
+impl LineError {
+    fn at(self, line: usize) -> AmeError {
+        let Self {
+            field,
+            columns,
+            kind,
+        } = self;
+        match kind {
+            LineErrorKind::ParseInt(source) => AmeError::ParseInt {
+                line,
+                field,
+                columns,
+                source,
+            },
+            LineErrorKind::ParseFloat(source) => AmeError::ParseFloat {
+                line,
+                field,
+                columns,
+                source,
+            },
+            LineErrorKind::TooShortLine => AmeError::TooShortLine {
+                line,
+                field,
+                columns,
+            },
+            LineErrorKind::StrIndex => AmeError::StrIndex {
+                line,
+                field,
+                columns,
+            },
+            LineErrorKind::Placeholder => AmeError::Placeholder {
+                line,
+                field,
+                columns,
+            },
+            LineErrorKind::NonFinite => AmeError::NonFinite {
+                line,
+                field,
+                columns,
+            },
+            LineErrorKind::NegativeUncertainty => AmeError::NegativeUncertainty {
+                line,
+                field,
+                columns,
+            },
+            #[cfg(feature = "decimal")]
+            LineErrorKind::Decimal => AmeError::Decimal {
+                line,
+                field,
+                columns,
+            },
+            LineErrorKind::MassNumber { a, expected } => AmeError::MassNumber {
+                line,
+                columns,
+                a,
+                expected,
+            },
+            LineErrorKind::Element { element, z } => AmeError::Element {
+                line,
+                columns,
+                element,
+                z,
+            },
+            LineErrorKind::DecayMode { mode } => AmeError::DecayMode {
+                line,
+                columns,
+                mode,
+            },
+        }
+    }
+}
+
+fn field_bytes(line: &[u8], field: Field, range: Range<usize>) -> Result<&[u8], LineError> {
+    line.get(range.clone()).ok_or(LineError {
+        field,
+        columns: range,
+        kind: LineErrorKind::TooShortLine,
+    })
+}
+
+fn range_err(line: &[u8], field: Field, range: Range<usize>) -> Result<&str, LineError> {
+    let bytes = field_bytes(line, field, range.clone())?;
+    // a multi-byte char that crosses the edge of the field is invalid in both fields
+    Ok(core::str::from_utf8(bytes)
+        .map_err(|_| LineError {
+            field,
+            columns: range,
+            kind: LineErrorKind::StrIndex,
+        })?
+        .trim())
+}
+
+fn parse_int<T: FromStr<Err = ParseIntError>>(
+    line: &[u8],
+    field: Field,
+    range: Range<usize>,
+) -> Result<T, LineError> {
+    range_err(line, field, range.clone())?
+        .parse()
+        .map_err(|e| LineError {
+            field,
+            columns: range,
+            kind: LineErrorKind::ParseInt(e),
+        })
+}
+
+/// Parses the element symbol, replacing invalid UTF-8 with U+FFFD, and returns whether any was
+/// replaced.
+fn parse_element(line: &[u8]) -> Result<(ArrayString<3>, bool), LineError> {
+    let lossy = String::from_utf8_lossy(field_bytes(line, Field::Element, 20..23)?);
+    let mut element = ArrayString::new();
+    // the replacement char is 3 bytes, so more than one doesn't fit
+    for c in lossy.trim().chars() {
+        if element.try_push(c).is_err() {
+            break;
+        }
+    }
+    Ok((element, matches!(lossy, Cow::Owned(_))))
+}
+
+fn parse_value<F: Float>(
+    line: &[u8],
+    (f_mean, r_mean): (Field, Range<usize>),
+    (f_unc, r_unc): (Field, Range<usize>),
+) -> Result<Value<F>, LineError> {
+    let columns = r_mean.start..r_unc.end;
+    // `f64::from_str` accepts "nan" and "inf", but they are never in the table
+    let finite = |x: F, field, columns| {
+        if x.is_finite() {
+            Ok(x)
+        } else {
+            Err(LineError {
+                field,
+                columns,
+                kind: LineErrorKind::NonFinite,
+            })
+        }
+    };
+    let mean = finite(
+        parse_estimated(line, f_mean, r_mean.clone())?,
+        f_mean,
+        r_mean,
+    )?;
+    let uncertainty = finite(
+        parse_estimated(line, f_unc, r_unc.clone())?,
+        f_unc,
+        r_unc.clone(),
+    )?;
+    // zero is allowed, since some values are exact, such as the mass excess of C-12
+    if uncertainty.is_negative() {
+        return Err(LineError {
+            field: f_unc,
+            columns: r_unc,
+            kind: LineErrorKind::NegativeUncertainty,
+        });
+    }
+    // only this value's columns, since other values on the line can be estimated when it isn't.
+    // both fields were parsed, so the line is long enough.
+    let is_estimated = line[columns].contains(&b'#');
+    Ok(Value {
+        mean,
+        uncertainty,
+        is_estimated,
+    })
+}
+
+/// Parses a value, which is `None` if the mean is a placeholder ('*'), unless placeholders are
+/// errors.
+///
+/// The uncertainty of a placeholder is blank, so it isn't checked.
+fn parse_optional_value<F: Float>(
+    line: &[u8],
+    (f_mean, r_mean): (Field, Range<usize>),
+    (f_unc, r_unc): (Field, Range<usize>),
+    placeholders: Placeholders,
+) -> Result<Option<Value<F>>, LineError> {
+    if placeholders == Placeholders::None && range_err(line, f_mean, r_mean.clone())? == "*" {
+        Ok(None)
+    } else {
+        parse_value(line, (f_mean, r_mean), (f_unc, r_unc)).map(Some)
+    }
+}
+
+/// Parses a float, where estimated values use '#' in place of '.'.
+fn parse_estimated<F: Float>(
+    line: &[u8],
+    field: Field,
+    range: Range<usize>,
+) -> Result<F, LineError> {
+    parse_estimated_with(line, field, range, F::parse, LineErrorKind::ParseFloat)
+}
+
+/// Parses a number with `parse`, where estimated values use '#' in place of '.', and a failure is
+/// `kind`.
+fn parse_estimated_with<T, E>(
+    line: &[u8],
+    field: Field,
+    range: Range<usize>,
+    parse: impl Fn(&str) -> Result<T, E>,
+    kind: impl FnOnce(E) -> LineErrorKind,
+) -> Result<T, LineError> {
+    // this is longer than any of the fields, except with unusual padding
+    const CAP: usize = 32;
+
+    let s = range_err(line, field, range.clone())?;
+    if s == "*" {
+        return Err(LineError {
+            field,
+            columns: range,
+            kind: LineErrorKind::Placeholder,
+        });
+    }
+    let res = if s.contains('#') {
+        let mut buf = [0; CAP];
+        match buf.get_mut(..s.len()) {
+            Some(buf) => {
+                for (b, c) in buf.iter_mut().zip(s.bytes()) {
+                    *b = if c == b'#' { b'.' } else { c };
+                }
+                parse(core::str::from_utf8(buf).expect("only an ascii char was replaced"))
+            }
+            None => parse(&s.replace('#', ".")),
+        }
+    } else {
+        parse(s)
+    };
+    res.map_err(|e| LineError {
+        field,
+        columns: range,
+        kind: kind(e),
+    })
+}
+
+/// Parses a line of the body, numbered `number`, adding any warnings about it to `warnings`.
+pub(crate) fn parse_nuclide<F: Float>(
+    line: &[u8],
+    number: usize,
+    options: &ParseOptions,
+    warnings: &mut Vec<Warning>,
+) -> Result<Nuclide<F>, AmeError> {
+    // the iterators already strip "\r\n", but a stray '\r' would shift the last field
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let checked = warnings.len();
+    let res = if line.contains(&b'\t') {
+        parse_fields(&expand_tabs(line), number, options, warnings)
+    } else {
+        parse_fields(line, number, options, warnings)
+    };
+    res.map_err(|e| {
+        // the warnings are only for lines that parse
+        warnings.truncate(checked);
+        e.at(number)
+    })
+}
+
+/// Parses the values of a line of the body, numbered `number`, as decimals, with the rest from
+/// `nuclide`, which was parsed from the same line.
+#[cfg(feature = "decimal")]
+pub(crate) fn parse_decimal_nuclide(
+    line: &[u8],
+    number: usize,
+    nuclide: Nuclide,
+) -> Result<Nuclide<Decimal>, AmeError> {
+    // the same normalization as `parse_nuclide`, so the columns line up
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let res = if line.contains(&b'\t') {
+        parse_decimal_fields(&expand_tabs(line), nuclide)
+    } else {
+        parse_decimal_fields(line, nuclide)
+    };
+    res.map_err(|e| e.at(number))
+}
+
+/// Replaces tabs with spaces, up to the next multiple of 8 columns.
+///
+/// The format never has tabs, but editors can replace runs of spaces with them, which shifts the
+/// fixed columns.
+pub(crate) fn expand_tabs(line: &[u8]) -> Vec<u8> {
+    const TAB_STOP: usize = 8;
+
+    let mut expanded = Vec::with_capacity(line.len() + TAB_STOP);
+    for &b in line {
+        if b == b'\t' {
+            let width = TAB_STOP - expanded.len() % TAB_STOP;
+            expanded.extend(core::iter::repeat_n(b' ', width));
+        } else {
+            expanded.push(b);
+        }
+    }
+    expanded
+}
+
+fn parse_fields<F: Float>(
+    line: &[u8],
+    number: usize,
+    options: &ParseOptions,
+    warnings: &mut Vec<Warning>,
+) -> Result<Nuclide<F>, LineError> {
+    let cross_check = options.validation != Validation::Off;
+    // a mismatch is an error, or a warning, depending on the validation
+    let mut mismatch = |error: LineError, warning: Warning| {
+        if options.validation == Validation::CrossCheck {
+            Err(error)
+        } else {
+            warnings.push(warning);
+            Ok(())
+        }
+    };
+    let n: u32 = parse_int(line, Field::N, 4..9)?;
+    let z = parse_int(line, Field::Z, 9..14)?;
+    if cross_check {
+        // A is redundant, but if it doesn't match, the columns are probably shifted
+        let a = parse_int(line, Field::A, 14..19)?;
+        if Some(a) != n.checked_add(z) {
+            let expected = n.saturating_add(z);
+            mismatch(
+                LineError {
+                    field: Field::A,
+                    columns: 14..19,
+                    kind: LineErrorKind::MassNumber { a, expected },
+                },
+                Warning::MassNumber {
+                    line: number,
+                    a,
+                    expected,
+                },
+            )?;
+        }
+    }
+    // invalid UTF-8 is replaced, so only a symbol that doesn't match Z is an error
+    let (element, is_lossy) = parse_element(line)?;
+    // like A, a wrong symbol means the line is misaligned. a replaced symbol can't match, but the
+    // bad byte is in place of the symbol, so the line isn't misaligned.
+    if cross_check && !is_lossy && crate::element::symbol(z) != Some(element.as_str()) {
+        mismatch(
+            LineError {
+                field: Field::Element,
+                columns: 20..23,
+                kind: LineErrorKind::Element { element, z },
+            },
+            Warning::Element {
+                line: number,
+                element,
+                z,
+            },
+        )?;
+    }
+    let origin = match range_err(line, Field::Origin, 23..27)? {
+        "" => None,
+        s => Some(ArrayString::from(s).expect("the range is 4 and the capacity is 4")),
+    };
+    let mass_excess = parse_value(
+        line,
+        (Field::MassExcess, 28..42),
+        (Field::MassExcessUncertainty, 42..54),
+    )?;
+    let binding_energy_per_a = parse_optional_value(
+        line,
+        (Field::BindingEnergyPerA, 54..67),
+        (Field::BindingEnergyPerAUncertainty, 68..78),
+        options.placeholders,
+    )?;
+    let decay_mode = match range_err(line, Field::DecayMode, 79..81)? {
+        "" => None,
+        s => Some(DecayMode::from_label(s).ok_or_else(|| LineError {
+            field: Field::DecayMode,
+            columns: 79..81,
+            kind: LineErrorKind::DecayMode {
+                mode: ArrayString::from(s).expect("the range is 2 and the capacity is 2"),
+            },
+        })?),
+    };
+    let beta_decay_energy = parse_optional_value(
+        line,
+        (Field::BetaDecayEnergy, 81..94),
+        (Field::BetaDecayEnergyUncertainty, 94..105),
+        options.placeholders,
+    )?;
+
+    // the value is given in micro-u, with a space before the 1e6 place.
+    // this makes it inconvenient to parse in u.
+    //
+    // lines don't have the same length, so use `line.len()`. you could use a RangeFrom,
+    // but that would require rewriting `parse_value` and `range_err` to be generic, and it
+    // would lead to more complicated bounds checks.
+    let mut atomic_mass: Value<F> = parse_value(
+        line,
+        (Field::AtomicMass, 110..123),
+        (Field::AtomicMassUncertainty, 123..(line.len())),
+    )?;
+    atomic_mass.mean = F::from_micro(
+        parse_int(line, Field::AtomicMass, 106..109)?,
+        atomic_mass.mean,
+    );
+    atomic_mass.uncertainty = F::from_micro(0, atomic_mass.uncertainty);
+
+    Ok(Nuclide {
+        n,
+        z,
+        element,
+        origin,
+        mass_excess,
+        binding_energy_per_a,
+        decay_mode,
+        beta_decay_energy,
+        atomic_mass,
+    })
+}
+
+// the fields were already checked by `parse_fields`, so only the conversion to `Decimal` can fail,
+// if a value has more digits than it holds
+#[cfg(feature = "decimal")]
+fn parse_decimal_fields(line: &[u8], nuclide: Nuclide) -> Result<Nuclide<Decimal>, LineError> {
+    let parse = |field, range| {
+        parse_estimated_with(line, field, range, Decimal::from_str, |_| {
+            LineErrorKind::Decimal
+        })
+    };
+    let value = |value: Value, (f_mean, r_mean), (f_unc, r_unc)| {
+        Ok(Value {
+            mean: parse(f_mean, r_mean)?,
+            uncertainty: parse(f_unc, r_unc)?,
+            is_estimated: value.is_estimated,
+        })
+    };
+    // placeholders are `None` in `nuclide`, so their columns aren't parsed
+    let optional = |v: Option<Value>, mean, unc| v.map(|v| value(v, mean, unc)).transpose();
+
+    let mass_excess = value(
+        nuclide.mass_excess,
+        (Field::MassExcess, 28..42),
+        (Field::MassExcessUncertainty, 42..54),
+    )?;
+    let binding_energy_per_a = optional(
+        nuclide.binding_energy_per_a,
+        (Field::BindingEnergyPerA, 54..67),
+        (Field::BindingEnergyPerAUncertainty, 68..78),
+    )?;
+    let beta_decay_energy = optional(
+        nuclide.beta_decay_energy,
+        (Field::BetaDecayEnergy, 81..94),
+        (Field::BetaDecayEnergyUncertainty, 94..105),
+    )?;
+
+    // the micro-u are moved 6 places, which is exact, unlike multiplying by 1e-6 as a float
+    let micro = |field, columns: Range<usize>, mut x: Decimal| {
+        x.set_scale(x.scale() + 6).map_err(|_| LineError {
+            field,
+            columns,
+            kind: LineErrorKind::Decimal,
+        })?;
+        Ok(x)
+    };
+    let unc_columns = 123..(line.len());
+    let mut atomic_mass = value(
+        nuclide.atomic_mass,
+        (Field::AtomicMass, 110..123),
+        (Field::AtomicMassUncertainty, unc_columns.clone()),
+    )?;
+    atomic_mass.mean = micro(Field::AtomicMass, 110..123, atomic_mass.mean)?;
+    atomic_mass.uncertainty = micro(
+        Field::AtomicMassUncertainty,
+        unc_columns,
+        atomic_mass.uncertainty,
+    )?;
+    atomic_mass.mean += Decimal::from(parse_int::<u16>(line, Field::AtomicMass, 106..109)?);
+
+    Ok(Nuclide {
+        n: nuclide.n,
+        z: nuclide.z,
+        element: nuclide.element,
+        origin: nuclide.origin,
+        mass_excess,
+        binding_energy_per_a,
+        decay_mode: nuclide.decay_mode,
+        beta_decay_energy,
+        atomic_mass,
+    })
+}
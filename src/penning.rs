@@ -0,0 +1,358 @@
+//! Mass-to-charge ratios and cyclotron frequency ratios of ions, as measured in Penning traps.
+//!
+//! A Penning trap measures the cyclotron frequency ν<sub>c</sub> = qB / (2πm) of an ion, and the
+//! mass is found from the ratio of the frequencies of two ions in the same field, which cancels B.
+//! The mass of an ion with charge q (in units of e) is that of the atom less q electrons, and the
+//! binding energies of the electrons, which are at most a few keV, are left out.
+//!
+//! The uncertainties of the two atomic masses are taken as independent.
+//!
+//! [`identify`] finds the atoms and molecules that could be an ion with a measured mass-to-charge
+//! ratio, and [`doublets`] finds pairs of ions with close mass-to-charge ratios, as for reference
+//! ions in a measurement.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, penning};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! // a proton, which is 1H⁺
+//! let proton = penning::mass_to_charge(&data[1], 1).unwrap();
+//! assert!((proton.mean - 1.007_276_45).abs() < 1e-8);
+//! ```
+use crate::{
+    constants::Constants,
+    dataset::Dataset,
+    molecule::{self, Molecule},
+    Nuclide, Value,
+};
+use std::{collections::BTreeMap, f64::consts::PI};
+
+// the constants for all of the conversions
+const CONSTANTS: Constants = Constants::AME2020;
+
+/// Returns the mass, in u, of the ion of `nuclide` with a charge of `charge`, in units of e.
+///
+/// A negative charge adds electrons.
+#[must_use]
+pub fn ion_mass(nuclide: &Nuclide, charge: i32) -> Value {
+    molecule::ion_mass(&[(nuclide, 1)], charge, &CONSTANTS)
+}
+
+/// Returns the mass-to-charge ratio, in u/e, of the ion of `nuclide` with a charge of `charge`.
+///
+/// If `charge` is 0, `None` is returned.
+#[must_use]
+pub fn mass_to_charge(nuclide: &Nuclide, charge: i32) -> Option<Value> {
+    molecule::mass_to_charge(&[(nuclide, 1)], charge, &CONSTANTS)
+}
+
+/// Returns the cyclotron frequency, in Hz, of the ion of `nuclide` with a charge of `charge`, in
+/// a magnetic field of `field` tesla.
+///
+/// If `charge` is 0, `None` is returned.
+#[must_use]
+pub fn cyclotron_frequency(nuclide: &Nuclide, charge: i32, field: f64) -> Option<Value> {
+    let mass_to_charge = mass_to_charge(nuclide, charge)?;
+    let mean = field * CONSTANTS.charge_per_mass / (2.0 * PI * mass_to_charge.mean);
+    Some(Value {
+        mean,
+        // the relative uncertainty is that of the mass
+        uncertainty: (mean * mass_to_charge.uncertainty / mass_to_charge.mean).abs(),
+        is_estimated: mass_to_charge.is_estimated,
+    })
+}
+
+/// Returns the ratio ν<sub>c</sub>(`reference`) / ν<sub>c</sub>(`nuclide`) of the cyclotron
+/// frequencies of the ion of `reference` with a charge of `reference_charge`, and that of
+/// `nuclide` with a charge of `charge`.
+///
+/// This is the ratio of the mass-to-charge ratio of `nuclide` to that of `reference`, and it is
+/// what a Penning trap measures, with `reference` as the ion of a well-known mass. If either
+/// charge is 0, `None` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{parse_str, penning};
+///
+/// let data = parse_str(r"1
+/// 1
+/// 0  0    6    6   12 C             0.0         0.0        7680.1446     0.0000  B-      *                   12 000000.0         0.0
+///   54  146   92  238 U         47308.9         1.5        7570.1213     0.0063  B-      *                  238 050788.2         1.6").unwrap();
+/// let (c12, u238) = (&data[0], &data[1]);
+/// // 238U³⁰⁺ against ¹²C⁺, a common reference
+/// let ratio = penning::frequency_ratio(u238, 30, c12, 1).unwrap();
+/// assert!((ratio.mean - 0.661_236_70).abs() < 1e-8);
+/// ```
+#[must_use]
+pub fn frequency_ratio(
+    nuclide: &Nuclide,
+    charge: i32,
+    reference: &Nuclide,
+    reference_charge: i32,
+) -> Option<Value> {
+    let numerator = mass_to_charge(nuclide, charge)?;
+    let denominator = mass_to_charge(reference, reference_charge)?;
+    let mean = numerator.mean / denominator.mean;
+    let relative =
+        (numerator.uncertainty / numerator.mean).hypot(denominator.uncertainty / denominator.mean);
+    Some(Value {
+        mean,
+        uncertainty: (mean * relative).abs(),
+        is_estimated: numerator.is_estimated || denominator.is_estimated,
+    })
+}
+
+/// A species that is consistent with a measured mass-to-charge ratio, from [`identify`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Candidate<'a> {
+    /// The nuclides of the atoms, with the number of atoms of each, in order of mass
+    pub atoms: Vec<(&'a Nuclide, u32)>,
+    /// The mass-to-charge ratio of the ion, in u/e
+    pub mass_to_charge: Value,
+    /// The measured mass-to-charge ratio less that of the ion, in u/e
+    pub difference: f64,
+}
+
+impl Candidate<'_> {
+    /// Returns the molecule of the atoms.
+    #[must_use]
+    pub fn molecule(&self) -> Molecule {
+        Molecule {
+            atoms: self
+                .atoms
+                .iter()
+                .map(|(nuc, count)| ((nuc.z, nuc.n), *count))
+                .collect(),
+        }
+    }
+}
+
+/// Returns the ions with a charge of `charge` whose mass-to-charge ratio is within `tolerance` of
+/// `mass_to_charge`, both in u/e, in order of the size of the difference.
+///
+/// The ions are of atoms of `nuclides`, or, if `max_atoms` is more than 1, of molecules of up to
+/// `max_atoms` of them. The number of molecules grows quickly with `max_atoms`, so it is usually
+/// best to include only the nuclides that could be in the trap, such as the stable ones. The
+/// uncertainty of a molecule adds the uncertainties of atoms of the same nuclide, and adds those
+/// of different nuclides in quadrature. If `charge` is 0, there are no ions.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{parse_str, penning};
+///
+/// let nuclides = parse_str(r"1
+/// 1
+/// 0  0    6    6   12 C             0.0         0.0        7680.1446     0.0000  B-      *                   12 000000.0         0.0
+///    0    7    7   14 N          2863.41672     0.00019    7475.6148     0.0000  B-      *                   14 003074.00413     0.00020
+///    0    8    8   16 O         -4737.00137     0.00016    7976.2071     0.0000  B-      *                   15 994914.62012     0.00017").unwrap();
+/// // what is the peak at 27.994 u/e, such as ¹²C¹⁶O⁺ or ¹⁴N₂⁺?
+/// let candidates = penning::identify(&nuclides, 27.994_36, 0.005, 1, 2);
+/// // ¹⁴N₂⁺ is 0.011 u/e away
+/// assert_eq!(candidates.len(), 1);
+/// assert_eq!(candidates[0].molecule().to_string(), "12C 16O");
+/// assert!(candidates[0].difference.abs() < 1e-5);
+/// ```
+#[must_use]
+pub fn identify(
+    nuclides: &[Nuclide],
+    mass_to_charge: f64,
+    tolerance: f64,
+    charge: i32,
+    max_atoms: u32,
+) -> Vec<Candidate<'_>> {
+    if charge == 0 {
+        return Vec::new();
+    }
+    let mut sorted: Vec<&Nuclide> = nuclides.iter().collect();
+    sorted.sort_by(|a, b| a.atomic_mass.mean.total_cmp(&b.atomic_mass.mean));
+    // the range of the sum of the atomic masses, with the electrons added back
+    let electrons = CONSTANTS.to_u(f64::from(charge) * CONSTANTS.electron_mass);
+    let mass = mass_to_charge * f64::from(charge) + electrons;
+    let spread = tolerance.abs() * f64::from(charge.unsigned_abs());
+    let mut found = Vec::new();
+    search(
+        &sorted,
+        &mut Vec::new(),
+        (mass - spread, mass + spread),
+        max_atoms,
+        &mut found,
+    );
+    let mut candidates: Vec<Candidate> = found
+        .into_iter()
+        .filter_map(|indices| {
+            let mut atoms: Vec<(&Nuclide, u32)> = Vec::new();
+            for i in indices {
+                match atoms.last_mut() {
+                    Some((nuc, count)) if core::ptr::eq(*nuc, sorted[i]) => *count += 1,
+                    _ => atoms.push((sorted[i], 1)),
+                }
+            }
+            let ion = molecule::mass_to_charge(&atoms, charge, &CONSTANTS)?;
+            Some(Candidate {
+                difference: mass_to_charge - ion.mean,
+                mass_to_charge: ion,
+                atoms,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.difference.abs().total_cmp(&b.difference.abs()));
+    candidates
+}
+
+// adds to `found` the multisets, as indices into `sorted`, that extend `chosen` with up to
+// `atoms` more, not before its last, and whose masses add to within `range`
+fn search(
+    sorted: &[&Nuclide],
+    chosen: &mut Vec<usize>,
+    range: (f64, f64),
+    atoms: u32,
+    found: &mut Vec<Vec<usize>>,
+) {
+    if atoms == 0 {
+        return;
+    }
+    let mass: f64 = chosen.iter().map(|&i| sorted[i].atomic_mass.mean).sum();
+    let start = chosen.last().copied().unwrap_or(0);
+    for i in start..sorted.len() {
+        let total = mass + sorted[i].atomic_mass.mean;
+        // the masses are in order, so no later nuclide fits either
+        if total > range.1 {
+            break;
+        }
+        chosen.push(i);
+        if total >= range.0 {
+            found.push(chosen.clone());
+        }
+        search(sorted, chosen, range, atoms - 1, found);
+        chosen.pop();
+    }
+}
+
+/// An ion in a [`Doublet`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Species<'a> {
+    /// The molecule, or atom
+    pub molecule: &'a Molecule,
+    /// The charge, in units of e
+    pub charge: i32,
+    /// The mass-to-charge ratio, in u/e
+    pub mass_to_charge: Value,
+}
+
+/// A pair of ions with close mass-to-charge ratios, from [`doublets`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Doublet<'a> {
+    /// The ion with the smaller mass-to-charge ratio
+    pub first: Species<'a>,
+    /// The ion with the larger mass-to-charge ratio
+    pub second: Species<'a>,
+    /// The mass-to-charge ratio of the second less that of the first, in u/e
+    pub difference: Value,
+}
+
+/// Returns the pairs of `ions`, given as a molecule and a charge in units of e, whose
+/// mass-to-charge ratios differ by less than `threshold` u/e, with the atomic masses in
+/// `dataset`, in order of the difference.
+///
+/// The uncertainty of the difference includes that the atoms of a nuclide in both ions cancel, so
+/// it is smaller than that of either ion when they share most of their atoms. The ions with a
+/// charge of 0, or a nuclide that isn't in the dataset, are left out, as are pairs of the same
+/// ion.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{dataset::{Dataset, Duplicates}, molecule::Molecule, penning};
+///
+/// let dataset = Dataset::from_slice(br"1
+/// 1
+/// 0 -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014
+///    0    6    6   12 C             0.0         0.0        7680.1446     0.0000  B-      *                   12 000000.0         0.0
+///    0    7    7   14 N          2863.41672     0.00019    7475.6148     0.0000  B-      *                   14 003074.00413     0.00020
+///    0    8    8   16 O         -4737.00137     0.00016    7976.2071     0.0000  B-      *                   15 994914.62012     0.00017
+///    1   14   13   27 Al       -17196.6581      0.0004     8331.5457     0.0000  B-      *                   26 981538.6291      0.0004
+///    0   14   14   28 Si       -21492.79430     0.00050    8447.7444     0.0000  B-      *                   27 976926.53744     0.00054", Duplicates::Error).unwrap();
+/// // candidates for a calibration doublet near A = 28
+/// let ions: Vec<(Molecule, i32)> = ["28Si", "12C 16O", "14N2", "1H 27Al"]
+///     .into_iter()
+///     .map(|formula| (Molecule::parse(formula).unwrap(), 1))
+///     .collect();
+/// let doublets = penning::doublets(&dataset, &ions, 0.02);
+/// let pairs: Vec<_> = doublets
+///     .iter()
+///     .map(|doublet| (doublet.first.molecule.to_string(), doublet.second.molecule.to_string()))
+///     .collect();
+/// // ²⁸Si⁺ and ¹⁴N₂⁺ are 0.029 u/e apart
+/// assert_eq!(pairs.len(), 5);
+/// assert_eq!(pairs[0], ("1H 27Al".to_string(), "12C 16O".to_string()));
+/// assert!((doublets[0].difference.mean - 0.005_551).abs() < 1e-6);
+/// ```
+#[must_use]
+pub fn doublets<'a>(
+    dataset: &Dataset,
+    ions: &'a [(Molecule, i32)],
+    threshold: f64,
+) -> Vec<Doublet<'a>> {
+    let mut species: Vec<Species> = ions
+        .iter()
+        .filter_map(|(molecule, charge)| {
+            Some(Species {
+                mass_to_charge: molecule.mass_to_charge(dataset, *charge)?,
+                molecule,
+                charge: *charge,
+            })
+        })
+        .collect();
+    species.sort_by(|a, b| a.mass_to_charge.mean.total_cmp(&b.mass_to_charge.mean));
+    let mut doublets = Vec::new();
+    for (i, first) in species.iter().enumerate() {
+        for second in &species[i + 1..] {
+            if second.mass_to_charge.mean - first.mass_to_charge.mean >= threshold {
+                break;
+            }
+            if first.molecule == second.molecule && first.charge == second.charge {
+                continue;
+            }
+            if let Some(difference) = difference(dataset, first, second) {
+                doublets.push(Doublet {
+                    first: first.clone(),
+                    second: second.clone(),
+                    difference,
+                });
+            }
+        }
+    }
+    doublets.sort_by(|a, b| a.difference.mean.total_cmp(&b.difference.mean));
+    doublets
+}
+
+// the mass-to-charge ratio of `second` less that of `first`, with the uncertainties of the atoms
+// of the same nuclide in both cancelling
+fn difference(dataset: &Dataset, first: &Species, second: &Species) -> Option<Value> {
+    let mut coefficients: BTreeMap<(u32, u32), f64> = BTreeMap::new();
+    for (species, sign) in [(second, 1.0), (first, -1.0)] {
+        let charge = f64::from(species.charge);
+        for &(nuclide, count) in &species.molecule.atoms {
+            *coefficients.entry(nuclide).or_default() += sign * f64::from(count) / charge;
+        }
+    }
+    let mut variance = 0.0;
+    let mut is_estimated = false;
+    for ((z, n), coefficient) in coefficients {
+        let mass = &dataset.get(z, n)?.atomic_mass;
+        variance += (coefficient * mass.uncertainty).powi(2);
+        is_estimated |= coefficient != 0.0 && mass.is_estimated;
+    }
+    Some(Value {
+        mean: second.mass_to_charge.mean - first.mass_to_charge.mean,
+        uncertainty: variance.sqrt(),
+        is_estimated,
+    })
+}
@@ -0,0 +1,124 @@
+//! Drawing the chart of nuclides with [plotters](https://crates.io/crates/plotters).
+//!
+//! Each nuclide is a square at (N, Z), colored by a quantity with the viridis color map, from the
+//! smallest value to the largest. Nuclides without a value aren't drawn.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::{plot, Iter, Nuclide};
+//!
+//! let data: Vec<Nuclide> = Iter::from_path("mass.mas20")?.collect::<Result<_, _>>()?;
+//! plot::save("chart.png", (1200, 800), &data, |nuc| {
+//!     Some(nuc.binding_energy_per_a.as_ref()?.mean)
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::Nuclide;
+use plotters::{
+    backend::{BitMapBackend, DrawingBackend},
+    chart::ChartBuilder,
+    coord::Shift,
+    drawing::{DrawingArea, DrawingAreaErrorKind, IntoDrawingArea},
+    element::Rectangle,
+    style::{
+        colors::colormaps::{ColorMap, ViridisRGB},
+        Color, WHITE,
+    },
+};
+use std::path::Path;
+
+/// The error from drawing on a bitmap.
+pub type BitMapError = DrawingAreaErrorKind<<BitMapBackend<'static> as DrawingBackend>::ErrorType>;
+
+/// Draws the chart of nuclides on `area`, colored by `quantity`.
+///
+/// The area isn't cleared first.
+///
+/// # Errors
+///
+/// If drawing fails, the error from the backend is returned.
+pub fn draw<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    nuclides: &[Nuclide],
+    quantity: impl Fn(&Nuclide) -> Option<f64>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let values: Vec<(&Nuclide, f64)> = nuclides
+        .iter()
+        .filter_map(|nuc| Some((nuc, quantity(nuc).filter(|x| x.is_finite())?)))
+        .collect();
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, x)| {
+            (min.min(x), max.max(x))
+        });
+    let n_max = nuclides.iter().map(|nuc| nuc.n).max().unwrap_or(0);
+    let z_max = nuclides.iter().map(|nuc| nuc.z).max().unwrap_or(0);
+
+    let mut chart = ChartBuilder::on(area)
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(-0.5..f64::from(n_max) + 0.5, -0.5..f64::from(z_max) + 0.5)?;
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc("N")
+        .y_desc("Z")
+        .draw()?;
+    chart.draw_series(values.into_iter().map(|(nuc, x)| {
+        let (n, z) = (f64::from(nuc.n), f64::from(nuc.z));
+        // with one value, min == max, and the color is the middle of the map
+        let h = if max > min {
+            (x - min) / (max - min)
+        } else {
+            0.5
+        };
+        let color = ViridisRGB.get_color(h);
+        Rectangle::new([(n - 0.5, z - 0.5), (n + 0.5, z + 0.5)], color.filled())
+    }))?;
+    Ok(())
+}
+
+/// Renders the chart of nuclides, colored by `quantity`, as an RGB bitmap of `size` pixels.
+///
+/// The pixels are in rows, from the top left, with 3 bytes each.
+///
+/// # Errors
+///
+/// If drawing fails, an error is returned.
+pub fn render(
+    size: (u32, u32),
+    nuclides: &[Nuclide],
+    quantity: impl Fn(&Nuclide) -> Option<f64>,
+) -> Result<Vec<u8>, BitMapError> {
+    let mut buf = vec![0; size.0 as usize * size.1 as usize * 3];
+    {
+        let area = BitMapBackend::with_buffer(&mut buf, size).into_drawing_area();
+        area.fill(&WHITE)?;
+        draw(&area, nuclides, quantity)?;
+        area.present()?;
+    }
+    Ok(buf)
+}
+
+/// Saves the chart of nuclides, colored by `quantity`, as an image of `size` pixels at `path`.
+///
+/// The format is chosen from the extension of `path`, such as `.png`.
+///
+/// # Errors
+///
+/// If drawing or saving fails, an error is returned.
+pub fn save(
+    path: impl AsRef<Path>,
+    size: (u32, u32),
+    nuclides: &[Nuclide],
+    quantity: impl Fn(&Nuclide) -> Option<f64>,
+) -> Result<(), BitMapError> {
+    let area = BitMapBackend::new(path.as_ref(), size).into_drawing_area();
+    area.fill(&WHITE)?;
+    draw(&area, nuclides, quantity)?;
+    area.present()
+}
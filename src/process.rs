@@ -0,0 +1,84 @@
+//! The paths of the r-process and rp-process through the chart, in the waiting-point
+//! approximation.
+//!
+//! Instead of a temperature and density, the captures are limited by a threshold on the separation
+//! energy: a nuclide captures a neutron (or proton) if the separation energy of the nuclide it
+//! would become is at least the threshold. When it can't, it is a waiting point, and it β decays,
+//! and then the captures continue from the daughter. The path is the sequence of waiting points,
+//! and it ends at a waiting point that can't β decay, or whose daughter isn't in the dataset.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{dataset::{Dataset, Duplicates}, process};
+//!
+//! let data = br"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! // the neutron β decays to 1H, which has nothing to capture
+//! assert_eq!(process::r_process(&dataset, 0, 1, 2_000.0), [(0, 1), (1, 0)]);
+//! ```
+use crate::{dataset::Dataset, reaction::Reaction, DecayMode};
+
+/// Returns the waiting points of the r-process from the nuclide with `z` protons and `n`
+/// neutrons, as (Z, N), with a threshold of `threshold` keV on S<sub>n</sub>.
+///
+/// Each waiting point has one more proton than the one before it, from β⁻ decay. If the nuclide
+/// isn't in the dataset, the path is empty.
+#[must_use]
+pub fn r_process(dataset: &Dataset, z: u32, n: u32, threshold: f64) -> Vec<(u32, u32)> {
+    path(dataset, (z, n), threshold, false)
+}
+
+/// Returns the waiting points of the rp-process from the nuclide with `z` protons and `n`
+/// neutrons, as (Z, N), with a threshold of `threshold` keV on S<sub>p</sub>.
+///
+/// Each waiting point has one more neutron than the one before it, from β⁺ decay. If the nuclide
+/// isn't in the dataset, the path is empty.
+#[must_use]
+pub fn rp_process(dataset: &Dataset, z: u32, n: u32, threshold: f64) -> Vec<(u32, u32)> {
+    path(dataset, (z, n), threshold, true)
+}
+
+fn path(
+    dataset: &Dataset,
+    mut nuclide: (u32, u32),
+    threshold: f64,
+    protons: bool,
+) -> Vec<(u32, u32)> {
+    let mut waiting_points = Vec::new();
+    if dataset.get(nuclide.0, nuclide.1).is_none() {
+        return waiting_points;
+    }
+    let (capture, mode) = if protons {
+        ((1, 0), DecayMode::BetaPlus)
+    } else {
+        ((0, 1), DecayMode::BetaMinus)
+    };
+    // each β decay adds a proton for the r-process, or a neutron for the rp-process, and captures
+    // only add the other, so no nuclide is visited twice
+    loop {
+        while let Some(separation_energy) = dataset.separation_energy(
+            nuclide.0 + capture.0,
+            nuclide.1 + capture.1,
+            capture.0,
+            capture.1,
+        ) {
+            if separation_energy.mean < threshold {
+                break;
+            }
+            nuclide = (nuclide.0 + capture.0, nuclide.1 + capture.1);
+        }
+        waiting_points.push(nuclide);
+        let Some(decay) = Reaction::decay(nuclide.0, nuclide.1, mode) else {
+            break;
+        };
+        match dataset.reaction_q_value(&decay) {
+            Some(q_value) if q_value.mean > 0.0 => nuclide = decay.products[0],
+            _ => break,
+        }
+    }
+    waiting_points
+}
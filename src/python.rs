@@ -0,0 +1,226 @@
+//! Bindings for Python, with [pyo3](https://pyo3.rs).
+//!
+//! The bindings are built into the `pyame2020` module with [maturin](https://www.maturin.rs),
+//! using the `pyproject.toml` in the repository, with `maturin build --release` or
+//! `pip install .`. The module has:
+//!
+//! * `parse(text)` and `read(path)`, which return a `Table`
+//! * `Table`, a [`Dataset`], with `get(z, n)`, `nuclides()`, `q_value(initial, products)` and `len()`
+//! * `Nuclide` and `Value`, with the same fields as [`Nuclide`] and [`Value`], and `to_dict()`
+//!
+//! Errors are raised as `ValueError`, or `OSError` if reading fails.
+//!
+//! ```python
+//! import pyame2020
+//!
+//! table = pyame2020.read("mass.mas20")
+//! fe56 = table.get(26, 30)
+//! print(fe56.mass_excess.mean, fe56.to_dict())
+//! # the β⁻ decay of the neutron, in keV
+//! print(table.q_value([(0, 1)], [(1, 0)]).mean)
+//! ```
+use crate::{
+    dataset::{Dataset, Duplicates},
+    AmeError, Nuclide, Value,
+};
+use pyo3::{
+    exceptions::{PyOSError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+fn to_py_err(err: &AmeError) -> PyErr {
+    match err {
+        AmeError::Io(_) => PyOSError::new_err(err.to_string()),
+        _ => PyValueError::new_err(err.to_string()),
+    }
+}
+
+/// A value with a mean and uncertainty.
+#[pyclass(name = "Value", module = "pyame2020", frozen, skip_from_py_object)]
+#[derive(Clone)]
+struct PyValue(Value);
+
+#[pymethods]
+impl PyValue {
+    #[getter]
+    fn mean(&self) -> f64 {
+        self.0.mean
+    }
+
+    #[getter]
+    fn uncertainty(&self) -> f64 {
+        self.0.uncertainty
+    }
+
+    #[getter]
+    fn is_estimated(&self) -> bool {
+        self.0.is_estimated
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("mean", self.0.mean)?;
+        dict.set_item("uncertainty", self.0.uncertainty)?;
+        dict.set_item("is_estimated", self.0.is_estimated)?;
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Value(mean={}, uncertainty={}, is_estimated={})",
+            self.0.mean,
+            self.0.uncertainty,
+            if self.0.is_estimated { "True" } else { "False" },
+        )
+    }
+}
+
+/// A nuclide, with its values from the table.
+#[pyclass(name = "Nuclide", module = "pyame2020", frozen)]
+struct PyNuclide(Nuclide);
+
+#[pymethods]
+impl PyNuclide {
+    #[getter]
+    fn n(&self) -> u32 {
+        self.0.n
+    }
+
+    #[getter]
+    fn z(&self) -> u32 {
+        self.0.z
+    }
+
+    #[getter]
+    fn a(&self) -> u32 {
+        self.0.a()
+    }
+
+    #[getter]
+    fn element(&self) -> &str {
+        &self.0.element
+    }
+
+    #[getter]
+    fn origin(&self) -> Option<&str> {
+        self.0.origin.as_deref()
+    }
+
+    #[getter]
+    fn mass_excess(&self) -> PyValue {
+        PyValue(self.0.mass_excess.clone())
+    }
+
+    #[getter]
+    fn binding_energy_per_a(&self) -> Option<PyValue> {
+        self.0.binding_energy_per_a.clone().map(PyValue)
+    }
+
+    #[getter]
+    fn decay_mode(&self) -> Option<&'static str> {
+        self.0.decay_mode.map(crate::DecayMode::label)
+    }
+
+    #[getter]
+    fn beta_decay_energy(&self) -> Option<PyValue> {
+        self.0.beta_decay_energy.clone().map(PyValue)
+    }
+
+    #[getter]
+    fn atomic_mass(&self) -> PyValue {
+        PyValue(self.0.atomic_mass.clone())
+    }
+
+    /// Returns the fields as a `dict`, with values as `dict`s.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let value = |value: Option<&Value>| {
+            value
+                .map(|value| PyValue(value.clone()).to_dict(py))
+                .transpose()
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("n", self.0.n)?;
+        dict.set_item("z", self.0.z)?;
+        dict.set_item("a", self.0.a())?;
+        dict.set_item("element", self.element())?;
+        dict.set_item("origin", self.origin())?;
+        dict.set_item("mass_excess", value(Some(&self.0.mass_excess))?)?;
+        dict.set_item(
+            "binding_energy_per_a",
+            value(self.0.binding_energy_per_a.as_ref())?,
+        )?;
+        dict.set_item("decay_mode", self.decay_mode())?;
+        dict.set_item(
+            "beta_decay_energy",
+            value(self.0.beta_decay_energy.as_ref())?,
+        )?;
+        dict.set_item("atomic_mass", value(Some(&self.0.atomic_mass))?)?;
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Nuclide(z={}, n={}, element='{}')",
+            self.0.z,
+            self.0.n,
+            self.0.element.as_str(),
+        )
+    }
+}
+
+/// A table of nuclides, indexed by Z and N.
+#[pyclass(name = "Table", module = "pyame2020", frozen)]
+struct PyTable(Dataset);
+
+#[pymethods]
+impl PyTable {
+    /// Returns the nuclide with `z` protons and `n` neutrons, or `None`.
+    fn get(&self, z: u32, n: u32) -> Option<PyNuclide> {
+        self.0.get(z, n).cloned().map(PyNuclide)
+    }
+
+    /// Returns a list of the nuclides, in the order they were read.
+    fn nuclides(&self) -> Vec<PyNuclide> {
+        self.0.iter().cloned().map(PyNuclide).collect()
+    }
+
+    /// Returns the Q-value, in keV, from lists of (Z, N), or `None`.
+    // lists from Python can't be borrowed as slices
+    #[allow(clippy::needless_pass_by_value)]
+    fn q_value(&self, initial: Vec<(u32, u32)>, products: Vec<(u32, u32)>) -> Option<PyValue> {
+        self.0.q_value(&initial, &products).map(PyValue)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Parses the contents of a `mass.mas20` file.
+#[pyfunction]
+fn parse(text: &str) -> PyResult<PyTable> {
+    Dataset::from_slice(text.as_bytes(), Duplicates::Error)
+        .map(PyTable)
+        .map_err(|err| to_py_err(&err))
+}
+
+/// Reads and parses the `mass.mas20` file at `path`.
+#[pyfunction]
+fn read(path: PathBuf) -> PyResult<PyTable> {
+    let file = File::open(path).map_err(|err| to_py_err(&err.into()))?;
+    Dataset::read(BufReader::new(file), Duplicates::Error)
+        .map(PyTable)
+        .map_err(|err| to_py_err(&err))
+}
+
+#[pymodule]
+fn pyame2020(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyValue>()?;
+    m.add_class::<PyNuclide>()?;
+    m.add_class::<PyTable>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(read, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Values as [uom](https://crates.io/crates/uom) quantities, so that energies and masses can't be
+//! mixed up.
+//!
+//! Each value is a pair of quantities, the mean and the uncertainty.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::parse_str;
+//! use uom::si::{energy::megaelectronvolt, mass::kilogram};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let (mass_excess, _) = data[0].mass_excess_quantity();
+//! assert!((mass_excess.get::<megaelectronvolt>() - 8.071_318_06).abs() < 1e-9);
+//! let (mass, _) = data[0].atomic_mass_quantity();
+//! assert!((mass.get::<kilogram>() - 1.674_927_5e-27).abs() < 1e-33);
+//! ```
+use crate::{Nuclide, Value};
+use uom::si::{
+    energy::kiloelectronvolt,
+    f64::{Energy, Mass},
+    mass::dalton,
+};
+
+fn energy(value: &Value) -> (Energy, Energy) {
+    (
+        Energy::new::<kiloelectronvolt>(value.mean),
+        Energy::new::<kiloelectronvolt>(value.uncertainty),
+    )
+}
+
+impl Nuclide {
+    /// Returns the mass excess, and its uncertainty.
+    #[must_use]
+    pub fn mass_excess_quantity(&self) -> (Energy, Energy) {
+        energy(&self.mass_excess)
+    }
+
+    /// Returns the binding energy per nucleon, and its uncertainty, if any.
+    #[must_use]
+    pub fn binding_energy_per_a_quantity(&self) -> Option<(Energy, Energy)> {
+        self.binding_energy_per_a.as_ref().map(energy)
+    }
+
+    /// Returns the beta decay energy, and its uncertainty, if any.
+    #[must_use]
+    pub fn beta_decay_energy_quantity(&self) -> Option<(Energy, Energy)> {
+        self.beta_decay_energy.as_ref().map(energy)
+    }
+
+    /// Returns the atomic mass, and its uncertainty.
+    #[must_use]
+    pub fn atomic_mass_quantity(&self) -> (Mass, Mass) {
+        (
+            Mass::new::<dalton>(self.atomic_mass.mean),
+            Mass::new::<dalton>(self.atomic_mass.uncertainty),
+        )
+    }
+}
@@ -0,0 +1,161 @@
+//! Reactions and decays, as the nuclides before and after, for computing Q-values.
+//!
+//! Reactions are written as `9Be(p,n)9B`, with the target, the projectiles and the ejectiles in
+//! parentheses, and the residual nucleus. The light particles are:
+//!
+//! | Particle | Symbol | (Z, N) |
+//! |----------|--------|--------|
+//! | neutron | `n` | (0, 1) |
+//! | proton | `p` | (1, 0) |
+//! | deuteron | `d` | (1, 1) |
+//! | triton | `t` | (1, 2) |
+//! | helion | `h` | (2, 1) |
+//! | α | `a` or `α` | (2, 2) |
+//! | γ | `g` or `γ` | none |
+//!
+//! A particle can have a count, up to 255, such as `2n`. Other nuclides are named as in
+//! [`element::nuclide`], such as `3He`, and several particles can be listed together, such as
+//! `(d,pn)` or `(6Li,d+a)`. The residual nucleus can be left out, as in `9Be(p,n)`, and is then
+//! found from the conservation of Z and N.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::reaction::Reaction;
+//!
+//! let reaction = Reaction::parse("9Be(p,n)9B").unwrap();
+//! assert_eq!(reaction.initial, [(4, 5), (1, 0)]);
+//! assert_eq!(reaction.products, [(0, 1), (5, 4)]);
+//! assert_eq!(Reaction::parse("9Be(p,n)"), Some(reaction));
+//! ```
+use crate::{element, DecayMode};
+use alloc::{vec, vec::Vec};
+
+/// A reaction or a decay, with the (Z, N) of the nuclides before and after.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Reaction {
+    /// The nuclides before, such as the target and the projectile
+    pub initial: Vec<(u32, u32)>,
+    /// The nuclides after, such as the ejectiles and the residual nucleus
+    pub products: Vec<(u32, u32)>,
+    /// The number of positrons that are created, as in β⁺ decay
+    ///
+    /// The Q-value from atomic masses doesn't count their masses, or those of the electrons the
+    /// atoms lose.
+    pub positrons: u32,
+}
+
+impl Reaction {
+    /// Parses a reaction written like `9Be(p,n)9B`, as described in the
+    /// [module documentation](self).
+    ///
+    /// If it isn't a reaction, or Z and N aren't conserved, `None` is returned.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let (target, rest) = s.trim().split_once('(')?;
+        let (particles, residual) = rest.split_once(')')?;
+        let (projectiles, ejectiles) = particles.split_once(',')?;
+
+        let mut initial = vec![element::nuclide(target.trim())?];
+        initial.extend(parse_particles(projectiles)?);
+        let mut products = parse_particles(ejectiles)?;
+        // `None` if the sum overflows
+        let sum = |side: &[(u32, u32)]| {
+            side.iter().try_fold((0_u32, 0_u32), |(z, n), &(dz, dn)| {
+                Some((z.checked_add(dz)?, n.checked_add(dn)?))
+            })
+        };
+        let (z, n) = sum(&initial)?;
+        let residual = match residual.trim() {
+            "" => {
+                let (ejected_z, ejected_n) = sum(&products)?;
+                (z.checked_sub(ejected_z)?, n.checked_sub(ejected_n)?)
+            }
+            residual => element::nuclide(residual)?,
+        };
+        products.push(residual);
+        (sum(&products)? == (z, n)).then_some(Self {
+            initial,
+            products,
+            positrons: 0,
+        })
+    }
+
+    /// Returns the decay of the nuclide with `z` protons and `n` neutrons by `mode`.
+    ///
+    /// If the nuclide can't decay by `mode`, such as the α decay of a nuclide with one proton,
+    /// `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::{reaction::Reaction, DecayMode};
+    ///
+    /// let decay = Reaction::decay(84, 128, DecayMode::Alpha).unwrap();
+    /// assert_eq!(decay.products, [(82, 126), (2, 2)]);
+    /// ```
+    #[must_use]
+    pub fn decay(z: u32, n: u32, mode: DecayMode) -> Option<Self> {
+        let (products, positrons) = match mode {
+            DecayMode::Alpha => (vec![(z.checked_sub(2)?, n.checked_sub(2)?), (2, 2)], 0),
+            DecayMode::BetaMinus => (vec![(z.checked_add(1)?, n.checked_sub(1)?)], 0),
+            DecayMode::BetaPlus => (vec![(z.checked_sub(1)?, n.checked_add(1)?)], 1),
+            DecayMode::ElectronCapture => (vec![(z.checked_sub(1)?, n.checked_add(1)?)], 0),
+        };
+        Some(Self {
+            initial: vec![(z, n)],
+            products,
+            positrons,
+        })
+    }
+}
+
+// parses particles like `2n`, `pn`, or `d+3He`
+fn parse_particles(s: &str) -> Option<Vec<(u32, u32)>> {
+    let mut particles = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        rest = rest.trim_start_matches(|c: char| c == '+' || c.is_whitespace());
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (count, after) = rest.split_at(digits);
+        let mut chars = after.chars();
+        let Some(first) = chars.next() else {
+            // trailing digits, such as `p2`, aren't a particle
+            return (digits == 0).then_some(particles);
+        };
+        let particle = match first {
+            'n' => Some((0, 1)),
+            'p' => Some((1, 0)),
+            'd' => Some((1, 1)),
+            't' => Some((1, 2)),
+            'h' => Some((2, 1)),
+            'a' | 'α' => Some((2, 2)),
+            'g' | 'γ' => None,
+            // a nuclide, such as `3He`, where the digits are A
+            c if c.is_ascii_uppercase() => {
+                let symbol = after[1..]
+                    .find(|c: char| !c.is_ascii_lowercase())
+                    .map_or(after.len(), |i| i + 1);
+                particles.push(element::nuclide(&rest[..digits + symbol])?);
+                rest = &after[symbol..];
+                continue;
+            }
+            _ => return None,
+        };
+        // a larger count isn't a reaction, and would be allocated
+        let count: u8 = if count.is_empty() {
+            1
+        } else {
+            count.parse().ok()?
+        };
+        particles.extend(
+            particle
+                .into_iter()
+                .flat_map(|particle| vec![particle; usize::from(count)]),
+        );
+        rest = chars.as_str();
+    }
+    Some(particles)
+}
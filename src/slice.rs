@@ -0,0 +1,199 @@
+use crate::{
+    parser::{is_blank, Parser},
+    AmeError, Experimental, Float, Nuclide, ParseOptions, Warning,
+};
+use core::{iter::FusedIterator, marker::PhantomData, ops::ControlFlow};
+
+/// An iterator that reads AME2020 data from a byte slice.
+///
+/// This yields the same items as [`Iter`][crate::Iter], but the lines are borrowed from the slice
+/// instead of being read into a new `String` each. This makes it faster when all of the data is
+/// already in memory, such as when the file is memory-mapped.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::SliceIter;
+///
+/// let data = br"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+/// let mut iter = SliceIter::new(data);
+/// assert!(iter.next().is_some());
+/// assert!(iter.next().is_none());
+/// ```
+///
+/// The values are `f64`, unless another [`Float`] is chosen with [`with_float`][Self::with_float].
+///
+/// # Errors
+///
+/// If a line fails to parse, [`next`][Self::next] will return `Some(Err)`,
+/// and calling `next` again continues with the following line. Once `next` has returned `None`, it
+/// will always return `None`.
+pub struct SliceIter<'a, F = f64> {
+    lines: Lines<'a>,
+    parser: Parser,
+    // a function pointer, so the iterator is `Send`, `Sync`, and `Unpin` whatever `F` is
+    float: PhantomData<fn() -> F>,
+}
+
+impl<'a> SliceIter<'a> {
+    /// Creates a new `SliceIter` from `data`.
+    #[must_use]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::with_options(data, ParseOptions::default())
+    }
+
+    /// Creates a new `SliceIter` from `data`, which parses according to `options`.
+    #[must_use]
+    pub fn with_options(data: &'a [u8], options: ParseOptions) -> Self {
+        Self::with_float(data, options)
+    }
+}
+
+impl<'a, F: Float> SliceIter<'a, F> {
+    /// Creates a new `SliceIter` from `data`, which parses according to `options`, with the values
+    /// as `F`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::{ParseOptions, SliceIter};
+    ///
+    /// let data = br"1
+    /// 1
+    /// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047";
+    /// let mut iter = SliceIter::<f32>::with_float(data, ParseOptions::default());
+    /// let neutron = iter.next().unwrap().unwrap();
+    /// assert_eq!(neutron.mass_excess.mean, 8_071.318);
+    /// ```
+    #[must_use]
+    pub fn with_float(data: &'a [u8], options: ParseOptions) -> Self {
+        Self {
+            lines: Lines::new(data),
+            parser: Parser::new(options),
+            float: PhantomData,
+        }
+    }
+
+    /// Skips lines that fail to parse, instead of returning errors for them.
+    ///
+    /// The errors for the skipped lines are available from [`skipped`][Self::skipped]. Other
+    /// reading errors are still returned.
+    #[must_use]
+    pub fn lenient(mut self) -> Self {
+        self.parser.set_lenient(true);
+        self
+    }
+
+    /// Yields only the experimental data, with estimated values as `None`.
+    #[must_use]
+    pub fn experimental(self) -> Experimental<Self> {
+        Experimental(self)
+    }
+
+    /// Returns the errors for the lines that have been skipped so far, in order.
+    ///
+    /// Lines are only skipped if [`lenient`][Self::lenient] was used.
+    #[must_use]
+    pub fn skipped(&self) -> &[AmeError] {
+        self.parser.skipped()
+    }
+
+    /// Returns the warnings for the lines that have been read so far, in order.
+    #[must_use]
+    pub fn warnings(&self) -> &[Warning] {
+        self.parser.warnings()
+    }
+
+    /// Returns the number of the most recent line read, starting from 1.
+    ///
+    /// After an item is returned, this is the line it was on.
+    #[must_use]
+    pub fn line_number(&self) -> usize {
+        self.parser.line_number()
+    }
+}
+
+impl<F: Float> Iterator for SliceIter<'_, F> {
+    type Item = Result<Nuclide<F>, AmeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.parser.parse_line(self.lines.next()?) {
+                ControlFlow::Continue(()) => {}
+                ControlFlow::Break(res) => return Some(res),
+            }
+        }
+    }
+
+    // the preamble doesn't say how many nuclides there are, but there is at most one per line,
+    // and once the body has started, there is exactly one per line, unless lines are skipped.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lines = self.lines.remaining();
+        if self.parser.is_stopped() {
+            (0, Some(0))
+        } else if self.parser.in_body_exact() {
+            (lines, Some(lines))
+        } else {
+            (0, Some(lines))
+        }
+    }
+}
+
+impl<F: Float> FusedIterator for SliceIter<'_, F> {}
+
+/// An iterator over the lines of a byte slice.
+///
+/// This splits lines the same way as [`BufRead::lines`][std::io::BufRead::lines].
+pub(crate) struct Lines<'a> {
+    data: &'a [u8],
+    // the number of lines left that aren't blank, which are counted once, up front
+    remaining: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            remaining: split_lines(data).filter(|line| !is_blank(line)).count(),
+        }
+    }
+
+    /// Returns the number of lines left that aren't blank.
+    fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = next_line(&mut self.data)?;
+        if !is_blank(line) {
+            self.remaining -= 1;
+        }
+        Some(line)
+    }
+}
+
+fn split_lines(mut data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    core::iter::from_fn(move || next_line(&mut data))
+}
+
+// splits the first line off of `data`, without its line ending
+fn next_line<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    if data.is_empty() {
+        return None;
+    }
+    let (line, rest) = match data.iter().position(|&b| b == b'\n') {
+        Some(i) => {
+            let line = &data[..i];
+            (line.strip_suffix(b"\r").unwrap_or(line), &data[(i + 1)..])
+        }
+        None => (*data, &[][..]),
+    };
+    *data = rest;
+    Some(line)
+}
@@ -0,0 +1,121 @@
+use crate::{Float, Nuclide};
+use core::cmp::Ordering;
+
+/// Sorting and extrema for slices of nuclides, such as a `Vec<Nuclide>` collected from an
+/// iterator.
+///
+/// The sorts are stable, so nuclides with the same value stay in the same order, and of the
+/// nuclides with the same smallest or largest value, the first is returned. The values are
+/// compared with [`f64::total_cmp`], and can be any [`Float`].
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{parse_str, NuclideSliceExt};
+///
+/// let mut data = parse_str(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+///   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+/// assert_eq!(data.min_by_uncertainty().map(|nuc| nuc.z), Some(1));
+/// data.sort_by_mass_excess();
+/// assert_eq!(data[0].z, 1);
+/// ```
+pub trait NuclideSliceExt<F = f64> {
+    /// Sorts the nuclides by Z, and then N.
+    fn sort_by_zn(&mut self);
+
+    /// Sorts the nuclides by A, and then Z.
+    fn sort_by_a(&mut self);
+
+    /// Sorts the nuclides by mass excess, the smallest first.
+    fn sort_by_mass_excess(&mut self);
+
+    /// Sorts the nuclides by the uncertainty of the mass excess, the smallest first.
+    fn sort_by_uncertainty(&mut self);
+
+    /// Sorts the nuclides by binding energy per nucleon, the largest first, with the nuclides
+    /// without one last.
+    fn sort_by_binding_energy(&mut self);
+
+    /// Returns the nuclide with the smallest mass excess.
+    fn min_mass_excess(&self) -> Option<&Nuclide<F>>;
+
+    /// Returns the nuclide with the largest mass excess.
+    fn max_mass_excess(&self) -> Option<&Nuclide<F>>;
+
+    /// Returns the nuclide with the smallest uncertainty of the mass excess.
+    fn min_by_uncertainty(&self) -> Option<&Nuclide<F>>;
+
+    /// Returns the nuclide with the largest uncertainty of the mass excess.
+    fn max_by_uncertainty(&self) -> Option<&Nuclide<F>>;
+
+    /// Returns the nuclide with the largest binding energy per nucleon, of those with one.
+    fn max_binding_energy(&self) -> Option<&Nuclide<F>>;
+}
+
+impl<F: Float> NuclideSliceExt<F> for [Nuclide<F>] {
+    fn sort_by_zn(&mut self) {
+        self.sort_by_key(|nuc| (nuc.z, nuc.n));
+    }
+
+    fn sort_by_a(&mut self) {
+        self.sort_by_key(|nuc| (nuc.a(), nuc.z));
+    }
+
+    fn sort_by_mass_excess(&mut self) {
+        self.sort_by(|a, b| cmp(a.mass_excess.mean, b.mass_excess.mean));
+    }
+
+    fn sort_by_uncertainty(&mut self) {
+        self.sort_by(|a, b| cmp(a.mass_excess.uncertainty, b.mass_excess.uncertainty));
+    }
+
+    fn sort_by_binding_energy(&mut self) {
+        self.sort_by(|a, b| match (binding_energy(a), binding_energy(b)) {
+            (Some(a), Some(b)) => cmp(b, a),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+    }
+
+    fn min_mass_excess(&self) -> Option<&Nuclide<F>> {
+        self.iter()
+            .min_by(|a, b| cmp(a.mass_excess.mean, b.mass_excess.mean))
+    }
+
+    fn max_mass_excess(&self) -> Option<&Nuclide<F>> {
+        // `max_by` returns the last of equal values
+        self.iter()
+            .rev()
+            .max_by(|a, b| cmp(a.mass_excess.mean, b.mass_excess.mean))
+    }
+
+    fn min_by_uncertainty(&self) -> Option<&Nuclide<F>> {
+        self.iter()
+            .min_by(|a, b| cmp(a.mass_excess.uncertainty, b.mass_excess.uncertainty))
+    }
+
+    fn max_by_uncertainty(&self) -> Option<&Nuclide<F>> {
+        self.iter()
+            .rev()
+            .max_by(|a, b| cmp(a.mass_excess.uncertainty, b.mass_excess.uncertainty))
+    }
+
+    fn max_binding_energy(&self) -> Option<&Nuclide<F>> {
+        self.iter()
+            .rev()
+            .filter_map(|nuc| Some((binding_energy(nuc)?, nuc)))
+            .max_by(|&(a, _), &(b, _)| cmp(a, b))
+            .map(|(_, nuc)| nuc)
+    }
+}
+
+// the mean binding energy per nucleon, if there is one
+fn binding_energy<F: Float>(nuc: &Nuclide<F>) -> Option<F> {
+    nuc.binding_energy_per_a.as_ref().map(|value| value.mean)
+}
+
+// `f64::total_cmp`, for any `Float`, which converts to `f64` exactly
+fn cmp<F: Float>(a: F, b: F) -> Ordering {
+    a.to_f64().total_cmp(&b.to_f64())
+}
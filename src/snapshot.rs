@@ -0,0 +1,277 @@
+//! A compact binary snapshot of parsed data.
+//!
+//! Parsing the text format is much slower than reading a snapshot, so a snapshot can be saved the
+//! first time a file is parsed, and loaded on later runs. The snapshot records a hash of the source
+//! it was made from, so that it can be invalidated when the source changes.
+//!
+//! The encoding is specific to this crate, and is versioned with [`FORMAT_VERSION`]. It is not
+//! built on `serde`, because the `serde` representation skips fields, which non-self-describing
+//! formats can't handle.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::snapshot::load_cached;
+//!
+//! // parses "mass.mas20" the first time, and reads "mass.snap" afterwards
+//! let data = load_cached("mass.mas20", "mass.snap")?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{AmeError, DecayMode, Nuclide, SliceIter, Value};
+use arrayvec::ArrayString;
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// The version of the snapshot encoding.
+///
+/// This changes whenever the encoding changes, and snapshots of other versions are rejected.
+pub const FORMAT_VERSION: u32 = 2;
+
+const MAGIC: [u8; 8] = *b"AME2020S";
+
+/// Parsed data, along with the hash of its source.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    /// The [`hash`] of the source text
+    pub source_hash: u64,
+    /// The parsed data
+    pub nuclides: Vec<Nuclide>,
+}
+
+impl Snapshot {
+    /// Parses `source`, and creates a snapshot of it.
+    ///
+    /// # Errors
+    ///
+    /// If `source` fails to parse, an error is returned.
+    pub fn from_source(source: &[u8]) -> Result<Self, AmeError> {
+        let nuclides = SliceIter::new(source).collect::<Result<_, _>>()?;
+        Ok(Self {
+            source_hash: hash(source),
+            nuclides,
+        })
+    }
+
+    /// Returns whether this snapshot was made from `source`.
+    #[must_use]
+    pub fn is_fresh(&self, source: &[u8]) -> bool {
+        self.source_hash == hash(source)
+    }
+
+    /// Writes the snapshot to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an error is returned.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), AmeError> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&self.source_hash.to_le_bytes())?;
+        writer.write_all(&(self.nuclides.len() as u64).to_le_bytes())?;
+        for nuclide in &self.nuclides {
+            write_nuclide(&mut writer, nuclide)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a snapshot from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the data is not a snapshot, or the snapshot is of a different
+    /// [`FORMAT_VERSION`], an error is returned.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, AmeError> {
+        if read_array::<8, _>(&mut reader)? != MAGIC {
+            return Err(AmeError::InvalidSnapshot);
+        }
+        let version = u32::from_le_bytes(read_array(&mut reader)?);
+        if version != FORMAT_VERSION {
+            return Err(AmeError::SnapshotVersion(version));
+        }
+        let source_hash = u64::from_le_bytes(read_array(&mut reader)?);
+        let len = u64::from_le_bytes(read_array(&mut reader)?);
+        let nuclides = (0..len)
+            .map(|_| read_nuclide(&mut reader))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            source_hash,
+            nuclides,
+        })
+    }
+}
+
+/// Loads the data in `source`, using the snapshot at `cache` if it is fresh.
+///
+/// If the snapshot is missing, stale, or unreadable, `source` is parsed and a new snapshot is
+/// written to `cache`.
+///
+/// # Errors
+///
+/// If `source` fails to read or parse, or `cache` fails to write, an error is returned.
+pub fn load_cached(
+    source: impl AsRef<Path>,
+    cache: impl AsRef<Path>,
+) -> Result<Vec<Nuclide>, AmeError> {
+    let source = fs::read(source)?;
+    let cached = File::open(&cache)
+        .ok()
+        .and_then(|file| Snapshot::read(BufReader::new(file)).ok())
+        .filter(|snapshot| snapshot.is_fresh(&source));
+    if let Some(snapshot) = cached {
+        return Ok(snapshot.nuclides);
+    }
+
+    let snapshot = Snapshot::from_source(&source)?;
+    let mut writer = BufWriter::new(File::create(cache)?);
+    snapshot.write(&mut writer)?;
+    writer.flush()?;
+    Ok(snapshot.nuclides)
+}
+
+/// Hashes `source` with 64-bit FNV-1a.
+///
+/// Unlike [`DefaultHasher`][std::collections::hash_map::DefaultHasher], this is stable across
+/// Rust versions, so it can be stored.
+#[must_use]
+pub fn hash(source: &[u8]) -> u64 {
+    source.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn write_nuclide<W: Write>(writer: &mut W, nuclide: &Nuclide) -> Result<(), AmeError> {
+    writer.write_all(&nuclide.n.to_le_bytes())?;
+    writer.write_all(&nuclide.z.to_le_bytes())?;
+    write_str(writer, &nuclide.element)?;
+    match &nuclide.origin {
+        Some(origin) => {
+            writer.write_all(&[1])?;
+            write_str(writer, origin)?;
+        }
+        None => writer.write_all(&[0])?,
+    }
+    write_value(writer, &nuclide.mass_excess)?;
+    write_optional_value(writer, nuclide.binding_energy_per_a.as_ref())?;
+    writer.write_all(&[match nuclide.decay_mode {
+        None => 0,
+        Some(DecayMode::BetaMinus) => 1,
+        Some(DecayMode::BetaPlus) => 2,
+        Some(DecayMode::Alpha) => 3,
+        Some(DecayMode::ElectronCapture) => 4,
+    }])?;
+    write_optional_value(writer, nuclide.beta_decay_energy.as_ref())?;
+    write_value(writer, &nuclide.atomic_mass)?;
+    Ok(())
+}
+
+fn write_str<W: Write, const CAP: usize>(
+    writer: &mut W,
+    s: &ArrayString<CAP>,
+) -> Result<(), AmeError> {
+    let len = u8::try_from(s.len()).expect("the capacity is small");
+    writer.write_all(&[len])?;
+    writer.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+fn write_optional_value<W: Write>(writer: &mut W, value: Option<&Value>) -> Result<(), AmeError> {
+    match value {
+        Some(value) => {
+            writer.write_all(&[1])?;
+            write_value(writer, value)
+        }
+        None => Ok(writer.write_all(&[0])?),
+    }
+}
+
+fn write_value<W: Write>(writer: &mut W, value: &Value) -> Result<(), AmeError> {
+    writer.write_all(&value.mean.to_le_bytes())?;
+    writer.write_all(&value.uncertainty.to_le_bytes())?;
+    writer.write_all(&[u8::from(value.is_estimated)])?;
+    Ok(())
+}
+
+fn read_nuclide<R: Read>(reader: &mut R) -> Result<Nuclide, AmeError> {
+    let n = u32::from_le_bytes(read_array(reader)?);
+    let z = u32::from_le_bytes(read_array(reader)?);
+    let element = read_str(reader)?;
+    let origin = match read_array(reader)? {
+        [0] => None,
+        [1] => Some(read_str(reader)?),
+        _ => return Err(AmeError::InvalidSnapshot),
+    };
+    let mass_excess = read_value(reader)?;
+    let binding_energy_per_a = read_optional_value(reader)?;
+    let decay_mode = match read_array(reader)? {
+        [0] => None,
+        [1] => Some(DecayMode::BetaMinus),
+        [2] => Some(DecayMode::BetaPlus),
+        [3] => Some(DecayMode::Alpha),
+        [4] => Some(DecayMode::ElectronCapture),
+        _ => return Err(AmeError::InvalidSnapshot),
+    };
+    let beta_decay_energy = read_optional_value(reader)?;
+    let atomic_mass = read_value(reader)?;
+    Ok(Nuclide {
+        n,
+        z,
+        element,
+        origin,
+        mass_excess,
+        binding_energy_per_a,
+        decay_mode,
+        beta_decay_energy,
+        atomic_mass,
+    })
+}
+
+fn read_str<R: Read, const CAP: usize>(reader: &mut R) -> Result<ArrayString<CAP>, AmeError> {
+    let [len] = read_array(reader)?;
+    let mut buf = [0; CAP];
+    let buf = buf
+        .get_mut(..usize::from(len))
+        .ok_or(AmeError::InvalidSnapshot)?;
+    reader.read_exact(buf).map_err(|e| match e.kind() {
+        ErrorKind::UnexpectedEof => AmeError::InvalidSnapshot,
+        _ => e.into(),
+    })?;
+    let s = std::str::from_utf8(buf).map_err(|_| AmeError::InvalidSnapshot)?;
+    Ok(ArrayString::from(s).expect("the length is at most CAP"))
+}
+
+fn read_optional_value<R: Read>(reader: &mut R) -> Result<Option<Value>, AmeError> {
+    match read_array(reader)? {
+        [0] => Ok(None),
+        [1] => Ok(Some(read_value(reader)?)),
+        _ => Err(AmeError::InvalidSnapshot),
+    }
+}
+
+fn read_value<R: Read>(reader: &mut R) -> Result<Value, AmeError> {
+    let mean = f64::from_le_bytes(read_array(reader)?);
+    let uncertainty = f64::from_le_bytes(read_array(reader)?);
+    let is_estimated = match read_array(reader)? {
+        [0] => false,
+        [1] => true,
+        _ => return Err(AmeError::InvalidSnapshot),
+    };
+    Ok(Value {
+        mean,
+        uncertainty,
+        is_estimated,
+    })
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N], AmeError> {
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf).map_err(|e| match e.kind() {
+        ErrorKind::UnexpectedEof => AmeError::InvalidSnapshot,
+        _ => e.into(),
+    })?;
+    Ok(buf)
+}
@@ -0,0 +1,150 @@
+//! Querying the data with SQL, with [DataFusion](https://datafusion.apache.org).
+//!
+//! [`register`] adds the nuclides to a `SessionContext` as a table, so that they can be queried
+//! without exporting them anywhere. Each field of [`Nuclide`] is a column, and values are split
+//! into three columns, for the mean, the uncertainty, and whether the value is estimated, such as
+//! `mass_excess`, `mass_excess_uncertainty`, and `mass_excess_is_estimated`. A missing value is
+//! `NULL`. The `is_estimated` column is whether any of the values are estimated.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn query() -> Result<(), Box<dyn std::error::Error>> {
+//! use ame2020::{sql, Iter, Nuclide};
+//! use datafusion::prelude::SessionContext;
+//!
+//! let data: Vec<Nuclide> = Iter::from_path("mass.mas20")?.collect::<Result<_, _>>()?;
+//! let ctx = SessionContext::new();
+//! sql::register(&ctx, "nuclides", &data)?;
+//! let tin = ctx
+//!     .sql("SELECT * FROM nuclides WHERE z = 50 AND is_estimated = false ORDER BY n")
+//!     .await?;
+//! tin.show().await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{DecayMode, Nuclide, Value, VALUE_COLUMNS};
+use datafusion::{
+    arrow::{
+        array::{ArrayRef, BooleanArray, Float64Array, StringArray, UInt32Array},
+        datatypes::{Field, Schema},
+        error::ArrowError,
+        record_batch::RecordBatch,
+    },
+    datasource::MemTable,
+    error::DataFusionError,
+    prelude::SessionContext,
+};
+use std::sync::Arc;
+
+/// Returns the nuclides as an Arrow record batch, with the columns described in the
+/// [module documentation](self).
+///
+/// # Errors
+///
+/// If the batch can't be created, an error is returned.
+pub fn record_batch(nuclides: &[Nuclide]) -> Result<RecordBatch, ArrowError> {
+    let mut fields = Vec::new();
+    let mut columns: Vec<ArrayRef> = Vec::new();
+    let mut column = |name: &str, nullable: bool, array: ArrayRef| {
+        fields.push(Field::new(name, array.data_type().clone(), nullable));
+        columns.push(array);
+    };
+
+    let uints = |f: fn(&Nuclide) -> u32| Arc::new(nuclides.iter().map(f).collect::<UInt32Array>());
+    column("z", false, uints(|nuc| nuc.z));
+    column("n", false, uints(|nuc| nuc.n));
+    column("a", false, uints(Nuclide::a));
+    column(
+        "element",
+        false,
+        Arc::new(StringArray::from_iter_values(
+            nuclides.iter().map(|nuc| nuc.element.as_str()),
+        )),
+    );
+    column(
+        "origin",
+        true,
+        Arc::new(
+            nuclides
+                .iter()
+                .map(|nuc| nuc.origin.as_deref())
+                .collect::<StringArray>(),
+        ),
+    );
+    column(
+        "decay_mode",
+        true,
+        Arc::new(
+            nuclides
+                .iter()
+                .map(|nuc| nuc.decay_mode.map(DecayMode::label))
+                .collect::<StringArray>(),
+        ),
+    );
+
+    for (name, f) in VALUE_COLUMNS {
+        let nullable = !matches!(name, "mass_excess" | "atomic_mass");
+        let floats = |g: fn(&Value) -> f64| {
+            Arc::new(
+                nuclides
+                    .iter()
+                    .map(|nuc| f(nuc).map(g))
+                    .collect::<Float64Array>(),
+            )
+        };
+        column(name, nullable, floats(|value| value.mean));
+        column(
+            &format!("{name}_uncertainty"),
+            nullable,
+            floats(|value| value.uncertainty),
+        );
+        column(
+            &format!("{name}_is_estimated"),
+            nullable,
+            Arc::new(
+                nuclides
+                    .iter()
+                    .map(|nuc| f(nuc).map(|value| value.is_estimated))
+                    .collect::<BooleanArray>(),
+            ),
+        );
+    }
+    column(
+        "is_estimated",
+        false,
+        Arc::new(
+            nuclides
+                .iter()
+                .map(|nuc| {
+                    Some(
+                        VALUE_COLUMNS
+                            .iter()
+                            .any(|(_, f)| f(nuc).is_some_and(|value| value.is_estimated)),
+                    )
+                })
+                .collect::<BooleanArray>(),
+        ),
+    );
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+/// Registers `nuclides` in `ctx` as the table `name`.
+///
+/// A table that was already registered as `name` is replaced.
+///
+/// # Errors
+///
+/// If the table can't be created or registered, an error is returned.
+pub fn register(
+    ctx: &SessionContext,
+    name: &str,
+    nuclides: &[Nuclide],
+) -> Result<(), DataFusionError> {
+    let batch = record_batch(nuclides)?;
+    let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+    ctx.deregister_table(name)?;
+    ctx.register_table(name, Arc::new(table))?;
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Strategies for generating realistic nuclides with [proptest](https://crates.io/crates/proptest).
+//!
+//! Unlike the `Arbitrary` implementations for fuzzing, the nuclides are like those in the table.
+//! Z is at most 118, the element symbol matches Z, and the values are finite. The binding energy
+//! per nucleon and the atomic mass are derived from the mass excess, so the nuclides pass
+//! [`validate::check`](crate::validate::check). [`Value`] and [`Nuclide`] also implement
+//! proptest's `Arbitrary` with these strategies, so they can be generated with `any`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::strategy::nuclide;
+//! use proptest::{
+//!     strategy::{Strategy, ValueTree},
+//!     test_runner::TestRunner,
+//! };
+//!
+//! let mut runner = TestRunner::default();
+//! let nuc = nuclide().new_tree(&mut runner).unwrap().current();
+//! assert_eq!(ame2020::element::symbol(nuc.z), Some(&*nuc.element));
+//! ```
+use crate::{constants::Constants, element, DecayMode, Nuclide, Value};
+use arrayvec::ArrayString;
+use proptest::{
+    arbitrary::Arbitrary,
+    option,
+    prelude::{any, BoxedStrategy, Strategy},
+    sample,
+};
+
+// the codes for how masses were derived, as in the table
+const ORIGINS: [&str; 6] = ["-n", "-nn", "-p", "-pp", "-a", "x"];
+
+/// Returns a strategy for values, with a mean of at most 300,000 keV in magnitude, and an
+/// uncertainty of at most 1,000 keV.
+pub fn value() -> impl Strategy<Value = Value> {
+    (-300_000.0..300_000.0, 0.0..1_000.0, any::<bool>()).prop_map(
+        |(mean, uncertainty, is_estimated)| Value {
+            mean,
+            uncertainty,
+            is_estimated,
+        },
+    )
+}
+
+/// Returns a strategy for nuclides, with Z up to 118 and N up to 180.
+pub fn nuclide() -> impl Strategy<Value = Nuclide> {
+    (
+        (0..=118_u32, 0..=180_u32).prop_filter("A is at least 1", |(z, n)| z + n > 0),
+        option::of(sample::select(&ORIGINS[..])),
+        value(),
+        option::of(value()),
+    )
+        .prop_map(|((z, n), origin, mass_excess, beta_decay_energy)| {
+            let constants = Constants::AME2020;
+            let a = f64::from(z + n);
+            let binding_energy = f64::from(z) * constants.hydrogen_mass_excess
+                + f64::from(n) * constants.neutron_mass_excess
+                - mass_excess.mean;
+            let binding_energy_per_a = Value {
+                mean: binding_energy / a,
+                uncertainty: mass_excess.uncertainty / a,
+                is_estimated: mass_excess.is_estimated,
+            };
+            let atomic_mass = Value {
+                mean: a + constants.to_u(mass_excess.mean),
+                uncertainty: constants.to_u(mass_excess.uncertainty),
+                is_estimated: mass_excess.is_estimated,
+            };
+            Nuclide {
+                n,
+                z,
+                element: ArrayString::from(element::symbol(z).unwrap_or_default())
+                    .unwrap_or_default(),
+                origin: origin.and_then(|origin| ArrayString::from(origin).ok()),
+                mass_excess,
+                binding_energy_per_a: Some(binding_energy_per_a),
+                // every line in `mass.mas20` is labelled `B-`
+                decay_mode: Some(DecayMode::BetaMinus),
+                beta_decay_energy,
+                atomic_mass,
+            }
+        })
+}
+
+impl Arbitrary for Value {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        value().boxed()
+    }
+}
+
+impl Arbitrary for Nuclide {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        nuclide().boxed()
+    }
+}
@@ -0,0 +1,298 @@
+//! Counts and distributions over a table, for reports.
+//!
+//! [`summary`] counts the nuclides, and [`coverage`] finds where each element relies on estimated
+//! mass excesses, the ones marked with `#` in the table. [`completeness`] lists the nuclides of a
+//! region of the chart that are experimental, estimated, or missing.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, summary::summary};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! let summary = summary(&data);
+//! assert_eq!(summary.counts.total, 2);
+//! assert_eq!(summary.elements[&1].experimental, 1);
+//! assert_eq!(summary.z, Some(0..=1));
+//! ```
+use crate::Nuclide;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::RangeInclusive;
+
+/// What is in a table, from [`summary`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Summary {
+    /// The number of nuclides
+    pub counts: Counts,
+    /// The number of nuclides of each element, by Z
+    pub elements: BTreeMap<u32, Counts>,
+    /// The uncertainties of the experimental mass excesses, in keV, if there are any
+    pub uncertainty: Option<Distribution>,
+    /// The smallest and largest Z, if there are any nuclides
+    pub z: Option<RangeInclusive<u32>>,
+    /// The smallest and largest N, if there are any nuclides
+    pub n: Option<RangeInclusive<u32>>,
+    /// The smallest and largest A, if there are any nuclides
+    pub a: Option<RangeInclusive<u32>>,
+}
+
+/// Numbers of nuclides.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Counts {
+    /// All of them
+    pub total: usize,
+    /// The nuclides with only experimental values
+    pub experimental: usize,
+    /// The nuclides with any estimated values, as in [`Nuclide::is_estimated`]
+    pub estimated: usize,
+}
+
+impl Counts {
+    fn add(&mut self, nuc: &Nuclide) {
+        self.total += 1;
+        if nuc.is_estimated() {
+            self.estimated += 1;
+        } else {
+            self.experimental += 1;
+        }
+    }
+}
+
+/// The distribution of a set of numbers, by its quartiles.
+///
+/// The quartiles are interpolated linearly between the nearest numbers.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Distribution {
+    /// The smallest
+    pub min: f64,
+    /// The first quartile, which a quarter of the numbers are below
+    pub lower_quartile: f64,
+    /// The median
+    pub median: f64,
+    /// The third quartile, which three quarters of the numbers are below
+    pub upper_quartile: f64,
+    /// The largest
+    pub max: f64,
+    /// The mean
+    pub mean: f64,
+}
+
+impl Distribution {
+    /// Returns the distribution of `values`, or `None` if there are none.
+    ///
+    /// NaN is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ame2020::summary::Distribution;
+    ///
+    /// let distribution = Distribution::new([4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+    /// assert_eq!(distribution.median, 3.0);
+    /// assert_eq!(distribution.lower_quartile, 2.0);
+    /// assert_eq!(distribution.mean, 3.0);
+    /// ```
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut values: Vec<f64> = values.into_iter().filter(|x| !x.is_nan()).collect();
+        values.sort_by(f64::total_cmp);
+        let (&min, &max) = (values.first()?, values.last()?);
+        // a table has far fewer values than can be represented exactly
+        #[allow(clippy::cast_precision_loss)]
+        let len = values.len() as f64;
+        let quantile = |p: f64| {
+            let position = p * (len - 1.0);
+            // `position` is between 0 and the last index
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let i = position as usize;
+            let next = values.get(i + 1).unwrap_or(&values[i]);
+            #[allow(clippy::cast_precision_loss)]
+            let fraction = position - i as f64;
+            values[i] + fraction * (next - values[i])
+        };
+        Some(Self {
+            min,
+            lower_quartile: quantile(0.25),
+            median: quantile(0.5),
+            upper_quartile: quantile(0.75),
+            max,
+            mean: values.iter().sum::<f64>() / len,
+        })
+    }
+}
+
+/// The isotopes of an element with experimental and estimated mass excesses, from [`coverage`].
+///
+/// The experimentally known region is found by starting at the most bound isotope, with the
+/// largest binding energy per nucleon, and going to smaller and larger N until an isotope has an
+/// estimated mass excess.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Coverage {
+    /// Atomic number
+    pub z: u32,
+    /// The number of isotopes with experimental mass excesses
+    pub experimental: usize,
+    /// The number of isotopes with estimated mass excesses
+    pub estimated: usize,
+    /// The N of the isotopes in the experimentally known region, if there are any
+    pub known: Option<RangeInclusive<u32>>,
+    /// The N of the first estimated isotope below the known region, if there is one
+    pub proton_rich: Option<u32>,
+    /// The N of the first estimated isotope above the known region, if there is one
+    pub neutron_rich: Option<u32>,
+}
+
+/// Finds where each element in `nuclides` relies on estimated mass excesses, in order of Z.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{parse_str, summary::coverage};
+///
+/// let data = parse_str(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+/// let coverage = coverage(&data);
+/// assert_eq!(coverage[0].known, Some(1..=1));
+/// assert_eq!(coverage[0].neutron_rich, None);
+/// ```
+pub fn coverage<'a>(nuclides: impl IntoIterator<Item = &'a Nuclide>) -> Vec<Coverage> {
+    let mut elements: BTreeMap<u32, BTreeMap<u32, &Nuclide>> = BTreeMap::new();
+    for nuc in nuclides {
+        elements.entry(nuc.z).or_default().insert(nuc.n, nuc);
+    }
+    elements
+        .into_iter()
+        .map(|(z, isotopes)| {
+            let is_known = |nuc: &Nuclide| !nuc.mass_excess.is_estimated;
+            let experimental = isotopes.values().filter(|nuc| is_known(nuc)).count();
+            let mut coverage = Coverage {
+                z,
+                experimental,
+                estimated: isotopes.len() - experimental,
+                ..Coverage::default()
+            };
+            let binding_energy_per_a = |nuc: &Nuclide| {
+                nuc.binding_energy_per_a
+                    .as_ref()
+                    .map_or(0.0, |value| value.mean)
+            };
+            let Some(center) = isotopes
+                .values()
+                .filter(|nuc| is_known(nuc))
+                .max_by(|a, b| binding_energy_per_a(a).total_cmp(&binding_energy_per_a(b)))
+            else {
+                return coverage;
+            };
+            // the last known isotope, and the first estimated one, going away from the center
+            let edge = |isotopes: &mut dyn Iterator<Item = (&u32, &&Nuclide)>| {
+                let mut last = center.n;
+                for (&n, nuc) in isotopes {
+                    if !is_known(nuc) {
+                        return (last, Some(n));
+                    }
+                    last = n;
+                }
+                (last, None)
+            };
+            let (lightest, proton_rich) = edge(&mut isotopes.range(..center.n).rev());
+            let (heaviest, neutron_rich) = edge(&mut isotopes.range(center.n + 1..));
+            coverage.known = Some(lightest..=heaviest);
+            coverage.proton_rich = proton_rich;
+            coverage.neutron_rich = neutron_rich;
+            coverage
+        })
+        .collect()
+}
+
+/// The nuclides of a region of the chart, as (Z, N), by whether their mass excesses are known,
+/// from [`completeness`].
+///
+/// Each list is in order of Z, and then N.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Completeness {
+    /// The nuclides with experimental mass excesses
+    pub experimental: Vec<(u32, u32)>,
+    /// The nuclides with estimated mass excesses, which are extrapolated from the systematics
+    pub estimated: Vec<(u32, u32)>,
+    /// The nuclides that aren't in the table
+    pub missing: Vec<(u32, u32)>,
+}
+
+impl Completeness {
+    /// Returns whether every nuclide in the region has an experimental mass excess.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.estimated.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Finds which nuclides in `nuclides` with Z in `z` and N in `n` are experimental and estimated,
+/// and which are missing, such as for the region of a reaction network.
+///
+/// If a nuclide appears more than once, it is experimental if any of them is.
+///
+/// # Examples
+///
+/// ```
+/// use ame2020::{parse_str, summary::completeness};
+///
+/// let data = parse_str(r"1
+/// 1
+/// 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+/// let completeness = completeness(&data, 0..=1, 1..=1);
+/// assert_eq!(completeness.experimental, [(0, 1)]);
+/// assert_eq!(completeness.missing, [(1, 1)]);
+/// ```
+pub fn completeness<'a, F: 'a>(
+    nuclides: impl IntoIterator<Item = &'a Nuclide<F>>,
+    z: RangeInclusive<u32>,
+    n: RangeInclusive<u32>,
+) -> Completeness {
+    // whether each nuclide in the region is experimental
+    let mut known: BTreeMap<(u32, u32), bool> = BTreeMap::new();
+    for nuc in nuclides {
+        if z.contains(&nuc.z) && n.contains(&nuc.n) {
+            *known.entry((nuc.z, nuc.n)).or_default() |= !nuc.mass_excess.is_estimated;
+        }
+    }
+    let mut completeness = Completeness::default();
+    for z in z {
+        for n in n.clone() {
+            let list = match known.get(&(z, n)) {
+                Some(true) => &mut completeness.experimental,
+                Some(false) => &mut completeness.estimated,
+                None => &mut completeness.missing,
+            };
+            list.push((z, n));
+        }
+    }
+    completeness
+}
+
+/// Counts the nuclides in `nuclides`, and finds the distribution of the uncertainties and the
+/// extent of the chart.
+pub fn summary<'a>(nuclides: impl IntoIterator<Item = &'a Nuclide>) -> Summary {
+    let mut summary = Summary::default();
+    let mut uncertainties = Vec::new();
+    let extend = |range: &mut Option<RangeInclusive<u32>>, x: u32| {
+        *range = Some(match range.take() {
+            Some(range) => (*range.start()).min(x)..=(*range.end()).max(x),
+            None => x..=x,
+        });
+    };
+    for nuc in nuclides {
+        summary.counts.add(nuc);
+        summary.elements.entry(nuc.z).or_default().add(nuc);
+        if !nuc.mass_excess.is_estimated {
+            uncertainties.push(nuc.mass_excess.uncertainty);
+        }
+        extend(&mut summary.z, nuc.z);
+        extend(&mut summary.n, nuc.n);
+        extend(&mut summary.a, nuc.a());
+    }
+    summary.uncertainty = Distribution::new(uncertainties);
+    summary
+}
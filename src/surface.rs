@@ -0,0 +1,137 @@
+//! The mass excess as a surface over Z and N, for interpolating between nuclides and taking
+//! derivatives.
+//!
+//! The values are linear combinations of the mass excesses of nearby nuclides, so they are
+//! returned as [`Value`]s, with the uncertainties added in quadrature as if they were independent,
+//! and estimated if any of the mass excesses are. If a nuclide that is needed isn't in the
+//! dataset, `None` is returned.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{
+//!     dataset::{Dataset, Duplicates},
+//!     surface::Interpolation,
+//! };
+//!
+//! let data = br"1
+//! 1
+//! 0  0    1    1    2 H         13135.722895    0.000015   1112.2831     0.0002  B-      *                    2 014101.777844    0.000015
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014";
+//! let dataset = Dataset::from_slice(data, Duplicates::Error).unwrap();
+//! // halfway between H-1 and H-2
+//! let value = dataset.interpolate(1.0, 0.5, Interpolation::Bilinear).unwrap();
+//! assert!((value.mean - 10_212.346_979_5).abs() < 1e-6);
+//! // the neutron isn't in the dataset
+//! assert_eq!(dataset.interpolate(0.5, 0.5, Interpolation::Bilinear), None);
+//! ```
+use crate::{dataset::Dataset, Value};
+
+/// How to interpolate between nuclides.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Interpolation {
+    /// Linear in Z and N, between the 4 nuclides around the point
+    Bilinear,
+    /// Cubic in Z and N, as a Catmull-Rom spline through the 16 nuclides around the point
+    ///
+    /// The surface goes through the nuclides, and its slope is continuous between them.
+    Bicubic,
+}
+
+/// A direction on the chart of nuclides.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Axis {
+    /// Along Z, with N fixed
+    Z,
+    /// Along N, with Z fixed
+    N,
+}
+
+impl Dataset {
+    /// Returns the mass excess at (`z`, `n`), interpolated between the nuclides around it, in keV.
+    ///
+    /// Only the nuclides with a nonzero weight are needed, so at a nuclide, it is its own mass
+    /// excess, and along a line of constant Z or N, only the nuclides on that line are needed.
+    #[must_use]
+    pub fn interpolate(&self, z: f64, n: f64, interpolation: Interpolation) -> Option<Value> {
+        // the first nuclide, relative to the one below the point, and the weight of each
+        let weights = |x: f64| -> Option<(i64, Vec<f64>)> {
+            if !x.is_finite() || x < 0.0 {
+                return None;
+            }
+            let t = x.fract();
+            Some(match interpolation {
+                Interpolation::Bilinear => (0, vec![1.0 - t, t]),
+                Interpolation::Bicubic => (
+                    -1,
+                    vec![
+                        0.5 * (-t.powi(3) + 2.0 * t.powi(2) - t),
+                        0.5 * (3.0 * t.powi(3) - 5.0 * t.powi(2) + 2.0),
+                        0.5 * (-3.0 * t.powi(3) + 4.0 * t.powi(2) + t),
+                        0.5 * (t.powi(3) - t.powi(2)),
+                    ],
+                ),
+            })
+        };
+        let ((z_offset, z_weights), (n_offset, n_weights)) = (weights(z)?, weights(n)?);
+        // `z` and `n` are finite and positive, and far smaller than `i64::MAX`
+        #[allow(clippy::cast_possible_truncation)]
+        let (z0, n0) = (z as i64 + z_offset, n as i64 + n_offset);
+        let mut terms = Vec::new();
+        for (dz, z_weight) in (0..).zip(&z_weights) {
+            for (dn, n_weight) in (0..).zip(&n_weights) {
+                terms.push(((z0 + dz, n0 + dn), z_weight * n_weight));
+            }
+        }
+        self.combine(terms)
+    }
+
+    /// Returns the derivative of the mass excess at the nuclide with `z` protons and `n` neutrons
+    /// along `axis`, in keV per nucleon.
+    ///
+    /// It is the central difference, half the difference between the nuclides on either side.
+    #[must_use]
+    pub fn derivative(&self, z: u32, n: u32, axis: Axis) -> Option<Value> {
+        let [before, _, after] = neighbors(z, n, axis);
+        self.combine([(before, -0.5), (after, 0.5)])
+    }
+
+    /// Returns the second derivative of the mass excess at the nuclide with `z` protons and `n`
+    /// neutrons along `axis`, in keV per nucleon squared.
+    ///
+    /// It is the central difference, the difference between the nuclides on either side and
+    /// twice the nuclide.
+    #[must_use]
+    pub fn second_derivative(&self, z: u32, n: u32, axis: Axis) -> Option<Value> {
+        let [before, at, after] = neighbors(z, n, axis);
+        self.combine([(before, 1.0), (at, -2.0), (after, 1.0)])
+    }
+
+    // the sum of the mass excesses weighted by `terms`, skipping the weights that are zero
+    fn combine(&self, terms: impl IntoIterator<Item = ((i64, i64), f64)>) -> Option<Value> {
+        let mut sum = Value {
+            mean: 0.0,
+            uncertainty: 0.0,
+            is_estimated: false,
+        };
+        for ((z, n), weight) in terms {
+            if weight == 0.0 {
+                continue;
+            }
+            let nuc = self.get(u32::try_from(z).ok()?, u32::try_from(n).ok()?)?;
+            sum.mean += weight * nuc.mass_excess.mean;
+            sum.uncertainty = sum.uncertainty.hypot(weight * nuc.mass_excess.uncertainty);
+            sum.is_estimated |= nuc.mass_excess.is_estimated;
+        }
+        Some(sum)
+    }
+}
+
+// the nuclide before (z, n) along `axis`, itself, and the one after
+fn neighbors(z: u32, n: u32, axis: Axis) -> [(i64, i64); 3] {
+    let (z, n) = (i64::from(z), i64::from(n));
+    match axis {
+        Axis::Z => [(z - 1, n), (z, n), (z + 1, n)],
+        Axis::N => [(z, n - 1), (z, n), (z, n + 1)],
+    }
+}
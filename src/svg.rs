@@ -0,0 +1,183 @@
+//! Writing the chart of nuclides as an SVG image, for embedding in web pages.
+//!
+//! Each nuclide is a square, with N increasing to the right and Z increasing upwards, colored by a
+//! quantity with a [`ColorScale`], from the smallest value to the largest. Each square has a
+//! tooltip with the symbol and the mass excess. Nuclides without a value are gray.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, svg::{self, ColorScale, SvgOptions}};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let options = SvgOptions::new().cell_size(8).color_scale(ColorScale::Grayscale);
+//! let mut buf = Vec::new();
+//! svg::write(&mut buf, &data, |nuc| Some(nuc.mass_excess.mean), &options).unwrap();
+//! let svg = String::from_utf8(buf).unwrap();
+//! assert!(svg.contains("<title>n-1: 8071.31806 ± 0.00044 keV</title>"));
+//! ```
+use crate::{AmeError, Nuclide};
+use std::io::Write;
+
+// the viridis color map, at even steps
+const VIRIDIS: [[u8; 3]; 5] = [
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+
+/// How values are mapped to colors, from the smallest to the largest.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum ColorScale {
+    /// The viridis color map, from purple to yellow
+    #[default]
+    Viridis,
+    /// From black to white
+    Grayscale,
+    /// Linear, from `low` to `high`, as RGB
+    Linear { low: [u8; 3], high: [u8; 3] },
+}
+
+impl ColorScale {
+    /// Returns the color at `h`, from 0 to 1, as RGB.
+    #[must_use]
+    pub fn color(self, h: f64) -> [u8; 3] {
+        let h = if h.is_nan() { 0.0 } else { h.clamp(0.0, 1.0) };
+        match self {
+            Self::Viridis => {
+                // there are 4 steps between the 5 colors
+                let step = (h * 4.0).floor().min(3.0);
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let i = step as usize;
+                lerp(VIRIDIS[i], VIRIDIS[i + 1], h * 4.0 - step)
+            }
+            Self::Grayscale => lerp([0; 3], [255; 3], h),
+            Self::Linear { low, high } => lerp(low, high, h),
+        }
+    }
+}
+
+fn lerp(low: [u8; 3], high: [u8; 3], h: f64) -> [u8; 3] {
+    let mut color = [0; 3];
+    for ((c, low), high) in color.iter_mut().zip(low).zip(high) {
+        let (low, high) = (f64::from(low), f64::from(high));
+        // the result is between `low` and `high`
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let value = (low + (high - low) * h).round() as u8;
+        *c = value;
+    }
+    color
+}
+
+/// The configuration of [`write`].
+#[derive(Clone, Debug)]
+pub struct SvgOptions {
+    cell_size: u32,
+    color_scale: ColorScale,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 10,
+            color_scale: ColorScale::default(),
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Creates the default options, with 10 pixel squares and [`ColorScale::Viridis`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width and height of each square, in pixels.
+    #[must_use]
+    pub fn cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets how values are mapped to colors.
+    #[must_use]
+    pub fn color_scale(mut self, color_scale: ColorScale) -> Self {
+        self.color_scale = color_scale;
+        self
+    }
+}
+
+/// Writes the chart of nuclides to `writer`, as an SVG image colored by `quantity`.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write<W: Write>(
+    mut writer: W,
+    nuclides: &[Nuclide],
+    quantity: impl Fn(&Nuclide) -> Option<f64>,
+    options: &SvgOptions,
+) -> Result<(), AmeError> {
+    let values: Vec<Option<f64>> = nuclides
+        .iter()
+        .map(|nuc| quantity(nuc).filter(|x| x.is_finite()))
+        .collect();
+    let (min, max) = values
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+            (min.min(x), max.max(x))
+        });
+    let n_max = nuclides.iter().map(|nuc| nuc.n).max().unwrap_or(0);
+    let z_max = nuclides.iter().map(|nuc| nuc.z).max().unwrap_or(0);
+    let cell = options.cell_size;
+    let (width, height) = ((n_max + 1) * cell, (z_max + 1) * cell);
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )?;
+    for (nuc, value) in nuclides.iter().zip(values) {
+        let fill = match value {
+            Some(value) => {
+                // with one value, min == max, and the color is the middle of the scale
+                let position = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.5
+                };
+                let [red, green, blue] = options.color_scale.color(position);
+                format!("#{red:02x}{green:02x}{blue:02x}")
+            }
+            None => "#dddddd".to_string(),
+        };
+        let (left, top) = (nuc.n * cell, (z_max - nuc.z) * cell);
+        let mass_excess = &nuc.mass_excess;
+        writeln!(
+            writer,
+            r#"<rect x="{left}" y="{top}" width="{cell}" height="{cell}" fill="{fill}"><title>{}-{}: {} ± {} keV{}</title></rect>"#,
+            escape(&nuc.element),
+            nuc.a(),
+            mass_excess.mean,
+            mass_excess.uncertainty,
+            if mass_excess.is_estimated {
+                ", estimated"
+            } else {
+                ""
+            },
+        )?;
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,74 @@
+//! Writing mass tables for [TALYS](https://nds.iaea.org/talys/), and similar reaction codes.
+//!
+//! TALYS reads masses from a directory with a file for each element, named like `z026` for Z = 26,
+//! which is chosen with the `massdir` keyword. Each line of a file has Z, A, the atomic mass in u,
+//! and the mass excess in mega-electronvolts, in the Fortran format `(2i4,2f12.6)`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, talys};
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047").unwrap();
+//! let mut buf = Vec::new();
+//! talys::write(&mut buf, &data).unwrap();
+//! assert_eq!(String::from_utf8(buf).unwrap(), "   0   1    1.008665    8.071318\n");
+//! ```
+use crate::{AmeError, Nuclide};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Writes `nuclides` to `writer`, one per line, in the order they are in.
+///
+/// # Errors
+///
+/// If writing fails, an error is returned.
+pub fn write<'a, W: Write>(
+    mut writer: W,
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+) -> Result<(), AmeError> {
+    for nuc in nuclides {
+        writeln!(
+            writer,
+            "{:>4}{:>4}{:>12.6}{:>12.6}",
+            nuc.z,
+            nuc.a(),
+            nuc.atomic_mass.mean,
+            nuc.mass_excess.mean / 1000.0
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes `nuclides` to the directory `dir`, with a file for each element, for `massdir`.
+///
+/// The directory is created if it doesn't exist, and the files in it are replaced. The nuclides
+/// in each file are in order of A.
+///
+/// # Errors
+///
+/// If creating the directory or writing fails, an error is returned.
+pub fn write_dir<'a>(
+    dir: impl AsRef<Path>,
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+) -> Result<(), AmeError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut elements: BTreeMap<u32, Vec<&Nuclide>> = BTreeMap::new();
+    for nuc in nuclides {
+        elements.entry(nuc.z).or_default().push(nuc);
+    }
+    for (z, mut isotopes) in elements {
+        isotopes.sort_by_key(|nuc| nuc.a());
+        let mut writer = BufWriter::new(File::create(dir.join(format!("z{z:03}")))?);
+        write(&mut writer, isotopes)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
@@ -1,5 +1,16 @@
-use crate::{AmeError, Iter};
-use std::io::{self, Cursor};
+use crate::{
+    options::Validation, snapshot::Snapshot, AmeError, Field, Iter, Nuclide, NuclideSliceExt,
+    ParseOptions, SliceIter, Value,
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, Cursor},
+};
+
+// the options that make a line with an A or element that doesn't match N and Z an error
+fn cross_check() -> ParseOptions {
+    ParseOptions::new().validation(Validation::CrossCheck)
+}
 
 // if the file is empty, that's not an error, there are just no items
 #[test]
@@ -72,8 +83,22 @@ fn single() {
 fn too_short_line() {
     let reader = Cursor::new(include_str!("tests/too_short_line"));
     let mut iter = Iter::new(reader);
-    assert_eq!(iter.next().unwrap(), Err(AmeError::TooShortLine));
-    assert_eq!(iter.next().unwrap(), Err(AmeError::TooShortLine));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(AmeError::TooShortLine {
+            line: 3,
+            field: Field::AtomicMass,
+            columns: 110..123
+        })
+    );
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(AmeError::TooShortLine {
+            line: 4,
+            field: Field::BetaDecayEnergyUncertainty,
+            columns: 94..105
+        })
+    );
     assert!(iter.next().is_none());
 }
 
@@ -83,25 +108,43 @@ fn str_index() {
     // the char spans a slice boundary, so we get an indexing error
     let reader = Cursor::new(include_str!("tests/str_index_1"));
     let mut iter = Iter::new(reader);
-    assert_eq!(iter.next().unwrap(), Err(AmeError::StrIndex));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(AmeError::StrIndex {
+            line: 3,
+            field: Field::N,
+            columns: 4..9
+        })
+    );
     assert!(iter.next().is_none());
 
     // the char is within a slice, so we get a parsing error
     let reader = Cursor::new(include_str!("tests/str_index_2"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseFloat(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseFloat { line: 3, .. })
+    ));
     assert!(iter.next().is_none());
 }
 
-// the input for this test has a non-utf8 byte
+// the input for this test has a non-utf8 byte in the element, which is replaced
 #[test]
 fn non_utf8() {
-    let reader = Cursor::new(include_bytes!("tests/non_utf8"));
-    let mut iter = Iter::new(reader);
-    assert_eq!(
-        iter.next().unwrap(),
-        Err(AmeError::Io(io::ErrorKind::InvalidData))
-    );
+    let input = include_bytes!("tests/non_utf8");
+    let nuc = Iter::new(&input[..]).next().unwrap().unwrap();
+    assert_eq!((nuc.z, nuc.n, nuc.element.as_str()), (0, 1, "\u{fffd}"));
+    assert_eq!(SliceIter::new(input).next(), Some(Ok(nuc.clone())));
+    // the replaced symbol isn't checked against Z
+    let mut iter = Iter::with_options(&input[..], cross_check());
+    assert_eq!(iter.next(), Some(Ok(nuc)));
+    assert!(iter.warnings().is_empty());
+
+    // outside of the fields, it is ignored
+    let mut input = b"\xff\n1 \xae\n".to_vec();
+    input.extend_from_slice(&include_bytes!("tests/single")[2..]);
+    assert!(Iter::new(input.as_slice()).next().unwrap().is_ok());
+    assert!(SliceIter::new(&input).next().unwrap().is_ok());
 }
 
 // This test should be able to open "src", but since it is a directory, reading from it should be
@@ -114,6 +157,16 @@ fn io_error() {
     let reader = BufReader::new(reader);
     let mut iter = Iter::new(reader);
     assert!(matches!(iter.next().unwrap(), Err(AmeError::Io(_))));
+
+    let mut iter = Iter::from_path("src").unwrap();
+    assert!(matches!(iter.next().unwrap(), Err(AmeError::Io(_))));
+    // the iterator is finished after a reading error
+    assert!(iter.next().is_none());
+
+    assert!(matches!(
+        Iter::from_path("does not exist"),
+        Err(AmeError::Io(io::ErrorKind::NotFound))
+    ));
 }
 
 #[test]
@@ -121,32 +174,2927 @@ fn parse_error() {
     // fails to parse an int in n
     let reader = Cursor::new(include_str!("tests/parse_int_error_1"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseInt(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseInt {
+            line: 3,
+            field: Field::N,
+            ..
+        })
+    ));
 
     // fails to parse an int in z
     let reader = Cursor::new(include_str!("tests/parse_int_error_2"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseInt(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseInt {
+            line: 3,
+            field: Field::Z,
+            ..
+        })
+    ));
 
     // fails to parse an int in the first part of mass
     let reader = Cursor::new(include_str!("tests/parse_int_error_3"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseInt(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseInt {
+            line: 3,
+            field: Field::AtomicMass,
+            ..
+        })
+    ));
 
     // fails to parse a float in the mass excess mean
     let reader = Cursor::new(include_str!("tests/parse_float_error_1"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseFloat(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseFloat {
+            line: 3,
+            field: Field::MassExcess,
+            ..
+        })
+    ));
 
     // fails to parse a float in the mass excess uncertainty
     let reader = Cursor::new(include_str!("tests/parse_float_error_2"));
     let mut iter = Iter::new(reader);
-    assert!(matches!(iter.next().unwrap(), Err(AmeError::ParseFloat(_))));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseFloat {
+            line: 3,
+            field: Field::MassExcessUncertainty,
+            ..
+        })
+    ));
 }
 
 #[test]
 fn multi() {
     let reader = Cursor::new(include_str!("tests/multi"));
     let iter = Iter::new(reader);
-    assert!(matches!(iter.collect::<Result<Vec<_>, _>>(), Ok(_)));
+    assert!(iter.collect::<Result<Vec<_>, _>>().is_ok());
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let source = include_bytes!("tests/multi");
+    let snapshot = Snapshot::from_source(source).unwrap();
+    assert!(snapshot.is_fresh(source));
+    assert!(!snapshot.is_fresh(include_bytes!("tests/single")));
+
+    let mut buf = Vec::new();
+    snapshot.write(&mut buf).unwrap();
+    assert_eq!(Snapshot::read(buf.as_slice()), Ok(snapshot));
+}
+
+#[test]
+fn snapshot_errors() {
+    let snapshot = Snapshot::from_source(include_bytes!("tests/multi")).unwrap();
+    let mut buf = Vec::new();
+    snapshot.write(&mut buf).unwrap();
+
+    // truncated
+    assert_eq!(
+        Snapshot::read(&buf[..buf.len() - 1]),
+        Err(AmeError::InvalidSnapshot)
+    );
+
+    // not a snapshot
+    assert_eq!(
+        Snapshot::read(&include_bytes!("tests/multi")[..]),
+        Err(AmeError::InvalidSnapshot)
+    );
+
+    // a different version
+    buf[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        Snapshot::read(buf.as_slice()),
+        Err(AmeError::SnapshotVersion(u32::MAX))
+    );
+}
+
+// the slice iterator should give the same results as the reader iterator
+#[test]
+fn slice_iter() {
+    let inputs: [&[u8]; 7] = [
+        include_bytes!("tests/empty"),
+        include_bytes!("tests/pre_preamble"),
+        include_bytes!("tests/extra_headers"),
+        include_bytes!("tests/too_short_line"),
+        include_bytes!("tests/str_index_1"),
+        include_bytes!("tests/non_utf8"),
+        include_bytes!("tests/multi"),
+    ];
+    for input in inputs {
+        let expected = Iter::new(input).collect::<Vec<_>>();
+        let actual = SliceIter::new(input).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+}
+
+// the size is only known exactly once the body has started
+#[test]
+fn slice_iter_size_hint() {
+    let mut iter = SliceIter::new(include_bytes!("tests/multi"));
+    assert_eq!(iter.size_hint(), (0, Some(9)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (6, Some(6)));
+    assert_eq!(iter.count(), 6);
+
+    let mut iter = SliceIter::new(b"1\n1\n");
+    assert_eq!(iter.size_hint(), (0, Some(2)));
+    assert!(iter.next().is_none());
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    // the reader iterator doesn't know how many lines are left until it is done
+    let mut iter = Iter::new(&include_bytes!("tests/multi")[..]);
+    assert_eq!(iter.size_hint(), (0, None));
+    assert_eq!(iter.by_ref().count(), 7);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+// the async iterator should give the same results as the reader iterator
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn async_iter() {
+    use crate::AsyncIter;
+    use futures_core::Stream;
+    use std::{future::poll_fn, pin::Pin};
+
+    let inputs: [&[u8]; 4] = [
+        include_bytes!("tests/empty"),
+        include_bytes!("tests/too_short_line"),
+        include_bytes!("tests/non_utf8"),
+        include_bytes!("tests/multi"),
+    ];
+    for input in inputs {
+        let expected = Iter::new(input).collect::<Vec<_>>();
+        let mut iter = AsyncIter::new(input);
+        let mut actual = Vec::new();
+        while let Some(res) = poll_fn(|cx| Pin::new(&mut iter).poll_next(cx)).await {
+            actual.push(res);
+        }
+        assert_eq!(actual, expected);
+    }
+}
+
+// the parallel parser should give the same results as the iterators
+#[cfg(feature = "rayon")]
+#[test]
+fn par_parse_slice() {
+    use crate::options::Estimated;
+
+    let inputs: [&[u8]; 10] = [
+        include_bytes!("tests/empty"),
+        include_bytes!("tests/only_preamble_and_headers"),
+        include_bytes!("tests/extra_headers"),
+        include_bytes!("tests/too_short_line"),
+        include_bytes!("tests/parse_float_error_2"),
+        include_bytes!("tests/non_utf8"),
+        include_bytes!("tests/multi"),
+        include_bytes!("tests/element_multi"),
+        include_bytes!("tests/too_many_page_feeds"),
+        include_bytes!("tests/parse_int_error_3"),
+    ];
+    // long enough for many chunks, with tables and blank lines across their boundaries
+    let multi = include_str!("tests/multi");
+    let mut long = multi.repeat(200);
+    long.insert_str(long.len() / 3, "\n\n");
+    let long = [long.as_bytes()];
+    let options = [
+        ParseOptions::new(),
+        cross_check(),
+        ParseOptions::new().lenient(true),
+        ParseOptions::new().estimated(Estimated::Skip),
+        ParseOptions::new().concatenated(true),
+        ParseOptions::new()
+            .concatenated(true)
+            .stop_after(|nuc| nuc.z == 2),
+        ParseOptions::new()
+            .concatenated(true)
+            .lenient(true)
+            .estimated(Estimated::Skip),
+    ];
+    for input in inputs.iter().chain(&long) {
+        let expected = Iter::new(*input).collect::<Result<Vec<_>, _>>();
+        assert_eq!(crate::par::parse_slice(input), expected);
+        for options in &options {
+            let expected = SliceIter::with_options(input, options.clone()).collect();
+            assert_eq!(
+                crate::par::parse_slice_with_options(input, options),
+                expected
+            );
+        }
+    }
+}
+
+// estimated values use '#' in place of '.'
+#[test]
+fn estimated() {
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let li3 = &data[5];
+    assert_eq!(&li3.element, "Li");
+    assert!(li3.mass_excess.is_estimated);
+    assert!((li3.mass_excess.mean - 28667.0).abs() < 1e-9);
+    assert!((li3.mass_excess.uncertainty - 2000.0).abs() < 1e-9);
+    assert!((li3.binding_energy_per_a.as_ref().unwrap().mean + 2267.0).abs() < 1e-9);
+    assert!((li3.atomic_mass.mean - 3.030_775).abs() < 1e-12);
+}
+
+// the line after an error is still read, and the iterator stays finished after `None`
+#[test]
+fn fused() {
+    let input = include_bytes!("tests/too_short_line");
+    let mut iter = Iter::new(&input[..]);
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::TooShortLine { line: 3, .. })
+    ));
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::TooShortLine { line: 4, .. })
+    ));
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+
+    let mut iter = Iter::with_options(&include_bytes!("tests/element_multi")[..], cross_check());
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::Element { line: 3, .. })
+    ));
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+// lines are counted from 1, including the preamble, headers, and lines that can't be read
+#[test]
+fn line_numbers() {
+    let reader = Cursor::new(include_str!("tests/extra_headers").replace("8071.", "8071a"));
+    let mut iter = Iter::new(reader);
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(AmeError::ParseFloat { line: 8, .. })
+    ));
+
+    let mut input = include_bytes!("tests/element_multi").to_vec();
+    input.extend_from_slice(&include_bytes!("tests/too_short_line")[4..]);
+    let line_numbers = |res: Result<_, AmeError>| match res {
+        Ok(_) => Some(0),
+        Err(AmeError::TooShortLine { line, .. }) => Some(line),
+        Err(_) => None,
+    };
+    let expected = [None, Some(0), Some(5), Some(6)];
+    let actual = Iter::with_options(input.as_slice(), cross_check()).map(line_numbers);
+    assert!(actual.eq(expected));
+    let actual = SliceIter::with_options(&input, cross_check()).map(line_numbers);
+    assert!(actual.eq(expected));
+}
+
+#[test]
+fn error_display() {
+    let reader = Cursor::new(include_str!("tests/str_index_2"));
+    let err = Iter::new(reader).next().unwrap().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "float parsing error in mass excess, columns 28..42, on line 3"
+    );
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn diagnostic() {
+    use crate::diagnostic::SourceError;
+    use miette::Diagnostic;
+
+    let source = include_str!("tests/too_short_line");
+    let err = Iter::new(source.as_bytes()).nth(1).unwrap().unwrap_err();
+    let err = SourceError::new(err, source);
+    let label = err.labels().unwrap().next().unwrap();
+    assert_eq!(label.label(), Some("beta decay energy uncertainty"));
+    // the line ends partway through the field
+    assert_eq!((label.offset(), label.len()), (94, 10));
+    let contents = err
+        .source_code()
+        .unwrap()
+        .read_span(label.inner(), 0, 0)
+        .unwrap();
+    assert_eq!(contents.line(), 3);
+    assert_eq!(contents.data(), source.lines().nth(3).unwrap().as_bytes());
+}
+
+// the label is on the line as it's shown, with the tabs expanded
+#[cfg(feature = "miette")]
+#[test]
+fn diagnostic_tabs() {
+    use crate::diagnostic::SourceError;
+    use miette::Diagnostic;
+
+    let expanded = include_str!("tests/single").replacen("8071.", "8071a", 1);
+    // the tab is at column 4, so it's expanded to the 4 spaces it replaces
+    let source = expanded.replacen("0  1    1", "0  1\t1", 1);
+    let err = Iter::new(source.as_bytes()).next().unwrap().unwrap_err();
+    let columns = err.columns().unwrap();
+    let err = SourceError::new(err, &source);
+    let label = err.labels().unwrap().next().unwrap();
+    assert_eq!(label.label(), Some("mass excess"));
+    assert_eq!(
+        (label.offset(), label.len()),
+        (columns.start, columns.len())
+    );
+    let contents = err
+        .source_code()
+        .unwrap()
+        .read_span(label.inner(), 0, 0)
+        .unwrap();
+    let text = expanded.lines().nth(2).unwrap();
+    assert_eq!(contents.data(), text.as_bytes());
+    assert_eq!(text[label.offset()..][..label.len()].trim(), "8071a31806");
+}
+
+// bad lines are skipped, and their errors are kept
+#[test]
+fn lenient() {
+    let mut input = include_bytes!("tests/element_multi").to_vec();
+    input.extend_from_slice(&include_bytes!("tests/too_short_line")[4..]);
+
+    let mut iter = Iter::with_options(input.as_slice(), cross_check()).lenient();
+    assert_eq!(iter.by_ref().count(), 1);
+    let skipped = iter.skipped();
+    assert_eq!(skipped.len(), 3);
+    assert!(matches!(skipped[0], AmeError::Element { line: 3, .. }));
+    assert_eq!(skipped[1].line(), Some(5));
+    assert_eq!(skipped[2].line(), Some(6));
+
+    let mut slice_iter = SliceIter::with_options(&input, cross_check()).lenient();
+    assert_eq!(slice_iter.by_ref().count(), 1);
+    assert_eq!(slice_iter.skipped(), skipped);
+}
+
+// the test data is consistent, except where it was edited
+#[test]
+fn validate() {
+    use crate::validate::{check_all, Inconsistency};
+
+    let mut data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let reports = check_all(&data);
+    assert!(reports.iter().all(Vec::is_empty), "{reports:?}");
+
+    // the mass excess of H-3 is also used for its beta decay energy
+    data[3].mass_excess.mean += 1.0;
+    let reports = check_all(&data);
+    assert!(matches!(
+        reports[3].as_slice(),
+        [
+            Inconsistency::MassExcess { .. },
+            Inconsistency::BindingEnergyPerA { .. },
+            Inconsistency::BetaDecayEnergy { .. },
+        ]
+    ));
+    assert!(reports[4].is_empty());
+}
+
+// A is checked against N+Z, when asked for
+#[test]
+fn mass_number() {
+    use crate::Warning;
+
+    let input = include_str!("tests/mass_number");
+    let mut iter = Iter::with_options(Cursor::new(input), cross_check());
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(AmeError::MassNumber {
+            line: 3,
+            columns: 14..19,
+            a: 2,
+            expected: 1
+        })
+    );
+    assert!(iter.next().is_none());
+
+    // by default, the A column isn't read
+    assert!(crate::parse_str(input).is_ok());
+
+    let options = ParseOptions::new().validation(Validation::Warn);
+    let mut iter = Iter::with_options(Cursor::new(input), options);
+    assert!(iter.next().unwrap().is_ok());
+    let warning = Warning::MassNumber {
+        line: 3,
+        a: 2,
+        expected: 1,
+    };
+    assert_eq!(
+        iter.warnings()[0].to_string(),
+        "mass number 2 is not N+Z (1), on line 3"
+    );
+    assert_eq!(iter.warnings(), [warning]);
+}
+
+#[test]
+fn element() {
+    let reader = Cursor::new(include_str!("tests/element"));
+    let mut iter = Iter::with_options(reader, cross_check());
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(AmeError::Element {
+            line: 3,
+            columns: 20..23,
+            element: "H".parse().unwrap(),
+            z: 0
+        })
+    );
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn decay_mode() {
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    assert!(data
+        .iter()
+        .all(|nuc| nuc.decay_mode == Some(crate::DecayMode::BetaMinus)));
+
+    let input = include_str!("tests/single").replacen("B-", "XX", 1);
+    assert_eq!(
+        crate::parse_str(&input),
+        Err(AmeError::DecayMode {
+            line: 3,
+            columns: 79..81,
+            mode: "XX".parse().unwrap(),
+        })
+    );
+}
+
+#[test]
+fn origin() {
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    assert_eq!(data[0].origin, None);
+    assert_eq!(data[5].origin.as_deref(), Some("-pp"));
+}
+
+// a placeholder makes an optional value `None`, and is an error in a required value
+#[test]
+fn placeholder() {
+    let input = include_str!("tests/single");
+    let start = input.trim_end().rfind('\n').unwrap() + 1;
+
+    let mut binding = input.to_string();
+    binding.replace_range(start + 54..start + 78, &format!("{:>13}{:11}", "*", ""));
+    let data = crate::parse_str(&binding).unwrap();
+    assert_eq!(data[0].binding_energy_per_a, None);
+
+    let mut mass_excess = input.to_string();
+    mass_excess.replace_range(start + 28..start + 54, &format!("{:>14}{:12}", "*", ""));
+    assert_eq!(
+        crate::parse_str(&mass_excess),
+        Err(AmeError::Placeholder {
+            line: 3,
+            field: Field::MassExcess,
+            columns: 28..42,
+        })
+    );
+}
+
+// CRLF line endings, tabs in place of spaces, and trailing whitespace don't change the data
+#[test]
+fn whitespace() {
+    let input = include_str!("tests/multi");
+    let expected = crate::parse_str(input).unwrap();
+
+    let crlf = input.replace('\n', "\r\n");
+    assert_eq!(crate::parse_str(&crlf).unwrap(), expected);
+    assert_eq!(
+        Iter::new(Cursor::new(&crlf))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        expected
+    );
+
+    let trailing = input.replace('\n', "   \n");
+    assert_eq!(crate::parse_str(&trailing).unwrap(), expected);
+
+    // replace each run of spaces that ends on a tab stop with a tab, like `unexpand`
+    let tabs = input
+        .lines()
+        .map(|line| {
+            let mut out = String::new();
+            for (i, chunk) in line.as_bytes().chunks(8).enumerate() {
+                let chunk = std::str::from_utf8(chunk).unwrap();
+                let trimmed = chunk.trim_end_matches(' ');
+                if chunk.len() == 8 && trimmed.len() < 7 && (i + 1) * 8 < line.len() {
+                    out.push_str(trimmed);
+                    out.push('\t');
+                } else {
+                    out.push_str(chunk);
+                }
+            }
+            out + "\n"
+        })
+        .collect::<String>();
+    assert!(tabs.contains('\t'));
+    assert_eq!(crate::parse_str(&tabs).unwrap(), expected);
+}
+
+// a nuclide that appears twice is an error, unless a policy is chosen
+#[test]
+fn duplicates() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_str!("tests/multi");
+    let dataset = Dataset::from_slice(multi.as_bytes(), Duplicates::Error).unwrap();
+    assert_eq!(dataset.len(), 7);
+    assert_eq!(
+        dataset.get(3, 0).map(|nuc| nuc.element.as_str()),
+        Some("Li")
+    );
+
+    // li-3 again, but with a different mass excess
+    let li3 = multi.lines().find(|line| line.contains(" Li ")).unwrap();
+    let input = format!("{multi}{}\n", li3.replacen("28667#", "28668#", 1));
+    assert_eq!(
+        Dataset::from_slice(input.as_bytes(), Duplicates::Error),
+        Err(AmeError::Duplicate {
+            line: 10,
+            first_line: 8,
+            z: 3,
+            n: 0
+        })
+    );
+    assert_eq!(
+        Dataset::read(input.as_bytes(), Duplicates::Error),
+        Dataset::from_slice(input.as_bytes(), Duplicates::Error)
+    );
+
+    let first = Dataset::from_slice(input.as_bytes(), Duplicates::KeepFirst).unwrap();
+    assert_eq!(first.nuclides(), dataset.nuclides());
+    assert_eq!(first.duplicates().len(), 1);
+    let last = Dataset::from_slice(input.as_bytes(), Duplicates::KeepLast).unwrap();
+    assert_eq!(last.len(), 7);
+    assert!((last.get(3, 0).unwrap().mass_excess.mean - 28668.0).abs() < 1e-9);
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn fingerprint() {
+    use crate::fingerprint::{sha256, verify};
+
+    let expected = [
+        0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22,
+        0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00,
+        0x15, 0xad,
+    ];
+    assert_eq!(sha256(&b"abc"[..]), Ok(expected));
+    assert_eq!(verify(&b"abd"[..], &expected), Ok(false));
+
+    let data = include_bytes!("tests/multi");
+    let expected = [
+        0x1a, 0x87, 0x2a, 0x84, 0xbe, 0xec, 0x0e, 0x6e, 0x48, 0xa0, 0x42, 0xb7, 0xf4, 0x31, 0xaf,
+        0x1e, 0x47, 0x8b, 0x19, 0xec, 0xca, 0x6b, 0x4c, 0xea, 0x1e, 0xbe, 0x53, 0x2b, 0x79, 0x96,
+        0x9e, 0xac,
+    ];
+    assert_eq!(verify(&data[..], &expected), Ok(true));
+    // a single digit changed, as in an edited copy
+    let mut edited = data.to_vec();
+    let i = edited.iter().position(|&b| b == b'8').unwrap();
+    edited[i] = b'9';
+    assert_eq!(verify(&edited[..], &expected), Ok(false));
+}
+
+// the generated source is compiled here, and it must be what is generated now
+#[test]
+fn codegen() {
+    mod generated {
+        include!("tests/codegen_multi.rs");
+    }
+
+    let multi = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let mut source = Vec::new();
+    crate::codegen::write_static(&mut source, "NUCLIDES", &multi).unwrap();
+    assert_eq!(
+        String::from_utf8(source).unwrap(),
+        include_str!("tests/codegen_multi.rs")
+    );
+    let nuclides: Vec<Nuclide> = generated::NUCLIDES
+        .iter()
+        .map(crate::codegen::StaticNuclide::to_nuclide)
+        .collect();
+    assert_eq!(nuclides, multi);
+}
+
+#[test]
+fn constants() {
+    use crate::{
+        constants::Constants,
+        dataset::{Dataset, Duplicates},
+    };
+
+    // the preamble doesn't state the constants, so the dataset uses those of AME2020
+    let multi = include_bytes!("tests/multi");
+    let dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    assert_eq!(dataset.constants(), &Constants::AME2020);
+    assert!(dataset.validate().inconsistencies.is_empty());
+
+    // others are used for the conversions, so the masses don't match the mass excesses
+    let constants = Constants {
+        atomic_mass_unit: 931_495.0,
+        ..Constants::AME2020
+    };
+    let dataset = dataset.with_constants(constants);
+    assert_eq!(dataset.constants(), &constants);
+    assert!(!dataset.validate().inconsistencies.is_empty());
+}
+
+#[test]
+fn dataset_particles() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_bytes!("tests/multi");
+    let dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    let a = |nuc: Option<&Nuclide>| nuc.map(|nuc| (nuc.z, nuc.n));
+    assert_eq!(a(dataset.neutron()), Some((0, 1)));
+    assert_eq!(a(dataset.proton()), Some((1, 0)));
+    assert_eq!(a(dataset.deuteron()), Some((1, 1)));
+    assert_eq!(a(dataset.triton()), Some((1, 2)));
+    assert_eq!(a(dataset.helion()), Some((2, 1)));
+    assert_eq!(a(dataset.alpha()), None);
+}
+
+#[test]
+fn dataset_group_by() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let keys = |groups: BTreeMap<u32, Vec<&Nuclide>>| -> Vec<(u32, Vec<(u32, u32)>)> {
+        groups
+            .into_iter()
+            .map(|(key, group)| (key, group.iter().map(|nuc| (nuc.z, nuc.n)).collect()))
+            .collect()
+    };
+    assert_eq!(
+        keys(dataset.group_by_element()),
+        [
+            (0, vec![(0, 1)]),
+            (1, vec![(1, 0), (1, 1), (1, 2), (1, 3)]),
+            (2, vec![(2, 1)]),
+            (3, vec![(3, 0)]),
+        ]
+    );
+    assert_eq!(
+        keys(dataset.group_by_a()),
+        [
+            (1, vec![(0, 1), (1, 0)]),
+            (2, vec![(1, 1)]),
+            (3, vec![(1, 2), (2, 1), (3, 0)]),
+            (4, vec![(1, 3)]),
+        ]
+    );
+    assert_eq!(
+        keys(dataset.group_by_n()),
+        [
+            (0, vec![(1, 0), (3, 0)]),
+            (1, vec![(0, 1), (1, 1), (2, 1)]),
+            (2, vec![(1, 2)]),
+            (3, vec![(1, 3)]),
+        ]
+    );
+    assert!(Dataset::default().group_by_a().is_empty());
+}
+
+#[test]
+fn dataset_nearest() {
+    use crate::dataset::{self, Dataset, Duplicates};
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let zn = |nuclides: Vec<&Nuclide>| -> Vec<(u32, u32)> {
+        nuclides.iter().map(|nuc| (nuc.z, nuc.n)).collect()
+    };
+    assert_eq!(zn(dataset.nearest(1, 1, 3)), [(1, 1), (0, 1), (1, 0)]);
+    // 2He isn't in the dataset
+    assert_eq!(zn(dataset.nearest(2, 0, 2)), [(1, 0), (2, 1)]);
+    assert_eq!(dataset.nearest(1, 1, 100).len(), 7);
+    assert_eq!(
+        zn(dataset.neighbors_within(1, 2, 1.5)),
+        [(1, 2), (1, 1), (1, 3), (0, 1), (2, 1)]
+    );
+    assert_eq!(zn(dataset.neighbors_within(10, 10, 1.0)), []);
+
+    let measured = dataset.iter().filter(|nuc| !nuc.mass_excess.is_estimated);
+    assert_eq!(zn(dataset::nearest_in(measured, 3, 0, 1)), [(2, 1)]);
+}
+
+#[test]
+fn dataset_overrides() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        overrides,
+    };
+
+    let multi = include_bytes!("tests/multi");
+    let mut dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    let h3 = dataset.get(1, 2).unwrap().clone();
+    // H-3, and Li-4, which isn't in the dataset
+    let patch = overrides::read(
+        &b"# nuclide mass_excess uncertainty
+
+3H 14949.8 0.1
+Li4 25320# 210
+"[..],
+    )
+    .unwrap();
+    assert_eq!(patch.len(), 2);
+    let li4 = &patch[1];
+    assert_eq!((li4.z, li4.n, li4.element.as_str()), (3, 1, "Li"));
+    assert!(li4.mass_excess.is_estimated);
+    // from the patch, H-3 has the same binding energy and atomic mass as in the evaluation
+    let binding_energy_per_a = patch[0].binding_energy_per_a.as_ref().unwrap();
+    assert!((binding_energy_per_a.mean - 2_827.3).abs() < 0.1);
+    assert!((binding_energy_per_a.uncertainty - 0.1 / 3.0).abs() < 1e-9);
+    assert!((patch[0].atomic_mass.mean - 3.016_049).abs() < 1e-6);
+
+    dataset.apply_overrides(patch);
+    assert_eq!(dataset.len(), 8);
+    // H-3 is replaced in its place, and Li-4 is added at the end
+    let at = |i: usize| {
+        let nuc = &dataset.nuclides()[i];
+        (nuc.z, nuc.n, nuc.mass_excess.mean)
+    };
+    assert_eq!((at(3), at(7)), ((1, 2, 14_949.8), (3, 1, 25_320.0)));
+    let overridden: Vec<_> = dataset
+        .overrides()
+        .iter()
+        .map(|over| (over.z, over.n, over.replaced.is_some()))
+        .collect();
+    assert_eq!(overridden, [(1, 2, true), (3, 1, false)]);
+    assert_eq!(dataset.overrides()[0].replaced.as_ref(), Some(&h3));
+
+    let invalid = |patch: &str| overrides::read(patch.as_bytes()).unwrap_err();
+    let column = |column| AmeError::InvalidColumn { line: 1, column };
+    assert_eq!(invalid("3X 1.0 0.1"), column("nuclide"));
+    assert_eq!(invalid("3H one 0.1"), column("mass excess"));
+    assert_eq!(invalid("3H 1.0"), column("uncertainty"));
+    assert_eq!(invalid("3H 1.0 -0.1"), column("uncertainty"));
+}
+
+#[test]
+fn dataset_validate() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_str!("tests/multi");
+    let report = Dataset::from_slice(multi.as_bytes(), Duplicates::Error)
+        .unwrap()
+        .validate();
+    assert!(report.inconsistencies.is_empty());
+    assert!(report.duplicates.is_empty());
+    assert!(report.out_of_range.is_empty());
+    // the heaviest isobars have no neighbor in the data
+    assert!(!report.missing_neighbors.is_empty());
+
+    let li3 = multi.lines().find(|line| line.contains(" Li ")).unwrap();
+    let input = format!("{multi}{li3}\n");
+    let dataset = Dataset::from_slice(input.as_bytes(), Duplicates::KeepFirst).unwrap();
+    assert_eq!(dataset.duplicates().len(), 1);
+    let report = dataset.validate();
+    assert_eq!(report.duplicates, dataset.duplicates());
+    assert!(!report.is_empty());
+}
+
+// "nan" and "inf" parse as floats, but aren't valid values
+#[test]
+fn non_finite() {
+    let input = include_str!("tests/single");
+    assert_eq!(
+        crate::parse_str(&input.replacen("8071.31806", "       NaN", 1)),
+        Err(AmeError::NonFinite {
+            line: 3,
+            field: Field::MassExcess,
+            columns: 28..42,
+        })
+    );
+    assert_eq!(
+        crate::parse_str(&input.replacen("0.0004 ", "   inf ", 1)),
+        Err(AmeError::NonFinite {
+            line: 3,
+            field: Field::BetaDecayEnergyUncertainty,
+            columns: 94..105,
+        })
+    );
+    assert_eq!(
+        crate::parse_str(&input.replacen(" 0.00044", "-0.00044", 1)),
+        Err(AmeError::NegativeUncertainty {
+            line: 3,
+            field: Field::MassExcessUncertainty,
+            columns: 42..54,
+        })
+    );
+}
+
+// only the estimated values are `None`, even if other values on the line are estimated
+#[test]
+fn experimental() {
+    let input = include_bytes!("tests/multi");
+    let data = SliceIter::new(input)
+        .experimental()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(data.len(), 7);
+    assert_eq!(
+        Iter::new(&input[..])
+            .experimental()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        data
+    );
+    let li3 = &data[5];
+    assert!(li3.mass_excess.is_none());
+    assert!(li3.atomic_mass.is_none());
+    assert!(data[6].mass_excess.is_some());
+
+    let input = include_str!("tests/single").replacen("782.3470 ", "782#3470 ", 1);
+    let nuclide = crate::parse_str(&input).unwrap().remove(0);
+    assert!(!nuclide.mass_excess.is_estimated);
+    let nuclide = crate::ExperimentalNuclide::from(nuclide);
+    assert!(nuclide.mass_excess.is_some());
+    assert!(nuclide.beta_decay_energy.is_none());
+}
+
+#[test]
+fn float_type() {
+    use crate::ExperimentalNuclide;
+
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    #[allow(clippy::cast_possible_truncation)]
+    let single: Vec<Nuclide<f32>> = data
+        .iter()
+        .cloned()
+        .map(|nuc| nuc.map(|x| x as f32))
+        .collect();
+    let h2 = &single[2];
+    assert_eq!((h2.z, h2.n, h2.a(), h2.element), (1, 1, 2, data[2].element));
+    assert_eq!(
+        (h2.mass_excess.mean, h2.mass_excess.uncertainty),
+        (13_135.723, 0.000_015)
+    );
+    assert!(single[5].is_estimated());
+    assert!(single[5].mass_excess > single[4].mass_excess);
+
+    // back to f64, the values are within the precision of f32
+    let double: Nuclide = h2.clone().into();
+    assert!((double.mass_excess.mean - data[2].mass_excess.mean).abs() < 1e-3);
+    assert_eq!(double.decay_mode, data[2].decay_mode);
+    let experimental = ExperimentalNuclide::from(single[5].clone());
+    assert!(experimental.mass_excess.is_none());
+    assert_eq!(
+        Value::from(Value {
+            mean: 1.5_f32,
+            uncertainty: 0.25,
+            is_estimated: true,
+        }),
+        Value {
+            mean: 1.5,
+            uncertainty: 0.25,
+            is_estimated: true,
+        }
+    );
+}
+
+#[test]
+fn float_parse() {
+    use crate::{metadata::Metadata, summary::completeness};
+
+    let source = include_str!("tests/multi");
+    let double = crate::parse_str(source).unwrap();
+    let single: Vec<Nuclide<f32>> = SliceIter::with_float(source.as_bytes(), ParseOptions::new())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(single.len(), double.len());
+    for (single, double) in single.iter().zip(&double) {
+        assert_eq!((single.z, single.n), (double.z, double.n));
+        assert_eq!(single.is_estimated(), double.is_estimated());
+        #[allow(clippy::cast_possible_truncation)]
+        let rounded = double.mass_excess.clone().map(|x| x as f32);
+        assert_eq!(single.mass_excess, rounded);
+        assert!((f64::from(single.atomic_mass.mean) - double.atomic_mass.mean).abs() < 1e-6);
+    }
+    let read: Vec<Nuclide<f32>> = Iter::with_float(Cursor::new(source), ParseOptions::new())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(read, single);
+
+    // the options apply as with f64
+    let options = ParseOptions::new().stop_after(|nuc| nuc.z == 1);
+    let stopped: Vec<Nuclide<f32>> = SliceIter::with_float(source.as_bytes(), options)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(stopped.len(), 2);
+    let experimental = SliceIter::<f32>::with_float(source.as_bytes(), ParseOptions::new())
+        .experimental()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(experimental[5].mass_excess.is_none());
+
+    // and with the helpers that don't compute anything
+    assert_eq!(single.min_mass_excess().map(|nuc| nuc.z), Some(1));
+    let mut runs = Metadata::new();
+    runs.insert(1, 1, "run 1");
+    assert_eq!(runs.join(&single).count(), 1);
+    assert_eq!(completeness(&single, 3..=3, 0..=0).estimated, [(3, 0)]);
+}
+
+#[test]
+fn options() {
+    use crate::{
+        options::{Estimated, Placeholders},
+        Warning,
+    };
+
+    let collect = |input: &[u8], options: ParseOptions| {
+        let expected = Iter::with_options(input, options.clone()).collect::<Vec<_>>();
+        let actual = SliceIter::with_options(input, options).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        actual.into_iter().collect::<Result<Vec<_>, _>>()
+    };
+    let multi = include_bytes!("tests/multi");
+
+    let options = ParseOptions::new().validation(Validation::Off);
+    assert!(collect(include_bytes!("tests/mass_number"), options.clone()).is_ok());
+    assert!(collect(include_bytes!("tests/element"), options).is_ok());
+    // the mismatches are only checked when asked for
+    let mut iter = SliceIter::new(include_bytes!("tests/element"));
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.warnings().is_empty());
+    let options = ParseOptions::new().validation(Validation::Warn);
+    let mut iter = SliceIter::with_options(include_bytes!("tests/element"), options);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(
+        iter.warnings(),
+        [Warning::Element {
+            line: 3,
+            element: "H".parse().unwrap(),
+            z: 0
+        }]
+    );
+
+    let options = ParseOptions::new().placeholders(Placeholders::Error);
+    assert!(matches!(
+        collect(multi, options),
+        Err(AmeError::Placeholder {
+            field: Field::BetaDecayEnergy,
+            ..
+        })
+    ));
+
+    let options = ParseOptions::new().estimated(Estimated::Skip);
+    let data = collect(multi, options.clone()).unwrap();
+    assert_eq!(data.len(), 5);
+    assert!(data
+        .iter()
+        .all(|nuc| &nuc.element != "Li" && (nuc.z, nuc.n) != (2, 1)));
+    assert_eq!(SliceIter::with_options(multi, options).size_hint().0, 0);
+
+    let options = ParseOptions::new().stop_after(|nuc| nuc.a() == 2);
+    let data = collect(multi, options.clone()).unwrap();
+    assert_eq!(data.last().map(crate::Nuclide::a), Some(2));
+    let mut iter = SliceIter::with_options(multi, options);
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn warnings() {
+    use crate::Warning;
+
+    let mut iter = Iter::new(Cursor::new(include_str!("tests/extra_headers")));
+    assert!(iter.by_ref().all(|res| res.is_ok()));
+    assert_eq!(
+        iter.warnings(),
+        (4..=7)
+            .map(|line| Warning::ExtraHeader { line })
+            .collect::<Vec<_>>()
+    );
+
+    let input = format!("{}\n   \n", include_str!("tests/multi"));
+    let mut iter = SliceIter::new(input.as_bytes());
+    assert_eq!(iter.size_hint(), (0, Some(9)));
+    assert_eq!(iter.by_ref().flatten().count(), 7);
+    assert_eq!(
+        iter.warnings(),
+        [
+            Warning::BlankLine { line: 10 },
+            Warning::BlankLine { line: 11 }
+        ]
+    );
+    assert_eq!(
+        iter.warnings()[0].to_string(),
+        "blank line in the body, on line 10"
+    );
+}
+
+#[test]
+fn progress() {
+    use std::sync::{Arc, Mutex};
+
+    let input = include_bytes!("tests/multi");
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reports);
+    let mut iter = Iter::new(&input[..]).on_progress(move |p| sink.lock().unwrap().push(p));
+    assert_eq!(iter.by_ref().count(), 7);
+    assert_eq!(iter.progress().bytes, input.len() as u64);
+    assert_eq!(iter.progress().nuclides, 7);
+
+    let reports = reports.lock().unwrap();
+    // one for each line
+    assert_eq!(reports.len(), 9);
+    assert_eq!(reports.last(), Some(&iter.progress()));
+    assert!(reports.windows(2).all(|w| w[0].bytes < w[1].bytes));
+}
+
+// with `concatenated`, a second table starts over, instead of being parsed as nuclides
+#[test]
+fn concatenated() {
+    use crate::{ParseOptions, Warning};
+
+    let single = include_str!("tests/single");
+    let multi = include_str!("tests/multi");
+    let input = format!("{single}{multi}");
+    assert!(crate::parse_str(&input).is_err());
+
+    let options = ParseOptions::new().concatenated(true);
+    let mut iter = SliceIter::with_options(input.as_bytes(), options);
+    assert_eq!(
+        iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap().len(),
+        8
+    );
+    assert_eq!(iter.warnings(), [Warning::NewTable { line: 4 }]);
+}
+
+#[test]
+fn q_value() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_bytes!("tests/multi");
+    let dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    let n = dataset.get(0, 1).unwrap();
+    let h = dataset.get(1, 0).unwrap();
+
+    let q = dataset.q_value(&[(0, 1)], &[(1, 0)]).unwrap();
+    assert!((q.mean - (n.mass_excess.mean - h.mass_excess.mean)).abs() < 1e-9);
+    assert!(q.uncertainty >= n.mass_excess.uncertainty);
+    assert!(!q.is_estimated);
+
+    // A isn't conserved
+    assert_eq!(dataset.q_value(&[(0, 1)], &[]), None);
+    // not in the data
+    assert_eq!(dataset.q_value(&[(50, 70)], &[(50, 70)]), None);
+    // the same on both sides
+    assert!(dataset.q_value(&[(1, 1)], &[(1, 1)]).unwrap().mean.abs() < 1e-9);
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi() {
+    use crate::ffi::*;
+    use std::{
+        ffi::{CStr, CString},
+        mem::MaybeUninit,
+    };
+
+    let path = CString::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/multi")).unwrap();
+    unsafe {
+        let table = ame2020_parse_file(path.as_ptr());
+        assert!(!table.is_null());
+        assert_eq!(ame2020_len(table), 7);
+        assert!(ame2020_get(table, 50, 50).is_null());
+        assert!(ame2020_get_index(table, 7).is_null());
+
+        let nuclide = ame2020_get(table, 0, 1);
+        assert_eq!(ame2020_nuclide_a(nuclide), 1);
+        assert_eq!(CStr::from_ptr(ame2020_nuclide_element(nuclide)), c"n");
+        assert!(ame2020_nuclide_origin(nuclide).is_null());
+        assert_eq!(CStr::from_ptr(ame2020_nuclide_decay_mode(nuclide)), c"B-");
+        let mass_excess = ame2020_nuclide_mass_excess(nuclide);
+        assert!((mass_excess.mean - 8_071.318_06).abs() < 1e-9);
+        let mut beta = MaybeUninit::uninit();
+        assert!(ame2020_nuclide_beta_decay_energy(
+            nuclide,
+            beta.as_mut_ptr()
+        ));
+        assert!((beta.assume_init().mean - 782.347).abs() < 1e-9);
+
+        let li3 = ame2020_get_index(table, 5);
+        assert_eq!(ame2020_nuclide_z(li3), 3);
+        assert_eq!(CStr::from_ptr(ame2020_nuclide_origin(li3)), c"-pp");
+        ame2020_free(table);
+
+        let missing = CString::new("/nonexistent/mass.mas20").unwrap();
+        assert!(ame2020_parse_file(missing.as_ptr()).is_null());
+        assert!(!ame2020_last_error().is_null());
+    }
+}
+
+#[test]
+fn separation_energy() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_bytes!("tests/multi");
+    let dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    let me = |z, n| dataset.get(z, n).unwrap().mass_excess.clone();
+
+    // the binding energy of the deuteron
+    let deuteron = dataset.separation_energy(1, 1, 0, 1).unwrap();
+    assert!((deuteron.mean - (me(1, 0).mean + me(0, 1).mean - me(1, 1).mean)).abs() < 1e-9);
+    assert!((deuteron.mean - 2224.566).abs() < 1e-2);
+
+    // the two neutrons are the same nuclide, so their uncertainties add linearly
+    let two = dataset
+        .q_value(&[(0, 1), (0, 1)], &[(0, 1), (0, 1)])
+        .unwrap();
+    assert!(two.mean.abs() < 1e-9 && two.uncertainty.abs() < 1e-12);
+    let triton = dataset.separation_energy(1, 2, 0, 2).unwrap();
+    let unc = (me(1, 2).uncertainty.powi(2)
+        + me(1, 0).uncertainty.powi(2)
+        + (2.0 * me(0, 1).uncertainty).powi(2))
+    .sqrt();
+    assert!((triton.uncertainty - unc).abs() < 1e-12);
+
+    assert_eq!(dataset.separation_energy(1, 1, 0, 2), None);
+    assert_eq!(dataset.separation_energy(1, 1, 2, 0), None);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn grid() {
+    use crate::dataset::{Dataset, Duplicates};
+
+    let multi = include_bytes!("tests/multi");
+    let dataset = Dataset::from_slice(multi, Duplicates::Error).unwrap();
+    let grid = dataset.grid(|nuc| Some(nuc.mass_excess.mean));
+    let n_max = dataset.iter().map(|nuc| nuc.n).max().unwrap() as usize;
+    let z_max = dataset.iter().map(|nuc| nuc.z).max().unwrap() as usize;
+    assert_eq!(grid.dim(), (n_max + 1, z_max + 1));
+    assert_eq!(grid.iter().filter(|x| !x.is_nan()).count(), dataset.len());
+    for nuc in &dataset {
+        assert!((grid[[nuc.n as usize, nuc.z as usize]] - nuc.mass_excess.mean).abs() < 1e-9);
+    }
+
+    let s_2n = dataset.grid(|nuc| Some(dataset.separation_energy(nuc.z, nuc.n, 0, 2)?.mean));
+    assert!(s_2n[[0, 1]].is_nan());
+    assert!(!s_2n[[2, 1]].is_nan());
+
+    assert_eq!(Dataset::default().grid(|_| Some(0.0)).dim(), (0, 0));
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz() {
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let mut buf = Cursor::new(Vec::new());
+    crate::npz::write(&mut buf, &data).unwrap();
+
+    let mut archive = ZipArchive::new(buf).unwrap();
+    assert_eq!(archive.len(), 18);
+    let mut array = |name: &str| {
+        let mut npy = Vec::new();
+        archive
+            .by_name(&format!("{name}.npy"))
+            .unwrap()
+            .read_to_end(&mut npy)
+            .unwrap();
+        assert_eq!(&npy[..8], b"\x93NUMPY\x01\x00");
+        let header_len = usize::from(u16::from_le_bytes([npy[8], npy[9]]));
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = String::from_utf8(npy[10..10 + header_len].to_vec()).unwrap();
+        (header, npy[10 + header_len..].to_vec())
+    };
+
+    let (header, z) = array("z");
+    assert!(header.starts_with("{'descr': '<u4', 'fortran_order': False, 'shape': (7,), }"));
+    assert!(header.ends_with('\n'));
+    assert_eq!(z.len(), 7 * 4);
+    assert_eq!(z[4..8], 1u32.to_le_bytes());
+
+    let (header, element) = array("element");
+    assert!(header.contains("'<U3'"));
+    assert_eq!(element[..12], [b'n', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let (_, mass_excess) = array("mass_excess");
+    let mean = f64::from_le_bytes(mass_excess[..8].try_into().unwrap());
+    assert!((mean - data[0].mass_excess.mean).abs() < 1e-9);
+    let (_, estimated) = array("atomic_mass_is_estimated");
+    let expected: Vec<u8> = data
+        .iter()
+        .map(|nuc| u8::from(nuc.atomic_mass.is_estimated))
+        .collect();
+    assert_eq!(estimated, expected);
+}
+
+#[cfg(feature = "mat")]
+#[test]
+fn mat() {
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let mut buf = Vec::new();
+    crate::mat::write(&mut buf, "ame2020", &data).unwrap();
+
+    assert!(buf.starts_with(b"MATLAB 5.0 MAT-file"));
+    assert_eq!(&buf[124..128], b"\x00\x01IM");
+    // one miMATRIX element, which covers the rest of the file
+    let u32_at = |i: usize| u32::from_le_bytes(buf[i..i + 4].try_into().unwrap());
+    assert_eq!(u32_at(128), 14);
+    assert_eq!(u32_at(132) as usize, buf.len() - 136);
+    assert_eq!(buf.len() % 8, 0);
+    // the flags are for a struct, the dimensions are 1×7, and the name follows
+    assert_eq!(u32_at(144), 2);
+    assert_eq!((u32_at(160), u32_at(164)), (1, 7));
+    assert_eq!(&buf[176..183], b"ame2020");
+
+    let mut empty = Vec::new();
+    crate::mat::write(&mut empty, "ame2020", &[]).unwrap();
+    assert!(empty.len() < buf.len());
+}
+
+#[cfg(feature = "plot")]
+#[test]
+fn plot() {
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let quantity = |nuc: &crate::Nuclide| Some(nuc.mass_excess.mean);
+
+    let (width, height) = (200, 100);
+    let buf = crate::plot::render((width, height), &data, quantity).unwrap();
+    assert_eq!(buf.len(), 200 * 100 * 3);
+    // the corners are white, and some of the chart isn't
+    assert_eq!(buf[..3], [255, 255, 255]);
+    assert!(buf.chunks(3).any(|pixel| pixel != [255, 255, 255]));
+
+    let path = std::env::temp_dir().join("ame2020-plot-test.png");
+    crate::plot::save(&path, (width, height), &data, quantity).unwrap();
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn svg() {
+    use crate::svg::{write, ColorScale, SvgOptions};
+
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let options = SvgOptions::new().cell_size(5);
+    let mut buf = Vec::new();
+    write(&mut buf, &data, |nuc| Some(nuc.mass_excess.mean), &options).unwrap();
+    let svg = String::from_utf8(buf).unwrap();
+    let n_max = data.iter().map(|nuc| nuc.n).max().unwrap();
+    let z_max = data.iter().map(|nuc| nuc.z).max().unwrap();
+    assert!(svg.starts_with(&format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}""#,
+        (n_max + 1) * 5,
+        (z_max + 1) * 5,
+    )));
+    assert_eq!(svg.matches("<rect").count(), data.len());
+    assert!(svg.contains("Li-3: 28667 ± 2000 keV, estimated</title>"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    // without values, the squares are gray
+    let mut buf = Vec::new();
+    write(&mut buf, &data, |_| None, &options).unwrap();
+    let svg = String::from_utf8(buf).unwrap();
+    assert_eq!(svg.matches(r##"fill="#dddddd""##).count(), data.len());
+
+    assert_eq!(ColorScale::Grayscale.color(0.0), [0, 0, 0]);
+    assert_eq!(ColorScale::Grayscale.color(1.0), [255, 255, 255]);
+    assert_eq!(ColorScale::Viridis.color(1.0), [253, 231, 37]);
+    let linear = ColorScale::Linear {
+        low: [0, 0, 0],
+        high: [200, 100, 0],
+    };
+    assert_eq!(linear.color(0.5), [100, 50, 0]);
+}
+
+#[cfg(feature = "evcxr")]
+#[test]
+fn evcxr() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        evcxr::{Table, MAX_ROWS},
+    };
+
+    let multi = include_str!("tests/multi");
+    let dataset = Dataset::from_slice(multi.as_bytes(), Duplicates::Error).unwrap();
+    let html = dataset.to_html();
+    assert!(html.starts_with("<table><tr><th>Z</th>"));
+    assert_eq!(html.matches("<tr>").count(), dataset.len() + 1);
+    assert!(html
+        .contains("<td>3</td><td>0</td><td>3</td><td>Li</td><td>-pp</td><td>28667 ± 2000#</td>"));
+
+    let html = dataset.get(0, 1).unwrap().to_html();
+    assert_eq!(html.matches("<tr>").count(), 2);
+
+    let table: Table = dataset.iter().filter(|nuc| nuc.z == 1).collect();
+    assert_eq!(table.to_html().matches("<td>H</td>").count(), 4);
+
+    // long tables are cut off
+    let nuc = dataset.get(0, 1).unwrap();
+    let table: Table = std::iter::repeat_n(nuc, MAX_ROWS + 5).collect();
+    let html = table.to_html();
+    assert_eq!(html.matches("<tr>").count(), MAX_ROWS + 2);
+    assert!(html.contains("… 5 more"));
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn strategy(nuc in proptest::prelude::any::<crate::Nuclide>()) {
+        proptest::prop_assert_eq!(crate::element::symbol(nuc.z), Some(&*nuc.element));
+        proptest::prop_assert!(crate::validate::check(&nuc).is_empty());
+    }
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv() {
+    use crate::{ArchivedNuclide, Nuclide};
+    use rkyv::{rancor::Error, vec::ArchivedVec};
+
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let bytes = rkyv::to_bytes::<Error>(&data).unwrap();
+    let archived = rkyv::access::<ArchivedVec<ArchivedNuclide>, Error>(&bytes).unwrap();
+    assert_eq!(archived.len(), data.len());
+    let li3 = &archived[5];
+    assert_eq!(li3.element, "Li");
+    assert_eq!(
+        li3.origin
+            .as_ref()
+            .map(rkyv::string::ArchivedString::as_str),
+        Some("-pp")
+    );
+    assert!(li3.mass_excess.is_estimated);
+    assert!(li3.beta_decay_energy.is_none());
+
+    let deserialized = rkyv::deserialize::<Vec<Nuclide>, Error>(archived).unwrap();
+    assert_eq!(deserialized, data);
+
+    // a corrupted archive is rejected
+    let mut bytes = bytes.to_vec();
+    let len = bytes.len();
+    bytes.truncate(len - 1);
+    assert!(rkyv::access::<ArchivedVec<ArchivedNuclide>, Error>(&bytes).is_err());
+}
+
+#[cfg(feature = "datafusion")]
+#[tokio::test]
+async fn sql() {
+    use datafusion::{
+        arrow::array::{AsArray, RecordBatch},
+        arrow::datatypes::UInt32Type,
+        prelude::SessionContext,
+    };
+
+    let multi = include_str!("tests/multi");
+    let data = crate::parse_str(multi).unwrap();
+    let ctx = SessionContext::new();
+    crate::sql::register(&ctx, "nuclides", &data).unwrap();
+    let batches: Vec<RecordBatch> = ctx
+        .sql("SELECT n FROM nuclides WHERE z = 1 AND is_estimated = false ORDER BY n")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let n: Vec<u32> = batches
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .column(0)
+                .as_primitive::<UInt32Type>()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    let expected: Vec<u32> = {
+        let mut expected: Vec<u32> = data
+            .iter()
+            .filter(|nuc| {
+                nuc.z == 1
+                    && !nuc.mass_excess.is_estimated
+                    && !nuc.atomic_mass.is_estimated
+                    && !nuc
+                        .beta_decay_energy
+                        .as_ref()
+                        .is_some_and(|v| v.is_estimated)
+                    && !nuc
+                        .binding_energy_per_a
+                        .as_ref()
+                        .is_some_and(|v| v.is_estimated)
+            })
+            .map(|nuc| nuc.n)
+            .collect();
+        expected.sort_unstable();
+        expected
+    };
+    assert_eq!(n, expected);
+
+    // missing values are NULL
+    let batches: Vec<RecordBatch> = ctx
+        .sql("SELECT count(*) FROM nuclides WHERE beta_decay_energy IS NULL")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let count = batches[0]
+        .column(0)
+        .as_primitive::<datafusion::arrow::datatypes::Int64Type>()
+        .value(0);
+    let expected = data
+        .iter()
+        .filter(|nuc| nuc.beta_decay_energy.is_none())
+        .count();
+    assert_eq!(count, i64::try_from(expected).unwrap());
+}
+
+#[test]
+fn livechart() {
+    use crate::{livechart::read, DecayMode};
+
+    let csv = include_str!("tests/livechart.csv");
+    let data = read(csv.as_bytes()).unwrap();
+    let multi = crate::parse_str(include_str!("tests/multi")).unwrap();
+    // the nuclide without a mass excess is skipped
+    assert_eq!(data.len(), 4);
+    for nuc in &data {
+        let expected = multi
+            .iter()
+            .find(|expected| (expected.z, expected.n) == (nuc.z, nuc.n))
+            .unwrap();
+        assert_eq!(nuc.element, expected.element);
+        assert!((nuc.mass_excess.mean - expected.mass_excess.mean).abs() < 1e-9);
+        assert!((nuc.atomic_mass.mean - expected.atomic_mass.mean).abs() < 1e-12);
+        assert!((nuc.atomic_mass.uncertainty - expected.atomic_mass.uncertainty).abs() < 1e-15);
+        assert_eq!(
+            nuc.mass_excess.is_estimated,
+            expected.mass_excess.is_estimated
+        );
+        assert_eq!(
+            nuc.beta_decay_energy.as_ref().map(|value| value.mean),
+            expected.beta_decay_energy.as_ref().map(|value| value.mean),
+        );
+    }
+    assert_eq!(data[0].decay_mode, Some(DecayMode::BetaMinus));
+    assert_eq!(data[1].decay_mode, None);
+    assert!(data[3].binding_energy_per_a.as_ref().unwrap().is_estimated);
+
+    assert_eq!(
+        read("z,n\n".as_bytes()),
+        Err(AmeError::MissingColumn("massexcess"))
+    );
+    let bad = csv.replace("14949.8109", "x");
+    let err = read(bad.as_bytes()).unwrap_err();
+    assert_eq!(
+        err,
+        AmeError::InvalidColumn {
+            line: 4,
+            column: "massexcess"
+        }
+    );
+    assert_eq!(err.line(), Some(4));
+}
+
+#[test]
+fn abundance() {
+    use crate::abundance::{join, read};
+
+    let isotopes = read(include_str!("tests/nist").as_bytes()).unwrap();
+    assert_eq!(isotopes.len(), 8);
+    let h1 = &isotopes[0];
+    assert_eq!((h1.z, h1.a, h1.n()), (1, 1, 0));
+    assert!((h1.relative_atomic_mass.mean - 1.007_825_032_23).abs() < 1e-15);
+    assert!((h1.relative_atomic_mass.uncertainty - 9e-11).abs() < 1e-20);
+    let composition = h1.isotopic_composition.as_ref().unwrap();
+    assert!((composition.mean - 0.999_885).abs() < 1e-12);
+    assert!((composition.uncertainty - 0.000_070).abs() < 1e-12);
+    assert!(isotopes[2].isotopic_composition.is_none());
+    assert!(!h1.relative_atomic_mass.is_estimated);
+    // the mass of H-7 is estimated
+    let h7 = &isotopes[7];
+    assert_eq!((h7.z, h7.a), (1, 7));
+    assert!((h7.relative_atomic_mass.mean - 7.052_750).abs() < 1e-12);
+    assert!((h7.relative_atomic_mass.uncertainty - 0.001_080).abs() < 1e-12);
+    assert!(h7.relative_atomic_mass.is_estimated);
+
+    // the `#` may also be after the parentheses, and a value without them is exact
+    let be9 = concat!(
+        "Atomic Number = 4\n",
+        "Mass Number = 9\n",
+        "Relative Atomic Mass = 9.012183065(82)#\n",
+        "Isotopic Composition = 1\n",
+    );
+    let be9 = read(be9.as_bytes()).unwrap();
+    assert!(be9[0].relative_atomic_mass.is_estimated);
+    assert!((be9[0].relative_atomic_mass.uncertainty - 8.2e-8).abs() < 1e-18);
+    let composition = be9[0].isotopic_composition.as_ref().unwrap();
+    assert_eq!((composition.mean, composition.uncertainty), (1.0, 0.0));
+    assert!(!composition.is_estimated);
+
+    let multi = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let joined = join(&multi, &isotopes);
+    assert_eq!(joined.len(), multi.len());
+    let natural: Vec<(u32, u32)> = joined
+        .iter()
+        .filter(|(_, abundance)| abundance.is_some())
+        .map(|(nuc, _)| (nuc.z, nuc.n))
+        .collect();
+    assert_eq!(natural, [(1, 0), (1, 1), (2, 1)]);
+
+    let err = read("Atomic Number = 1\nMass Number = x\n".as_bytes()).unwrap_err();
+    assert_eq!(
+        err,
+        AmeError::InvalidColumn {
+            line: 1,
+            column: "Mass Number"
+        }
+    );
+}
+
+#[test]
+fn atomic_weights() {
+    use crate::abundance::{atomic_weights, join, read};
+
+    let isotopes = read(include_str!("tests/nist").as_bytes()).unwrap();
+    let multi = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let weights = atomic_weights(&join(&multi, &isotopes));
+    // helium has only He-3 in the fixture, so its weight is that mass
+    assert_eq!(weights.keys().copied().collect::<Vec<_>>(), [1, 2]);
+    let hydrogen = &weights[&1];
+    // the NIST standard atomic weight of hydrogen is between 1.00784 and 1.00811
+    assert!((hydrogen.mean - 1.007_941).abs() < 1e-6);
+    // the uncertainties of the abundances dominate
+    let (m1, m2) = (1.007_825_031_898, 2.014_101_777_844);
+    let expected = f64::hypot(
+        (m1 - hydrogen.mean) * 0.000_070,
+        (m2 - hydrogen.mean) * 0.000_070,
+    );
+    assert!((hydrogen.uncertainty - expected).abs() < 1e-9);
+    assert!(!hydrogen.is_estimated);
+    assert!((weights[&2].mean - multi[4].atomic_mass.mean).abs() < 1e-12);
+}
+
+#[test]
+fn nuclide_name() {
+    use crate::element::nuclide;
+
+    assert_eq!(nuclide("26Al"), Some((13, 13)));
+    assert_eq!(nuclide("Al26"), Some((13, 13)));
+    assert_eq!(nuclide("Al-26"), Some((13, 13)));
+    assert_eq!(nuclide("3Li"), Some((3, 0)));
+    assert_eq!(nuclide("1n"), Some((0, 1)));
+    // the symbol is case sensitive, and A can't be less than Z
+    assert_eq!(nuclide("26al"), None);
+    assert_eq!(nuclide("2Li"), None);
+    assert_eq!(nuclide("26"), None);
+    assert_eq!(nuclide("Al-"), None);
+    assert_eq!(nuclide(""), None);
+}
+
+#[test]
+fn reaction() {
+    use crate::reaction::Reaction;
+
+    let reaction = |initial: &[(u32, u32)], products: &[(u32, u32)]| {
+        Some(Reaction {
+            initial: initial.to_vec(),
+            products: products.to_vec(),
+            positrons: 0,
+        })
+    };
+    assert_eq!(
+        Reaction::parse("3He(n,p)3H"),
+        reaction(&[(2, 1), (0, 1)], &[(1, 0), (1, 2)])
+    );
+    // counts, several particles, nuclides, and γ-rays
+    assert_eq!(
+        Reaction::parse("9Be(p,2n)8B"),
+        reaction(&[(4, 5), (1, 0)], &[(0, 1), (0, 1), (5, 3)])
+    );
+    assert_eq!(
+        Reaction::parse("7Li(d, p+n)"),
+        reaction(&[(3, 4), (1, 1)], &[(1, 0), (0, 1), (3, 4)])
+    );
+    assert_eq!(
+        Reaction::parse("6Li(3He,pa)"),
+        reaction(&[(3, 3), (2, 1)], &[(1, 0), (2, 2), (2, 2)])
+    );
+    assert_eq!(
+        Reaction::parse("1H(n,γ)2H"),
+        reaction(&[(1, 0), (0, 1)], &[(1, 1)])
+    );
+    // Z isn't conserved, or it isn't a reaction
+    assert_eq!(Reaction::parse("9Be(p,n)9Be"), None);
+    assert_eq!(Reaction::parse("1H(n,2p)"), None);
+    assert_eq!(Reaction::parse("9Be(x,n)"), None);
+    assert_eq!(Reaction::parse("9Be"), None);
+    assert_eq!(Reaction::parse("9Be(p2,n)"), None);
+    // the count is limited, and Z and N don't overflow
+    assert_eq!(Reaction::parse("12C(255n,g)").unwrap().products, [(6, 261)]);
+    assert_eq!(Reaction::parse("12C(4000000000n,g)"), None);
+    assert_eq!(Reaction::parse("4000000000He(4000000000He,g)"), None);
+}
+
+#[test]
+fn reaction_q_value() {
+    use crate::{
+        constants::ELECTRON_MASS,
+        dataset::{Dataset, Duplicates},
+        reaction::Reaction,
+        DecayMode,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let q = |reaction: &Reaction| dataset.reaction_q_value(reaction).unwrap();
+    let reaction = q(&Reaction::parse("3He(n,p)3H").unwrap());
+    let expected = 8_071.318_06 + 14_931.218_88 - 7_288.971_064 - 14_949.810_9;
+    assert!((reaction.mean - expected).abs() < 1e-6);
+    let tritium = q(&Reaction::decay(1, 2, DecayMode::BetaMinus).unwrap());
+    assert!((tritium.mean - 18.59202).abs() < 1e-5);
+    // the positron and the electron the atom loses are taken from the Q-value
+    let capture = q(&Reaction::decay(3, 0, DecayMode::ElectronCapture).unwrap());
+    let positron = q(&Reaction::decay(3, 0, DecayMode::BetaPlus).unwrap());
+    assert!((capture.mean - positron.mean - 2.0 * ELECTRON_MASS).abs() < 1e-6);
+    assert!(positron.is_estimated);
+    assert_eq!(Reaction::decay(1, 0, DecayMode::Alpha), None);
+    assert_eq!(Reaction::decay(u32::MAX, 1, DecayMode::BetaMinus), None);
+    assert_eq!(dataset.q_value(&[(u32::MAX, 1)], &[(0, 1)]), None);
+}
+
+#[test]
+fn filter() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        filter::{Filter, FilterError},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let filter = |expr: &str| -> Vec<(u32, u32)> {
+        let filter: Filter = expr.parse().unwrap();
+        filter.apply(&dataset).map(|nuc| (nuc.z, nuc.n)).collect()
+    };
+    assert_eq!(filter("z == 1"), [(1, 0), (1, 1), (1, 2), (1, 3)]);
+    // H-1 has no S_n, so the comparison is false
+    assert_eq!(filter("z == 1 && sn < 7000"), [(1, 1), (1, 2), (1, 3)]);
+    assert_eq!(filter("estimated"), [(2, 1), (3, 0)]);
+    assert_eq!(filter("!estimated && a == 3"), [(1, 2)]);
+    assert_eq!(filter("a == 3 && (z == 3 || n == 0)"), [(3, 0)]);
+    assert_eq!(filter("mass_excess_uncertainty > 1000"), [(3, 0)]);
+    assert_eq!(filter("1 + 2 * 3 == 7 && -z < 0 && z / 2 > 1"), [(3, 0)]);
+    assert_eq!(filter("estimated == false && z > 1"), []);
+
+    let error = |expr: &str| Filter::parse(expr).unwrap_err();
+    let expected = |position, expected| FilterError::Type { position, expected };
+    let unexpected = |position, found: &str| FilterError::Unexpected {
+        position,
+        found: found.to_string(),
+    };
+    assert_eq!(error("z + 1"), expected(0, "condition"));
+    assert_eq!(error("!z"), expected(1, "condition"));
+    assert_eq!(error("z > estimated"), expected(4, "number"));
+    assert_eq!(error("z == estimated"), expected(5, "number"));
+    assert_eq!(
+        error("foo > 1"),
+        FilterError::UnknownName {
+            position: 0,
+            name: "foo".to_string()
+        }
+    );
+    assert_eq!(error("z >"), unexpected(3, "end"));
+    assert_eq!(error("(z > 1"), unexpected(6, "end"));
+    assert_eq!(error("z > 1)"), unexpected(5, "`)`"));
+    assert_eq!(error("z @ 1"), unexpected(2, "`@`"));
+    assert_eq!(error("z < 1 < 2"), unexpected(6, "operator `<`"));
+}
+
+#[test]
+fn filter_region() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        filter::{Filter, Region},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let filter = |filter: Filter| -> Vec<(u32, u32)> {
+        filter.apply(&dataset).map(|nuc| (nuc.z, nuc.n)).collect()
+    };
+    for region in Region::ALL {
+        Filter::parse(region.expression()).unwrap();
+    }
+    assert_eq!(filter(Region::Light.into()).len(), 7);
+    assert_eq!(filter(Region::SdShell.into()), []);
+    // H-4 has N and Z 2 apart, and Li-3 has them 3 apart
+    assert_eq!(
+        filter(Filter::region(Region::NearNEqualsZ)),
+        [(0, 1), (1, 0), (1, 1), (1, 2), (2, 1), (1, 3)]
+    );
+    let estimated: Filter = "estimated".parse().unwrap();
+    assert_eq!(
+        filter(Filter::region(Region::Light).and(!estimated.clone())),
+        [(0, 1), (1, 0), (1, 1), (1, 2), (1, 3)]
+    );
+    assert_eq!(
+        filter(Filter::region(Region::Superheavy).or(estimated)),
+        [(2, 1), (3, 0)]
+    );
+}
+
+#[test]
+fn quantity() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        filter::{FilterError, Quantity},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let h2 = dataset.get(1, 1).unwrap();
+    let h1 = dataset.get(1, 0).unwrap();
+    let sn: Quantity = "sn".parse().unwrap();
+    assert!((sn.eval(&dataset, h2).unwrap() - 2_224.566_23).abs() < 1e-5);
+    // H-1 has no S_n
+    assert_eq!(sn.eval(&dataset, h1), None);
+    let a: Quantity = "(z + n) * 2".parse().unwrap();
+    assert_eq!(a.eval(&dataset, h2), Some(4.0));
+    // division by zero isn't finite
+    assert_eq!(
+        "1 / n".parse::<Quantity>().unwrap().eval(&dataset, h1),
+        None
+    );
+    assert_eq!(
+        Quantity::parse("z > 1").unwrap_err(),
+        FilterError::Type {
+            position: 0,
+            expected: "number"
+        }
+    );
+}
+
+#[test]
+fn diff() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        diff::diff,
+    };
+
+    let source = include_str!("tests/multi");
+    let old = Dataset::from_slice(source.as_bytes(), Duplicates::Error).unwrap();
+    // without Li-3, with H-3 0.5 keV heavier and more uncertain, and with H-4 copied to H-5
+    let new = source
+        .lines()
+        .filter(|line| !line.contains(" Li "))
+        .map(|line| line.replace("14949.81090     0.00008", "14950.31090     0.50000"))
+        .chain(
+            source
+                .lines()
+                .filter(|line| line.contains(" H    -n "))
+                .map(|line| line.replace("0  2    3    1    4", "0  3    4    1    5")),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+    let new = Dataset::from_slice(new.as_bytes(), Duplicates::Error).unwrap();
+    let diff = diff(&old, &new);
+    let z_n = |nuclides: &[&Nuclide]| {
+        nuclides
+            .iter()
+            .map(|nuc| (nuc.z, nuc.n))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(z_n(&diff.added), [(1, 4)]);
+    assert_eq!(z_n(&diff.removed), [(3, 0)]);
+    assert_eq!(diff.changed.len(), 1);
+    let change = &diff.changed[0];
+    assert_eq!((change.old.z, change.old.n), (1, 2));
+    // the binding energy has the same mean, so only the mass excess changed
+    assert_eq!(change.deltas.len(), 1);
+    let delta = change.delta(Field::MassExcess).unwrap();
+    assert!((delta.difference.mean - 0.5).abs() < 1e-9);
+    assert!((delta.sigma - 1.0).abs() < 1e-3);
+    assert_eq!(change.delta(Field::AtomicMass), None);
+    assert_eq!(crate::diff::diff(&old, &old), crate::diff::Diff::default());
+}
+
+#[test]
+fn summary() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        summary::{summary, Counts},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let summary = summary(&dataset);
+    let counts = |total, experimental, estimated| Counts {
+        total,
+        experimental,
+        estimated,
+    };
+    assert_eq!(summary.counts, counts(7, 5, 2));
+    assert_eq!(summary.elements.len(), 4);
+    // H-1, H-2, H-3, and H-4
+    assert_eq!(summary.elements[&1], counts(4, 4, 0));
+    assert_eq!(summary.elements[&2], counts(1, 0, 1));
+    assert_eq!(
+        (summary.z, summary.n, summary.a),
+        (Some(0..=3), Some(0..=3), Some(1..=4))
+    );
+    // Li-3 is the only estimated mass excess
+    let uncertainty = summary.uncertainty.unwrap();
+    assert_eq!((uncertainty.min, uncertainty.max), (0.000_013, 100.0));
+    // between He-3 and H-3
+    assert!((uncertainty.median - 0.000_07).abs() < 1e-12);
+    assert_eq!(
+        crate::summary::summary(&[]),
+        crate::summary::Summary::default()
+    );
+}
+
+#[test]
+fn histogram() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        histogram::{histogram, Histogram},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let histogram = histogram(
+        &dataset,
+        |nuc| nuc.binding_energy_per_a.as_ref().map(|value| value.mean),
+        vec![0.0, 1_000.0, 2_000.0, 3_000.0],
+    )
+    .unwrap();
+    // n and H-1 are 0, which is on the first edge, and Li-3 is negative
+    assert_eq!(histogram.counts, [2, 2, 2]);
+    assert_eq!(histogram.underflow, 1);
+    assert_eq!(histogram.overflow, 0);
+    assert_eq!(histogram.centers(), [500.0, 1_500.0, 2_500.0]);
+
+    let mut histogram = Histogram::uniform(0.0, 1.0, 4).unwrap();
+    histogram.extend([0.0, 0.25, 0.5, 1.0, 1.5, f64::NAN]);
+    // the last edge is in the last bin
+    assert_eq!(histogram.counts, [1, 1, 1, 1]);
+    assert_eq!((histogram.overflow, histogram.nan), (1, 1));
+    assert_eq!(histogram.edges(), [0.0, 0.25, 0.5, 0.75, 1.0]);
+
+    assert_eq!(Histogram::new(vec![1.0]), None);
+    assert_eq!(Histogram::new(vec![1.0, 1.0]), None);
+    assert_eq!(Histogram::uniform(0.0, 1.0, 0), None);
+    assert_eq!(Histogram::uniform(0.0, f64::INFINITY, 1), None);
+}
+
+#[test]
+fn theory() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        theory::compare,
+    };
+    use std::collections::HashMap;
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    // 1 σ above the neutron, 2 σ below H-3, and 1 MeV above Li-3
+    let theory = HashMap::from([
+        ((0, 1), 8_071.318_5),
+        ((1, 2), 14_949.810_74),
+        ((3, 0), 27_667.0),
+    ]);
+    let comparison = compare(&dataset, |z, n| theory.get(&(z, n)).copied(), true);
+    let residuals: Vec<_> = comparison
+        .residuals
+        .iter()
+        .map(|r| (r.nuclide.z, r.nuclide.n))
+        .collect();
+    assert_eq!(residuals, [(0, 1), (1, 2)]);
+    assert!((comparison.residuals[0].sigma + 1.0).abs() < 1e-6);
+    assert!((comparison.residuals[1].sigma - 2.0).abs() < 1e-6);
+    assert!((comparison.chi_squared - 5.0).abs() < 1e-6);
+
+    let comparison = compare(&dataset, |z, n| theory.get(&(z, n)).copied(), false);
+    assert_eq!(comparison.residuals.len(), 3);
+    assert!((comparison.mean.unwrap() - 1_000.0 / 3.0).abs() < 1e-3);
+    assert!((comparison.rms.unwrap() - (1e6_f64 / 3.0).sqrt()).abs() < 1e-3);
+
+    let comparison = compare(&dataset, |_, _| None, false);
+    assert_eq!((comparison.mean, comparison.rms), (None, None));
+}
+
+#[test]
+fn model() {
+    use crate::model::{self, Layout};
+
+    // H-2, in each of the layouts, with a header and the columns that aren't read as 0
+    let frdm = "Z N A e2 e3 e4 e6 b2 b3 b4 b6 Esp Emic Ebind Mth\n\
+        1 1 2 0 0 0 0 0 0 0 0 0 0 0 13.135722895\n";
+    let hfb = "# Z A bet2 bet4 Rch Edef Mth\n1 2 0 0 0 0 13.135722895\n";
+    let ws4 = "A Z b2 b4 b6 Esh Mexp Mth\n2 1 0 0 0 0 0 13.135722895\n";
+    for (table, layout) in [
+        (frdm, Layout::FRDM2012),
+        (hfb, Layout::HFB),
+        (ws4, Layout::WS4),
+    ] {
+        let data = model::read(table.as_bytes(), &layout).unwrap();
+        assert_eq!(data.len(), 1);
+        let h2 = &data[0];
+        assert_eq!((h2.z, h2.n, h2.element.as_str()), (1, 1, "H"));
+        assert!((h2.mass_excess.mean - 13_135.722_895).abs() < 1e-6);
+        assert!(h2.mass_excess.is_estimated);
+        // the same as in the evaluation
+        let binding_energy_per_a = h2.binding_energy_per_a.as_ref().unwrap();
+        assert!((binding_energy_per_a.mean - 1_112.283_1).abs() < 1e-4);
+        assert!((h2.atomic_mass.mean - 2.014_101_778).abs() < 1e-9);
+    }
+
+    // Z = 119 has no symbol
+    let data = model::read("119 300 0 0 0 0 0\n".as_bytes(), &Layout::HFB).unwrap();
+    assert!(data.is_empty());
+    assert_eq!(
+        model::read("1 2 0 0 0 0\n".as_bytes(), &Layout::HFB),
+        Err(AmeError::InvalidColumn {
+            line: 1,
+            column: "mass excess"
+        })
+    );
+    assert_eq!(
+        model::read("2 1 0 0 0 0 0\n".as_bytes(), &Layout::HFB),
+        Err(AmeError::InvalidColumn {
+            line: 1,
+            column: "A"
+        })
+    );
+}
+
+#[test]
+fn surface() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        surface::{Axis, Interpolation},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let mass_excess = |z, n| dataset.get(z, n).unwrap().mass_excess.mean;
+    let close = |value: Option<Value>, expected: f64| {
+        let value = value.unwrap();
+        assert!(
+            (value.mean - expected).abs() < 1e-6,
+            "{value:?} != {expected}"
+        );
+        value
+    };
+
+    // at a nuclide, only the nuclide is needed
+    let h3 = close(
+        dataset.interpolate(1.0, 2.0, Interpolation::Bicubic),
+        mass_excess(1, 2),
+    );
+    assert!((h3.uncertainty - 0.000_08).abs() < 1e-12);
+    close(
+        dataset.interpolate(1.0, 1.5, Interpolation::Bilinear),
+        f64::midpoint(mass_excess(1, 1), mass_excess(1, 2)),
+    );
+    // through H-1 to H-4, where H-4 is estimated
+    let bicubic = close(
+        dataset.interpolate(1.0, 1.5, Interpolation::Bicubic),
+        (9.0 * (mass_excess(1, 1) + mass_excess(1, 2)) - mass_excess(1, 0) - mass_excess(1, 3))
+            / 16.0,
+    );
+    assert!((bicubic.uncertainty - 100.0 / 16.0).abs() < 1e-6);
+    // He-2 isn't in the table
+    assert_eq!(dataset.interpolate(1.5, 0.5, Interpolation::Bilinear), None);
+    assert_eq!(
+        dataset.interpolate(-1.0, 0.5, Interpolation::Bilinear),
+        None
+    );
+
+    close(
+        dataset.derivative(1, 1, Axis::N),
+        (mass_excess(1, 2) - mass_excess(1, 0)) / 2.0,
+    );
+    close(
+        dataset.second_derivative(1, 2, Axis::N),
+        mass_excess(1, 3) - 2.0 * mass_excess(1, 2) + mass_excess(1, 1),
+    );
+    close(
+        dataset.derivative(1, 1, Axis::Z),
+        (mass_excess(2, 1) - mass_excess(0, 1)) / 2.0,
+    );
+    // there is no N = -1
+    assert_eq!(dataset.derivative(1, 0, Axis::N), None);
+}
+
+#[test]
+fn coverage() {
+    use crate::summary::{coverage, Coverage};
+
+    let source = include_str!("tests/multi");
+    // H-4 copied to H-5, with an estimated mass excess
+    let h5 = source
+        .lines()
+        .find(|line| line.contains(" H    -n "))
+        .unwrap()
+        .replace("0  2    3    1    4", "0  3    4    1    5")
+        .replace("24621.129     100.000", "32892#        100#   ");
+    let mut data = crate::parse_str(source).unwrap();
+    data.extend(crate::parse_str(&format!("1\n1\n{h5}")).unwrap());
+    let coverage = coverage(&data);
+    assert_eq!(coverage.len(), 4);
+    assert_eq!(
+        coverage[1],
+        Coverage {
+            z: 1,
+            experimental: 4,
+            estimated: 1,
+            known: Some(0..=3),
+            proton_rich: None,
+            neutron_rich: Some(4),
+        }
+    );
+    assert_eq!(coverage[2].known, Some(1..=1));
+    // Li-3 is the only isotope, and it is estimated
+    assert_eq!(
+        coverage[3],
+        Coverage {
+            z: 3,
+            experimental: 0,
+            estimated: 1,
+            ..Coverage::default()
+        }
+    );
+}
+
+#[test]
+fn builder() {
+    use crate::{
+        builder::{BuildError, NuclideBuilder},
+        constants::Constants,
+        DecayMode,
+    };
+
+    let value = |mean, uncertainty| Value {
+        mean,
+        uncertainty,
+        is_estimated: false,
+    };
+    // H-3, as in the evaluation
+    let multi = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let h3 = &multi[3];
+    let built = NuclideBuilder::new(1, 2)
+        .element("H")
+        .mass_excess(h3.mass_excess.clone())
+        .beta_decay_energy(DecayMode::BetaMinus, h3.beta_decay_energy.clone().unwrap())
+        .build()
+        .unwrap();
+    assert_eq!((built.element, built.origin), (h3.element, None));
+    assert_eq!(built.decay_mode, h3.decay_mode);
+    assert_eq!(built.beta_decay_energy, h3.beta_decay_energy);
+    let binding_energy_per_a = built.binding_energy_per_a.as_ref().unwrap();
+    let expected = h3.binding_energy_per_a.as_ref().unwrap();
+    assert!((binding_energy_per_a.mean - expected.mean).abs() < 1e-4);
+    assert!((built.atomic_mass.mean - h3.atomic_mass.mean).abs() < 1e-9);
+
+    // the values that are set aren't computed
+    let built = NuclideBuilder::new(1, 2)
+        .origin("-n")
+        .mass_excess(value(1.0, 0.5))
+        .binding_energy_per_a(value(2.0, 0.0))
+        .atomic_mass(value(3.0, 0.0))
+        .build()
+        .unwrap();
+    assert_eq!(built.origin.as_deref(), Some("-n"));
+    assert_eq!(
+        (
+            built.binding_energy_per_a.unwrap().mean,
+            built.atomic_mass.mean
+        ),
+        (2.0, 3.0)
+    );
+    let constants = Constants {
+        atomic_mass_unit: 1_000_000.0,
+        ..Constants::AME2020
+    };
+    let built = NuclideBuilder::new(0, 1)
+        .mass_excess(value(8_000.0, 0.0))
+        .constants(constants)
+        .build()
+        .unwrap();
+    assert!((built.atomic_mass.mean - 1.008).abs() < 1e-12);
+
+    let error = |builder: NuclideBuilder| builder.build().unwrap_err();
+    let h3 = || NuclideBuilder::new(1, 2).mass_excess(value(14_949.8, 0.1));
+    assert_eq!(
+        error(NuclideBuilder::new(1, 2)),
+        BuildError::MissingMassExcess
+    );
+    assert_eq!(
+        error(h3().element("He")),
+        BuildError::Element {
+            element: "He".to_string(),
+            z: 1
+        }
+    );
+    assert_eq!(
+        error(NuclideBuilder::new(119, 180).mass_excess(value(0.0, 0.0))),
+        BuildError::UnknownElement(119)
+    );
+    assert_eq!(
+        error(h3().origin("-nnnn")),
+        BuildError::Origin("-nnnn".to_string())
+    );
+    assert_eq!(
+        error(NuclideBuilder::new(1, 2).mass_excess(value(f64::NAN, 0.1))),
+        BuildError::NonFinite(Field::MassExcess)
+    );
+    assert_eq!(
+        error(NuclideBuilder::new(1, 2).mass_excess(value(1.0, -0.1))),
+        BuildError::NegativeUncertainty(Field::MassExcessUncertainty)
+    );
+    assert_eq!(
+        error(h3().atomic_mass(value(3.0, f64::INFINITY))),
+        BuildError::NonFinite(Field::AtomicMassUncertainty)
+    );
+}
+
+#[test]
+fn metadata() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        filter::Filter,
+        metadata::Metadata,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let mut tags: Metadata<Vec<&str>> = [((1, 2), vec!["measured"]), ((1, 0), vec!["reference"])]
+        .into_iter()
+        .collect();
+    assert_eq!(tags.len(), 2);
+    tags.get_mut(1, 2).unwrap().push("new");
+    assert_eq!(tags.insert(3, 0, vec![]), None);
+    assert_eq!(
+        tags.of(dataset.get(1, 2).unwrap()).unwrap(),
+        &["measured", "new"]
+    );
+    assert_eq!(
+        tags.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+        [(1, 0), (1, 2), (3, 0)]
+    );
+
+    // joined in the order of the nuclides, with the ones without data left out
+    let joined: Vec<_> = tags
+        .join(&dataset)
+        .map(|(nuc, tags)| (nuc.z, nuc.n, tags.len()))
+        .collect();
+    assert_eq!(joined, [(1, 0, 1), (1, 2, 2), (3, 0, 0)]);
+    let filter: Filter = "z == 1".parse().unwrap();
+    assert_eq!(tags.join(filter.apply(&dataset)).count(), 2);
+
+    assert_eq!(tags.remove(3, 0), Some(vec![]));
+    assert_eq!(tags.get(3, 0), None);
+    tags.extend([((0, 1), vec!["beam"])]);
+    assert_eq!(tags.into_iter().next(), Some(((0, 1), vec!["beam"])));
+    assert!(Metadata::<()>::default().is_empty());
+}
+
+#[test]
+fn slice_ext() {
+    let mut data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let zn =
+        |data: &[Nuclide]| -> Vec<(u32, u32)> { data.iter().map(|nuc| (nuc.z, nuc.n)).collect() };
+    let at = |nuc: Option<&Nuclide>| nuc.map(|nuc| (nuc.z, nuc.n));
+    assert_eq!(at(data.min_mass_excess()), Some((1, 0)));
+    assert_eq!(at(data.max_mass_excess()), Some((3, 0)));
+    assert_eq!(at(data.min_by_uncertainty()), Some((1, 0)));
+    assert_eq!(at(data.max_by_uncertainty()), Some((3, 0)));
+    assert_eq!(at(data.max_binding_energy()), Some((1, 2)));
+
+    data.sort_by_mass_excess();
+    assert_eq!(
+        zn(&data),
+        [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (1, 3), (3, 0)]
+    );
+    data.sort_by_zn();
+    assert_eq!(
+        zn(&data),
+        [(0, 1), (1, 0), (1, 1), (1, 2), (1, 3), (2, 1), (3, 0)]
+    );
+    data.sort_by_a();
+    assert_eq!(
+        zn(&data),
+        [(0, 1), (1, 0), (1, 1), (1, 2), (2, 1), (3, 0), (1, 3)]
+    );
+    // without a binding energy, the neutron is last
+    data[0].binding_energy_per_a = None;
+    data.sort_by_binding_energy();
+    assert_eq!(
+        zn(&data),
+        [(1, 2), (2, 1), (1, 3), (1, 1), (1, 0), (3, 0), (0, 1)]
+    );
+    data.sort_by_uncertainty();
+    assert_eq!(zn(&data)[..2], [(1, 0), (1, 1)]);
+
+    let empty: &[Nuclide] = &[];
+    assert_eq!(empty.max_binding_energy(), None);
+}
+
+#[test]
+fn completeness() {
+    use crate::summary::completeness;
+
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let completeness = completeness(&data, 1..=3, 0..=2);
+    assert_eq!(completeness.experimental, [(1, 0), (1, 1), (1, 2), (2, 1)]);
+    assert_eq!(completeness.estimated, [(3, 0)]);
+    assert_eq!(completeness.missing, [(2, 0), (2, 2), (3, 1), (3, 2)]);
+    assert!(!completeness.is_complete());
+    assert!(crate::summary::completeness(&data, 1..=1, 0..=3).is_complete());
+    assert_eq!(
+        crate::summary::completeness(&data, 100..=101, 0..=0).missing,
+        [(100, 0), (101, 0)]
+    );
+}
+
+#[test]
+fn fission() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        fission,
+    };
+
+    assert!((fission::fissility(92, 146) - 0.770).abs() < 1e-3);
+    assert!(fission::fissility(0, 0).abs() < f64::EPSILON);
+    // the extra proton and neutron are in the second fragment
+    let split = |z, n| fission::symmetric(z, n).map(|fission| fission.products);
+    assert_eq!(split(93, 144), Some(vec![(46, 72), (47, 72)]));
+    assert_eq!(split(1, 1), Some(vec![(0, 1), (1, 0)]));
+    assert_eq!(split(0, 1), None);
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    // H-2 into a neutron and H-1, which is minus S_n
+    let q = fission::symmetric_q_value(&dataset, 1, 1).unwrap();
+    assert!((q.mean + 2_224.566_23).abs() < 1e-5);
+    assert_eq!(fission::symmetric_q_value(&dataset, 3, 0), None);
+}
+
+#[test]
+fn viola_seaborg() {
+    use crate::{
+        alpha::ViolaSeaborg,
+        dataset::{Dataset, Duplicates},
+    };
+
+    let parameters = ViolaSeaborg::SOBICZEWSKI;
+    let q = |mean, uncertainty| Value {
+        mean,
+        uncertainty,
+        is_estimated: false,
+    };
+    let even = parameters.log_half_life(92, 146, &q(4_269.9, 2.0)).unwrap();
+    assert!((even.mean - 17.347).abs() < 1e-3);
+    // to first order, the same as the change from 1 σ
+    let shifted = parameters.log_half_life(92, 146, &q(4_271.9, 0.0)).unwrap();
+    assert!((even.uncertainty - (even.mean - shifted.mean)).abs() < 1e-3);
+    // the hindrances
+    let log_half_life = |z, n| {
+        parameters
+            .log_half_life(z, n, &q(4_269.9, 0.0))
+            .unwrap()
+            .mean
+    };
+    assert!((log_half_life(92, 147) - log_half_life(92, 146) - parameters.odd_n).abs() < 1e-3);
+    let odd_odd = parameters.odd_odd - parameters.odd_z;
+    assert!((log_half_life(93, 147) - log_half_life(93, 146) - odd_odd).abs() < 1e-3);
+    assert_eq!(parameters.log_half_life(92, 146, &q(-1.0, 0.0)), None);
+
+    // H-4 can't α decay
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    assert_eq!(parameters.estimate(&dataset, 1, 3), None);
+}
+
+#[test]
+fn alpha_chain() {
+    use crate::{
+        alpha::chain,
+        dataset::{Dataset, Duplicates},
+        DecayMode,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let decays = chain(&dataset, 1, 2, true);
+    assert_eq!(decays.len(), 1);
+    assert_eq!((decays[0].parent, decays[0].daughter), ((1, 2), (2, 1)));
+    assert_eq!(decays[0].mode, DecayMode::BetaMinus);
+    assert!((decays[0].q_value.mean - 18.592).abs() < 1e-3);
+    // without β decays, H-3 is stable
+    assert_eq!(chain(&dataset, 1, 2, false), []);
+    // Li-3 captures an electron, and He-3 is stable
+    let decays = chain(&dataset, 3, 0, true);
+    assert_eq!(decays.len(), 1);
+    assert_eq!(decays[0].mode, DecayMode::ElectronCapture);
+    assert_eq!(decays[0].daughter, (2, 1));
+}
+
+#[test]
+fn decay_graph() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        decay::{graph, Graph, Mode},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    // H-4 emits a neutron, and then H-3 β⁻ decays
+    let decays = graph(&dataset, 1, 3, &Mode::ALL);
+    let edges: Vec<_> = decays
+        .edges
+        .iter()
+        .map(|edge| (edge.parent, edge.mode, edge.daughter))
+        .collect();
+    assert_eq!(
+        edges,
+        [
+            ((1, 3), Mode::Neutron, (1, 2)),
+            ((1, 2), Mode::BetaMinus, (2, 1))
+        ]
+    );
+    assert!((decays.edges[0].q_value.mean - 1_600.0).abs() < 1.0);
+    assert_eq!(decays.decays(1, 2).count(), 1);
+    assert_eq!(
+        decays.nuclides().into_iter().collect::<Vec<_>>(),
+        [(1, 2), (1, 3), (2, 1)]
+    );
+    assert_eq!(
+        decays.to_dot(),
+        "digraph decays {\n    \"4H\" -> \"3H\" [label=\"n 1600.0 keV\"];\n    \"3H\" -> \"3He\" [label=\"β⁻ 18.6 keV\"];\n}\n"
+    );
+
+    // Li-3 goes to He-3 by both β⁺ decay and electron capture
+    let decays = graph(&dataset, 3, 0, &Mode::ALL);
+    let modes: Vec<_> = decays.edges.iter().map(|edge| edge.mode).collect();
+    assert_eq!(modes, [Mode::BetaPlus, Mode::ElectronCapture]);
+    assert_eq!(graph(&dataset, 1, 3, &[Mode::Alpha]), Graph::default());
+}
+
+#[test]
+fn decay_electron_capture() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        decay::{electron_capture, Mode},
+    };
+
+    let multi = include_str!("tests/multi");
+    let dataset = Dataset::from_slice(multi.as_bytes(), Duplicates::Error).unwrap();
+    let li3 = electron_capture(&dataset, 3, 0).unwrap();
+    assert!((li3.electron_capture.mean - 13_735.8).abs() < 0.1);
+    assert!((li3.electron_capture.mean - li3.beta_plus.mean - 1_021.998).abs() < 1e-3);
+    assert_eq!(li3.mode(), Some(Mode::BetaPlus));
+    assert!(!li3.is_pure_electron_capture());
+    // He-3 is lighter than H-3
+    assert_eq!(electron_capture(&dataset, 2, 1).unwrap().mode(), None);
+    assert_eq!(electron_capture(&dataset, 0, 1), None);
+
+    // with a Q-value of 568 keV, Li-3 can only capture an electron
+    let input = multi.replacen("28667#", "15500#", 1);
+    let dataset = Dataset::from_slice(input.as_bytes(), Duplicates::Error).unwrap();
+    let li3 = electron_capture(&dataset, 3, 0).unwrap();
+    assert_eq!(li3.mode(), Some(Mode::ElectronCapture));
+    assert!(li3.is_pure_electron_capture());
+}
+
+#[test]
+fn covariance() {
+    use crate::{
+        covariance,
+        dataset::{Dataset, Duplicates},
+    };
+
+    let covariance = covariance::read(
+        &b"# correlations
+1H 2H 0.5
+
+  3H   H-2  -0.25
+2H 1H 0.75
+1H 1H 0.1
+"[..],
+    )
+    .unwrap();
+    // the last of a pair is kept, and a nuclide is correlated with itself
+    assert_eq!(covariance.len(), 2);
+    assert_eq!(
+        covariance.iter().collect::<Vec<_>>(),
+        [((1, 0), (1, 1), 0.75), ((1, 1), (1, 2), -0.25)]
+    );
+    assert_eq!(
+        (
+            covariance.correlation((1, 2), (1, 1)),
+            covariance.correlation((1, 0), (1, 0)),
+            covariance.correlation((1, 0), (1, 2)),
+        ),
+        (-0.25, 1.0, 0.0)
+    );
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let (h1, h2) = (dataset.get(1, 0).unwrap(), dataset.get(1, 1).unwrap());
+    let expected = 0.75 * h1.mass_excess.uncertainty * h2.mass_excess.uncertainty;
+    assert_eq!(
+        covariance.covariance(&dataset, (1, 1), (1, 0)),
+        Some(expected)
+    );
+    let variance = h1.mass_excess.uncertainty.powi(2);
+    assert_eq!(
+        covariance.covariance(&dataset, (1, 0), (1, 0)),
+        Some(variance)
+    );
+    assert_eq!(covariance.covariance(&dataset, (1, 0), (8, 8)), None);
+
+    for (text, column) in [
+        ("1H 2H", "correlation"),
+        ("1H 2H 1.5", "correlation"),
+        ("1H 2H x", "correlation"),
+        ("1H Xx2 0.5", "nuclide"),
+        ("1H", "nuclide"),
+    ] {
+        let text = format!("\n1H 2H 0.5\n{text}\n");
+        assert_eq!(
+            covariance::read(text.as_bytes()),
+            Err(AmeError::InvalidColumn { line: 3, column })
+        );
+    }
+    assert!(covariance::read(&b""[..]).unwrap().is_empty());
+
+    // S_n of 2H, with 1H and 2H correlated, and the neutron not
+    let uncorrelated = dataset.separation_energy(1, 1, 0, 1).unwrap();
+    assert_eq!(dataset.covariance(), None);
+    let dataset = dataset.with_covariance(covariance);
+    assert!(dataset.covariance().is_some());
+    let correlated = dataset.separation_energy(1, 1, 0, 1).unwrap();
+    assert!((correlated.mean - uncorrelated.mean).abs() < 1e-12);
+    let (h1, h2) = (dataset.get(1, 0).unwrap(), dataset.get(1, 1).unwrap());
+    let variance = uncorrelated.uncertainty.powi(2)
+        - 2.0 * 0.75 * h1.mass_excess.uncertainty * h2.mass_excess.uncertainty;
+    assert!((correlated.uncertainty - variance.sqrt()).abs() < 1e-15);
+    assert!(correlated.uncertainty < uncorrelated.uncertainty);
+    // a nuclide on both sides still cancels
+    let q = dataset
+        .q_value(&[(1, 0), (1, 1)], &[(1, 1), (1, 0)])
+        .unwrap();
+    assert_eq!((q.mean, q.uncertainty), (0.0, 0.0));
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn covariance_matrix() {
+    use crate::{
+        covariance,
+        dataset::{Dataset, Duplicates},
+    };
+    use nalgebra::DMatrix;
+
+    let covariance = covariance::read(&b"1H 2H 0.75\n2H 3H -0.25\n"[..]).unwrap();
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let nuclides = [(1, 1), (1, 0), (0, 1)];
+    let correlations = covariance.correlation_matrix(&nuclides);
+    assert_eq!(
+        correlations,
+        DMatrix::from_row_slice(3, 3, &[1.0, 0.75, 0.0, 0.75, 1.0, 0.0, 0.0, 0.0, 1.0])
+    );
+    let matrix = covariance.matrix(&dataset, &nuclides).unwrap();
+    assert_eq!(matrix, matrix.transpose());
+    let h2 = dataset.get(1, 1).unwrap();
+    assert!((matrix[(0, 0)] - h2.mass_excess.uncertainty.powi(2)).abs() < 1e-15);
+    assert_eq!(covariance.matrix(&dataset, &[(8, 8)]), None);
+
+    // S_n of 2H is the same as from the dataset with the correlations
+    let jacobian = DMatrix::from_row_slice(1, 3, &[-1.0, 1.0, 1.0]);
+    let (values, propagated) = covariance
+        .propagate(&dataset, &nuclides, &jacobian)
+        .unwrap();
+    let sn = dataset
+        .clone()
+        .with_covariance(covariance.clone())
+        .separation_energy(1, 1, 0, 1)
+        .unwrap();
+    assert!((values[0] - sn.mean).abs() < 1e-9);
+    assert!((propagated[(0, 0)].sqrt() - sn.uncertainty).abs() < 1e-12);
+    let wrong = DMatrix::from_row_slice(1, 2, &[1.0, 1.0]);
+    assert_eq!(covariance.propagate(&dataset, &nuclides, &wrong), None);
+}
+
+#[test]
+fn kinematics() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        kinematics::{self, Kinematics},
+        reaction::Reaction,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let conserved = |kinematics: &Kinematics, angle: f64, count: usize| {
+        let ejectiles = kinematics.ejectile(angle);
+        assert_eq!(ejectiles.len(), count, "{angle}");
+        for ejectile in ejectiles {
+            let energy = ejectile.energy + ejectile.residual_energy;
+            assert!((energy - kinematics.beam_energy - kinematics.q_value()).abs() < 1e-6);
+            // the momenta across the beam cancel
+            let momentum = |energy: f64, mass: f64| (energy * (energy + 2.0 * mass)).sqrt();
+            let across = momentum(ejectile.energy, kinematics.ejectile) * angle.sin()
+                - momentum(ejectile.residual_energy, kinematics.residual)
+                    * ejectile.residual_angle.sin();
+            assert!(across.abs() < 1e-3);
+        }
+    };
+
+    // 2H(d,p)3H is exothermic, and the proton can go at any angle
+    let reaction = Reaction::parse("2H(d,p)3H").unwrap();
+    let kinematics = Kinematics::new(&dataset, &reaction, 1_000.0, 0.0).unwrap();
+    assert!((kinematics.q_value() - 4_032.7).abs() < 0.1);
+    assert_eq!(kinematics.max_angle(), None);
+    for angle in [0.0, 0.5, 1.5, 3.0] {
+        conserved(&kinematics, angle, 1);
+    }
+    // an excited residual takes the energy from the ejectile
+    let excited = Kinematics::new(&dataset, &reaction, 1_000.0, 1_000.0).unwrap();
+    assert!(excited.ejectile(0.5)[0].energy < kinematics.ejectile(0.5)[0].energy);
+
+    // 3H(p,n)3He has a threshold of 1.02 MeV, and just above it the neutrons go forward
+    let reaction = Reaction::parse("3H(p,n)3He").unwrap();
+    let kinematics = Kinematics::new(&dataset, &reaction, 1_100.0, 0.0).unwrap();
+    let max_angle = kinematics.max_angle().unwrap();
+    conserved(&kinematics, 0.0, 2);
+    conserved(&kinematics, max_angle - 1e-3, 2);
+    conserved(&kinematics, max_angle + 1e-3, 0);
+    let ejectiles = kinematics.ejectile(0.0);
+    assert!(ejectiles[0].energy > ejectiles[1].energy);
+    let below = Kinematics::new(&dataset, &reaction, 1_000.0, 0.0).unwrap();
+    conserved(&below, 0.0, 0);
+    assert_eq!(below.max_angle(), None);
+
+    // the threshold is where the two solutions at 0 meet
+    let threshold = kinematics::threshold_energy(&dataset, &reaction).unwrap();
+    assert!((threshold.mean - 1_019.0).abs() < 0.1);
+    let q = dataset.reaction_q_value(&reaction).unwrap();
+    assert!(threshold.uncertainty > q.uncertainty);
+    let above = Kinematics::new(&dataset, &reaction, threshold.mean + 1e-3, 0.0).unwrap();
+    let ejectiles = above.ejectile(0.0);
+    assert!((ejectiles[0].energy - ejectiles[1].energy).abs() < 1.0);
+    conserved(
+        &Kinematics::new(&dataset, &reaction, threshold.mean - 1e-3, 0.0).unwrap(),
+        0.0,
+        0,
+    );
+    let exothermic = Reaction::parse("2H(d,p)3H").unwrap();
+    let threshold = kinematics::threshold_energy(&dataset, &exothermic).unwrap();
+    assert_eq!((threshold.mean, threshold.uncertainty), (0.0, 0.0));
+    let decay = Reaction::decay(1, 2, crate::DecayMode::BetaMinus).unwrap();
+    assert_eq!(kinematics::threshold_energy(&dataset, &decay), None);
+
+    assert_eq!(
+        Kinematics::new(&dataset, &Reaction::parse("3H(p,g)").unwrap(), 1.0, 0.0),
+        None
+    );
+}
+
+#[test]
+fn molecule() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        molecule::Molecule,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    assert_eq!(
+        Molecule::parse("12C 16O2").unwrap().atoms,
+        [((6, 6), 1), ((8, 8), 2)]
+    );
+    // a nuclide that is listed twice has the atoms of both
+    let molecule = Molecule::parse(" 1H2  2H 1H ").unwrap();
+    assert_eq!(molecule.atoms, [((1, 0), 3), ((1, 1), 1)]);
+    assert_eq!(molecule.to_string(), "1H3 2H");
+    assert_eq!(
+        Molecule::parse(&molecule.to_string()),
+        Some(molecule.clone())
+    );
+    for bad in ["", "H2", "1H0", "1Hx", "1H2x", "3Xx"] {
+        assert_eq!(Molecule::parse(bad), None, "{bad}");
+    }
+
+    let (h1, h2) = (dataset.get(1, 0).unwrap(), dataset.get(1, 1).unwrap());
+    let neutral = molecule.mass(&dataset, 0).unwrap();
+    let mean = 3.0 * h1.atomic_mass.mean + h2.atomic_mass.mean;
+    assert!((neutral.mean - mean).abs() < 1e-12);
+    let uncertainty = (3.0 * h1.atomic_mass.uncertainty).hypot(h2.atomic_mass.uncertainty);
+    assert!((neutral.uncertainty - uncertainty).abs() < 1e-15);
+    let electron = crate::constants::ELECTRON_MASS / crate::constants::ATOMIC_MASS_UNIT;
+    let ion = molecule.mass_to_charge(&dataset, 2).unwrap();
+    assert!((ion.mean - (mean - 2.0 * electron) / 2.0).abs() < 1e-12);
+    assert_eq!(molecule.mass_to_charge(&dataset, 0), None);
+    assert_eq!(Molecule::parse("4He").unwrap().mass(&dataset, 1), None);
+}
+
+#[test]
+fn network() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        network,
+        reaction::Reaction,
+    };
+
+    assert_eq!(network::name(1, 2).as_deref(), Some("t"));
+    assert_eq!(network::name(2, 2).as_deref(), Some("he4"));
+    assert_eq!(network::name(13, 13).as_deref(), Some("al26"));
+    assert_eq!(network::name(200, 1), None);
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let mut buf = Vec::new();
+    network::write_winvn(&mut buf, dataset.nuclides(), |_| 0.0).unwrap();
+    let winvn = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = winvn.lines().collect();
+    // the temperatures, the names, and 4 lines for each nuclide
+    assert_eq!(lines.len(), 1 + 7 + 4 * 7);
+    assert_eq!(lines[0].split_whitespace().count(), 24);
+    assert_eq!(
+        lines[1..8],
+        ["    n", "    p", "    d", "    t", "  he3", "  li3", "   h4"]
+    );
+    assert_eq!(lines[8], "n           1.000   0   1   0.0     8.071 ame20");
+    assert_eq!(
+        lines[9].split_whitespace().collect::<Vec<_>>(),
+        ["1.000E0"; 8]
+    );
+
+    let mut buf = Vec::new();
+    let reactions = [
+        Reaction::parse("1H(n,g)2H").unwrap(),
+        Reaction::parse("3H(p,n)3He").unwrap(),
+        // He-4 isn't in the table
+        Reaction::parse("1H(t,g)4He").unwrap(),
+    ];
+    network::write_q_values(&mut buf, &dataset, &reactions).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "n p -> d 2.22457\np t -> n he3 -0.76375\n"
+    );
+}
+
+#[test]
+fn binding() {
+    use crate::binding;
+
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let curve = binding::curve(&data);
+    let a = |points: &[binding::Point]| -> Vec<_> {
+        points
+            .iter()
+            .map(|point| (point.nuclide.z, point.nuclide.n))
+            .collect()
+    };
+    // n and H-1 are both unbound, so the first is kept
+    assert_eq!(a(&curve), [(0, 1), (1, 1), (1, 2), (1, 3)]);
+    assert_eq!(a(&binding::most_bound(&data, 3)), [(1, 2), (2, 1), (1, 3)]);
+    assert_eq!(binding::most_bound(&data, 100).len(), 7);
+
+    let mut csv = Vec::new();
+    binding::write_csv(&mut csv, &curve).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[0],
+        "a,z,n,element,binding_energy_per_a,binding_energy_per_a_uncertainty"
+    );
+    assert_eq!(lines[3], "3,1,2,H,2827.2654,0.0003");
+}
+
+#[test]
+fn capture() {
+    use crate::{
+        capture,
+        dataset::{Dataset, Duplicates},
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let q_values = capture::q_values(&dataset);
+    assert_eq!(q_values.len(), 7);
+    // 1H(n,γ)2H and n(p,γ)2H have the same Q-value, the binding energy of the deuteron
+    let deuteron = q_values[1].neutron.as_ref().unwrap().mean;
+    assert!((deuteron - 2_224.566).abs() < 1e-3);
+    assert_eq!(q_values[0].proton, q_values[1].neutron);
+    assert_eq!(q_values[0].neutron, None);
+    assert!(q_values[2].proton.is_some());
+    // there is no 4He
+    assert!(q_values.iter().all(|q| q.alpha.is_none()));
+
+    let mut csv = Vec::new();
+    capture::write_csv(&mut csv, &q_values).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert!(lines.iter().all(|line| line.split(',').count() == 10));
+    assert!(lines[1].starts_with("0,1,1,n,,,"));
+
+    let mut json = Vec::new();
+    capture::write_json(&mut json, &q_values).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 7);
+    assert_eq!(json[1]["element"], "H");
+    assert_eq!(json[1]["n_gamma"], deuteron);
+    assert!(json[1]["a_gamma"].is_null());
+}
+
+#[test]
+fn penning() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        molecule::Molecule,
+        penning,
+    };
+
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let (h1, h2) = (&data[1], &data[2]);
+    let electron = crate::constants::ELECTRON_MASS / crate::constants::ATOMIC_MASS_UNIT;
+    assert!((penning::ion_mass(h1, -1).mean - h1.atomic_mass.mean - electron).abs() < 1e-12);
+    assert_eq!(penning::mass_to_charge(h1, 0), None);
+    assert_eq!(penning::frequency_ratio(h2, 1, h1, 0), None);
+
+    // a proton is 15.2 MHz in a field of 1 T
+    let frequency = penning::cyclotron_frequency(h1, 1, 1.0).unwrap();
+    assert!((frequency.mean - 15.245_19e6).abs() < 10.0);
+    let relative = h1.atomic_mass.uncertainty / penning::ion_mass(h1, 1).mean;
+    assert!((frequency.uncertainty / frequency.mean - relative).abs() < 1e-15);
+
+    // the ratio is that of the masses for the same charge
+    let ratio = penning::frequency_ratio(h2, 1, h1, 1).unwrap();
+    let masses = penning::ion_mass(h2, 1).mean / penning::ion_mass(h1, 1).mean;
+    assert!((ratio.mean - masses).abs() < 1e-12);
+    let doubled = penning::frequency_ratio(h2, 2, h1, 1).unwrap();
+    let masses = penning::ion_mass(h2, 2).mean / 2.0 / penning::ion_mass(h1, 1).mean;
+    assert!((doubled.mean - masses).abs() < 1e-12);
+    let relative = (h2.atomic_mass.uncertainty / penning::ion_mass(h2, 1).mean)
+        .hypot(h1.atomic_mass.uncertainty / penning::ion_mass(h1, 1).mean);
+    assert!((ratio.uncertainty / ratio.mean - relative).abs() < 1e-15);
+    assert!(!ratio.is_estimated);
+
+    // the ion of 2H, or a molecule of two of 1H and n
+    let measured = penning::mass_to_charge(h2, 1).unwrap().mean + 1e-5;
+    let candidates = penning::identify(&data, measured, 0.01, 1, 2);
+    let atoms: Vec<Vec<(u32, u32, u32)>> = candidates
+        .iter()
+        .map(|candidate| {
+            candidate
+                .atoms
+                .iter()
+                .map(|(nuc, count)| (nuc.z, nuc.n, *count))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        atoms,
+        [
+            vec![(1, 1, 1)],
+            vec![(1, 0, 2)],
+            vec![(1, 0, 1), (0, 1, 1)],
+            vec![(0, 1, 2)]
+        ]
+    );
+    assert!((candidates[0].difference - 1e-5).abs() < 1e-12);
+    assert_eq!(candidates[2].molecule().to_string(), "1H 1n");
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    let ions: Vec<(Molecule, i32)> = [("2H", 1), ("1H2", 1), ("4H", 2), ("1H2", 1), ("3He", 1)]
+        .into_iter()
+        .map(|(formula, charge)| (Molecule::parse(formula).unwrap(), charge))
+        .collect();
+    let doublets = penning::doublets(&dataset, &ions, 0.01);
+    let pairs: Vec<(String, String)> = doublets
+        .iter()
+        .map(|doublet| {
+            (
+                doublet.first.molecule.to_string(),
+                doublet.second.molecule.to_string(),
+            )
+        })
+        .collect();
+    // 4H²⁺ is at 2.0126 u/e, 2H⁺ at 2.0136 u/e, and 1H2⁺ at 2.0151 u/e, and the same ion twice
+    // isn't a doublet
+    assert_eq!(
+        pairs,
+        [
+            ("4H", "2H"),
+            ("2H", "1H2"),
+            ("2H", "1H2"),
+            ("4H", "1H2"),
+            ("4H", "1H2")
+        ]
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+    );
+    let difference = &doublets[1].difference;
+    let mean = 2.0 * h1.atomic_mass.mean - h2.atomic_mass.mean;
+    assert!((difference.mean - mean).abs() < 1e-12);
+    let uncertainty = (2.0 * h1.atomic_mass.uncertainty).hypot(h2.atomic_mass.uncertainty);
+    assert!((difference.uncertainty - uncertainty).abs() < 1e-15);
+    assert!(penning::doublets(&dataset, &ions, 1e-4).is_empty());
+    let two = &candidates[1].mass_to_charge;
+    assert!((two.mean - (2.0 * h1.atomic_mass.mean - electron)).abs() < 1e-12);
+    assert!((two.uncertainty - 2.0 * h1.atomic_mass.uncertainty).abs() < 1e-15);
+    assert_eq!(penning::identify(&data, measured, 0.01, 1, 1).len(), 1);
+    assert!(penning::identify(&data, measured, 0.01, 0, 2).is_empty());
+    // 2H²⁺ is at half of the mass-to-charge ratio
+    let doubly = penning::mass_to_charge(h2, 2).unwrap().mean;
+    assert_eq!(penning::identify(&data, doubly, 1e-4, 2, 2).len(), 1);
+}
+
+#[test]
+fn process() {
+    use crate::{
+        dataset::{Dataset, Duplicates},
+        process,
+    };
+
+    let dataset = Dataset::from_slice(include_bytes!("tests/multi"), Duplicates::Error).unwrap();
+    // S_n is 2.2 MeV for 2H, 6.3 MeV for 3H, and negative for 4H, and 3He doesn't β⁻ decay
+    assert_eq!(
+        process::r_process(&dataset, 1, 0, 2_000.0),
+        [(1, 2), (2, 1)]
+    );
+    assert_eq!(process::r_process(&dataset, 1, 0, 3_000.0), [(1, 0)]);
+    // S_p is 2.2 MeV for 2H and 5.5 MeV for 3He, which doesn't β⁺ decay
+    assert_eq!(process::rp_process(&dataset, 0, 1, 2_000.0), [(2, 1)]);
+    assert_eq!(process::rp_process(&dataset, 0, 1, 6_000.0), [(0, 1)]);
+    assert!(process::r_process(&dataset, 2, 2, 0.0).is_empty());
+}
+
+#[test]
+fn talys() {
+    let data = crate::parse_str(include_str!("tests/multi")).unwrap();
+    let dir = std::env::temp_dir().join("ame2020-talys-test");
+    crate::talys::write_dir(&dir, &data).unwrap();
+    let file = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+    let (z000, z001, z003) = (file("z000"), file("z001"), file("z003"));
+    let count = std::fs::read_dir(&dir).unwrap().count();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(count, 4);
+    assert_eq!(z000, "   0   1    1.008665    8.071318\n");
+    assert_eq!(
+        z001,
+        "   1   1    1.007825    7.288971\n\
+        \x20  1   2    2.014102   13.135723\n\
+        \x20  1   3    3.016049   14.949811\n\
+        \x20  1   4    4.026432   24.621129\n"
+    );
+    assert_eq!(z003, "   3   3    3.030775   28.667000\n");
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn decimal() {
+    use crate::{decimal, parse_str};
+    use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+    let source = include_str!("tests/multi");
+    let exact = decimal::parse_str(source).unwrap();
+    let floats = parse_str(source).unwrap();
+    assert_eq!(exact.len(), floats.len());
+    for (exact, float) in exact.iter().zip(&floats) {
+        assert_eq!((exact.z, exact.n), (float.z, float.n));
+        let pairs = [
+            (Some(&exact.mass_excess), Some(&float.mass_excess)),
+            (
+                exact.binding_energy_per_a.as_ref(),
+                float.binding_energy_per_a.as_ref(),
+            ),
+            (
+                exact.beta_decay_energy.as_ref(),
+                float.beta_decay_energy.as_ref(),
+            ),
+            (Some(&exact.atomic_mass), Some(&float.atomic_mass)),
+        ];
+        for pair in pairs {
+            match pair {
+                (Some(exact), Some(float)) => {
+                    assert_eq!(exact.is_estimated, float.is_estimated);
+                    assert!((exact.mean.to_f64().unwrap() - float.mean).abs() < 1e-9);
+                    assert!((exact.uncertainty.to_f64().unwrap() - float.uncertainty).abs() < 1e-9);
+                }
+                (None, None) => {}
+                pair => panic!("{pair:?}"),
+            }
+        }
+    }
+
+    // the digits are as written, including trailing zeros
+    let h4 = &exact[6];
+    assert_eq!(h4.mass_excess.uncertainty.to_string(), "100.000");
+    assert_eq!(h4.atomic_mass.mean.to_string(), "4.026431867");
+    assert_eq!(h4.atomic_mass.uncertainty.to_string(), "0.000107354");
+    // '#' is in place of '.'
+    let li3 = &exact[5];
+    assert_eq!(li3.mass_excess.mean, Decimal::from(28_667));
+    assert!(li3.mass_excess.is_estimated);
+    assert_eq!(li3.atomic_mass.mean.to_string(), "3.030775");
+    assert_eq!(exact[1].beta_decay_energy, None);
 }
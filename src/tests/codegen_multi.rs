@@ -0,0 +1,80 @@
+#[allow(clippy::all, clippy::pedantic)]
+pub static NUCLIDES: [::ame2020::codegen::StaticNuclide; 7] = [
+    ::ame2020::codegen::StaticNuclide {
+        n: 1,
+        z: 0,
+        element: "n",
+        origin: None,
+        mass_excess: ::ame2020::Value { mean: 8071.31806_f64, uncertainty: 0.00044_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 0.0_f64, uncertainty: 0.0_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: Some(::ame2020::Value { mean: 782.347_f64, uncertainty: 0.0004_f64, is_estimated: false }),
+        atomic_mass: ::ame2020::Value { mean: 1.0086649159_f64, uncertainty: 4.699999999999999e-10_f64, is_estimated: false },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 0,
+        z: 1,
+        element: "H",
+        origin: None,
+        mass_excess: ::ame2020::Value { mean: 7288.971064_f64, uncertainty: 1.3e-5_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 0.0_f64, uncertainty: 0.0_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: None,
+        atomic_mass: ::ame2020::Value { mean: 1.007825031898_f64, uncertainty: 1.3999999999999999e-11_f64, is_estimated: false },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 1,
+        z: 1,
+        element: "H",
+        origin: None,
+        mass_excess: ::ame2020::Value { mean: 13135.722895_f64, uncertainty: 1.5e-5_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 1112.2831_f64, uncertainty: 0.0002_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: None,
+        atomic_mass: ::ame2020::Value { mean: 2.014101777844_f64, uncertainty: 1.5e-11_f64, is_estimated: false },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 2,
+        z: 1,
+        element: "H",
+        origin: None,
+        mass_excess: ::ame2020::Value { mean: 14949.8109_f64, uncertainty: 8e-5_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 2827.2654_f64, uncertainty: 0.0003_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: Some(::ame2020::Value { mean: 18.59202_f64, uncertainty: 6e-5_f64, is_estimated: false }),
+        atomic_mass: ::ame2020::Value { mean: 3.01604928132_f64, uncertainty: 8.000000000000001e-11_f64, is_estimated: false },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 1,
+        z: 2,
+        element: "He",
+        origin: None,
+        mass_excess: ::ame2020::Value { mean: 14931.21888_f64, uncertainty: 6e-5_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 2572.68044_f64, uncertainty: 0.00015_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: Some(::ame2020::Value { mean: -13736.0_f64, uncertainty: 2000.0_f64, is_estimated: true }),
+        atomic_mass: ::ame2020::Value { mean: 3.01602932197_f64, uncertainty: 6e-11_f64, is_estimated: false },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 0,
+        z: 3,
+        element: "Li",
+        origin: Some("-pp"),
+        mass_excess: ::ame2020::Value { mean: 28667.0_f64, uncertainty: 2000.0_f64, is_estimated: true },
+        binding_energy_per_a: Some(::ame2020::Value { mean: -2267.0_f64, uncertainty: 667.0_f64, is_estimated: true }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: None,
+        atomic_mass: ::ame2020::Value { mean: 3.030775_f64, uncertainty: 0.002147_f64, is_estimated: true },
+    },
+    ::ame2020::codegen::StaticNuclide {
+        n: 3,
+        z: 1,
+        element: "H",
+        origin: Some("-n"),
+        mass_excess: ::ame2020::Value { mean: 24621.129_f64, uncertainty: 100.0_f64, is_estimated: false },
+        binding_energy_per_a: Some(::ame2020::Value { mean: 1720.4491_f64, uncertainty: 25.0_f64, is_estimated: false }),
+        decay_mode: Some(::ame2020::DecayMode::BetaMinus),
+        beta_decay_energy: Some(::ame2020::Value { mean: 22196.2131_f64, uncertainty: 100.0_f64, is_estimated: false }),
+        atomic_mass: ::ame2020::Value { mean: 4.026431867_f64, uncertainty: 0.00010735399999999999_f64, is_estimated: false },
+    },
+];
@@ -0,0 +1,100 @@
+//! Comparing the evaluation to a table of masses from a theory or model.
+//!
+//! The theory is given as mass excesses in keV, looked up by Z and N, so it can be a map, such as
+//! a [`HashMap`](std::collections::HashMap) with (Z, N) keys, or a function. The residuals are the
+//! evaluated mass excesses minus the theoretical ones.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::{parse_str, theory::compare};
+//! use std::collections::HashMap;
+//!
+//! let data = parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! let theory = HashMap::from([((0, 1), 8_000.0), ((1, 0), 7_300.0)]);
+//! let comparison = compare(&data, |z, n| theory.get(&(z, n)).copied(), false);
+//! assert_eq!(comparison.residuals.len(), 2);
+//! assert!((comparison.rms.unwrap() - 51.0).abs() < 1.0);
+//! ```
+use crate::Nuclide;
+
+/// The residuals of a theory, and their statistics, from [`compare`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Comparison<'a> {
+    /// The residual of each nuclide in both the evaluation and the theory, in the order of the
+    /// evaluation
+    pub residuals: Vec<Residual<'a>>,
+    /// The mean of the residuals in keV, if there are any
+    pub mean: Option<f64>,
+    /// The root mean square of the residuals in keV, if there are any
+    pub rms: Option<f64>,
+    /// The sum of the squares of the residuals in units of the uncertainties of the evaluation
+    ///
+    /// The nuclides with no uncertainty are left out.
+    pub chi_squared: f64,
+}
+
+/// The difference between the evaluation and the theory for one nuclide.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Residual<'a> {
+    /// The nuclide in the evaluation
+    pub nuclide: &'a Nuclide,
+    /// The mass excess from the theory, in keV
+    pub theory: f64,
+    /// The evaluated minus the theoretical mass excess, in keV
+    pub residual: f64,
+    /// The residual in units of the uncertainty of the evaluation, which is infinite if there is
+    /// no uncertainty
+    pub sigma: f64,
+}
+
+/// Compares the mass excesses of `nuclides` to those of `theory`, in keV.
+///
+/// The nuclides `theory` has no value for are left out, and so are the estimated mass excesses,
+/// if `experimental_only`.
+pub fn compare<'a>(
+    nuclides: impl IntoIterator<Item = &'a Nuclide>,
+    theory: impl Fn(u32, u32) -> Option<f64>,
+    experimental_only: bool,
+) -> Comparison<'a> {
+    let residuals: Vec<Residual> = nuclides
+        .into_iter()
+        .filter(|nuc| !(experimental_only && nuc.mass_excess.is_estimated))
+        .filter_map(|nuc| {
+            let theory = theory(nuc.z, nuc.n)?;
+            let residual = nuc.mass_excess.mean - theory;
+            let sigma = if nuc.mass_excess.uncertainty > 0.0 {
+                residual / nuc.mass_excess.uncertainty
+            } else {
+                residual.signum() * f64::INFINITY
+            };
+            Some(Residual {
+                nuclide: nuc,
+                theory,
+                residual,
+                sigma,
+            })
+        })
+        .collect();
+    // a table has far fewer values than can be represented exactly
+    #[allow(clippy::cast_precision_loss)]
+    let len = residuals.len() as f64;
+    let mean =
+        (!residuals.is_empty()).then(|| residuals.iter().map(|r| r.residual).sum::<f64>() / len);
+    let rms = (!residuals.is_empty())
+        .then(|| (residuals.iter().map(|r| r.residual.powi(2)).sum::<f64>() / len).sqrt());
+    let chi_squared = residuals
+        .iter()
+        .filter(|r| r.nuclide.mass_excess.uncertainty > 0.0)
+        .map(|r| r.sigma.powi(2))
+        .sum();
+    Comparison {
+        residuals,
+        mean,
+        rms,
+        chi_squared,
+    }
+}
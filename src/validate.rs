@@ -0,0 +1,201 @@
+//! Checks of the internal consistency of the data.
+//!
+//! Several of the quantities in the table are derived from the others, so they can be recomputed
+//! and compared. A difference larger than the uncertainties usually means that a line was corrupted
+//! or edited by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use ame2020::validate::check_all;
+//!
+//! let data = ame2020::parse_str(r"1
+//! 1
+//! 0  1    1    0    1  n         8071.31806     0.00044       0.0        0.0     B-    782.3470     0.0004    1 008664.91590     0.00047
+//!   -1    0    1    1 H          7288.971064    0.000013      0.0        0.0     B-      *                    1 007825.031898    0.000014").unwrap();
+//! let reports = check_all(&data);
+//! assert!(reports.iter().all(Vec::is_empty));
+//! ```
+use crate::{constants::Constants, element, AmeError, Nuclide, Value};
+use std::collections::{HashMap, HashSet};
+
+// the values in the file are rounded, so allow for some difference even without uncertainty
+const ROUNDING: f64 = 1e-3;
+
+/// A difference between a value in the table and the value derived from other values.
+#[derive(Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum Inconsistency {
+    /// The mass excess doesn't match the atomic mass.
+    MassExcess { expected: Value, actual: Value },
+    /// The binding energy per nucleon doesn't match the mass excess.
+    BindingEnergyPerA { expected: Value, actual: Value },
+    /// The beta decay energy doesn't match the difference in mass excess with the isobaric
+    /// neighbor (Z+1, N-1).
+    ///
+    /// The expected value is `None` if the neighbor isn't in the data.
+    BetaDecayEnergy {
+        expected: Option<Value>,
+        actual: Option<Value>,
+    },
+}
+
+/// Checks the quantities of `nuclide` that can be derived from its other quantities.
+///
+/// The mass excess is compared to the atomic mass, and the binding energy per nucleon is compared
+/// to the mass excess.
+#[must_use]
+pub fn check(nuclide: &Nuclide) -> Vec<Inconsistency> {
+    check_with(nuclide, &Constants::AME2020)
+}
+
+fn check_with(nuclide: &Nuclide, constants: &Constants) -> Vec<Inconsistency> {
+    let mut inconsistencies = Vec::new();
+
+    let a = f64::from(nuclide.n + nuclide.z);
+    let expected = Value {
+        mean: constants.to_kev(nuclide.atomic_mass.mean - a),
+        uncertainty: constants.to_kev(nuclide.atomic_mass.uncertainty),
+        is_estimated: nuclide.atomic_mass.is_estimated,
+    };
+    if !agrees(&expected, &nuclide.mass_excess) {
+        inconsistencies.push(Inconsistency::MassExcess {
+            expected,
+            actual: nuclide.mass_excess.clone(),
+        });
+    }
+
+    if let (Some(actual), true) = (&nuclide.binding_energy_per_a, nuclide.n + nuclide.z > 0) {
+        let binding_energy = f64::from(nuclide.z) * constants.hydrogen_mass_excess
+            + f64::from(nuclide.n) * constants.neutron_mass_excess
+            - nuclide.mass_excess.mean;
+        let expected = Value {
+            mean: binding_energy / a,
+            uncertainty: nuclide.mass_excess.uncertainty / a,
+            is_estimated: nuclide.mass_excess.is_estimated,
+        };
+        if !agrees(&expected, actual) {
+            inconsistencies.push(Inconsistency::BindingEnergyPerA {
+                expected,
+                actual: actual.clone(),
+            });
+        }
+    }
+
+    inconsistencies
+}
+
+/// Checks every nuclide in `nuclides`, including the quantities that depend on other nuclides.
+///
+/// In addition to [`check`], the beta decay energy is compared to the mass excess of the isobaric
+/// neighbor. The reports are in the same order as `nuclides`.
+#[must_use]
+pub fn check_all(nuclides: &[Nuclide]) -> Vec<Vec<Inconsistency>> {
+    check_all_with(nuclides, &Constants::AME2020)
+}
+
+fn check_all_with(nuclides: &[Nuclide], constants: &Constants) -> Vec<Vec<Inconsistency>> {
+    let by_zn = nuclides
+        .iter()
+        .map(|nuc| ((nuc.z, nuc.n), nuc))
+        .collect::<HashMap<_, _>>();
+
+    nuclides
+        .iter()
+        .map(|nuclide| {
+            let mut inconsistencies = check_with(nuclide, constants);
+
+            let daughter = nuclide
+                .n
+                .checked_sub(1)
+                .and_then(|n| by_zn.get(&(nuclide.z + 1, n)));
+            let expected = daughter.map(|daughter| Value {
+                mean: nuclide.mass_excess.mean - daughter.mass_excess.mean,
+                uncertainty: nuclide
+                    .mass_excess
+                    .uncertainty
+                    .hypot(daughter.mass_excess.uncertainty),
+                is_estimated: nuclide.mass_excess.is_estimated || daughter.mass_excess.is_estimated,
+            });
+            let consistent = match (&expected, &nuclide.beta_decay_energy) {
+                (Some(expected), Some(actual)) => agrees(expected, actual),
+                // without the neighbor, the value can't be checked
+                (None, _) => true,
+                (Some(_), None) => false,
+            };
+            if !consistent {
+                inconsistencies.push(Inconsistency::BetaDecayEnergy {
+                    expected,
+                    actual: nuclide.beta_decay_energy.clone(),
+                });
+            }
+
+            inconsistencies
+        })
+        .collect()
+}
+
+/// The problems found in a [`Dataset`][crate::dataset::Dataset], from
+/// [`Dataset::validate`][crate::dataset::Dataset::validate].
+///
+/// Nuclides are identified by (Z, N).
+#[derive(Clone, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub struct Report {
+    /// The inconsistencies of each nuclide that has any, as from [`check_all`]
+    pub inconsistencies: Vec<((u32, u32), Vec<Inconsistency>)>,
+    /// The nuclides with a beta decay energy, but without the neighbor (Z+1, N-1) to check it
+    pub missing_neighbors: Vec<(u32, u32)>,
+    /// The duplicates that were kept out of the dataset, as [`AmeError::Duplicate`]
+    pub duplicates: Vec<AmeError>,
+    /// The nuclides with a Z that isn't an element
+    pub out_of_range: Vec<(u32, u32)>,
+}
+
+impl Report {
+    /// Returns whether no problems were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inconsistencies.is_empty()
+            && self.missing_neighbors.is_empty()
+            && self.duplicates.is_empty()
+            && self.out_of_range.is_empty()
+    }
+
+    pub(crate) fn new(
+        nuclides: &[Nuclide],
+        duplicates: &[AmeError],
+        constants: &Constants,
+    ) -> Self {
+        let zn = |nuc: &Nuclide| (nuc.z, nuc.n);
+        let mut report = Self {
+            duplicates: duplicates.to_vec(),
+            ..Self::default()
+        };
+        for (nuclide, inconsistencies) in nuclides.iter().zip(check_all_with(nuclides, constants)) {
+            if !inconsistencies.is_empty() {
+                report.inconsistencies.push((zn(nuclide), inconsistencies));
+            }
+            if element::symbol(nuclide.z).is_none() {
+                report.out_of_range.push(zn(nuclide));
+            }
+        }
+        let by_zn = nuclides.iter().map(zn).collect::<HashSet<_>>();
+        report.missing_neighbors = nuclides
+            .iter()
+            .filter(|nuc| nuc.beta_decay_energy.is_some())
+            .filter(|nuc| {
+                nuc.n
+                    .checked_sub(1)
+                    .is_none_or(|n| !by_zn.contains(&(nuc.z + 1, n)))
+            })
+            .map(zn)
+            .collect();
+        report
+    }
+}
+
+fn agrees(expected: &Value, actual: &Value) -> bool {
+    let tolerance = expected.uncertainty.max(actual.uncertainty) + ROUNDING;
+    (expected.mean - actual.mean).abs() <= tolerance
+}
@@ -0,0 +1,63 @@
+use arrayvec::ArrayString;
+use core::fmt;
+
+/// An anomaly in the data that doesn't prevent parsing.
+///
+/// Warnings are collected by the iterators, and are available from
+/// [`Iter::warnings`][crate::Iter::warnings] and the like.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Warning {
+    /// There is more than one line between the column titles and the body.
+    ///
+    /// `mass.mas20` only has a line of units, so an extra line may be data that is missing the
+    /// '0' that starts the body.
+    ExtraHeader { line: usize },
+    /// A line of the body is blank, and was skipped.
+    BlankLine { line: usize },
+    /// A new table starts on this line, with
+    /// [`ParseOptions::concatenated`][crate::ParseOptions::concatenated].
+    NewTable { line: usize },
+    /// The A column isn't N+Z, with [`Validation::Warn`].
+    ///
+    /// [`Validation::Warn`]: crate::options::Validation::Warn
+    MassNumber { line: usize, a: u32, expected: u32 },
+    /// The element symbol isn't that of Z, with [`Validation::Warn`].
+    ///
+    /// [`Validation::Warn`]: crate::options::Validation::Warn
+    Element {
+        line: usize,
+        element: ArrayString<3>,
+        z: u32,
+    },
+}
+
+impl Warning {
+    /// Returns the number of the line the warning is on.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        match self {
+            Self::ExtraHeader { line }
+            | Self::BlankLine { line }
+            | Self::NewTable { line }
+            | Self::MassNumber { line, .. }
+            | Self::Element { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExtraHeader { line } => write!(f, "extra header line, on line {line}"),
+            Self::BlankLine { line } => write!(f, "blank line in the body, on line {line}"),
+            Self::NewTable { line } => write!(f, "new table, on line {line}"),
+            Self::MassNumber { line, a, expected } => {
+                write!(f, "mass number {a} is not N+Z ({expected}), on line {line}")
+            }
+            Self::Element { line, element, z } => {
+                write!(f, "element {element} does not have Z = {z}, on line {line}")
+            }
+        }
+    }
+}
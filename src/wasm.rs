@@ -0,0 +1,219 @@
+//! Loading data in the browser, and bindings for JavaScript.
+//!
+//! The data is read into memory with [`fetch`] or from a [`Uint8Array`], and then parsed with the
+//! same [`Iter`] used everywhere else.
+//!
+//! For JavaScript, [`parse`] and [`Table`] are exported. The module is built, and its bindings are
+//! generated with [wasm-bindgen](https://crates.io/crates/wasm-bindgen-cli), with
+//!
+//! ```sh
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/ame2020.wasm
+//! ```
+//!
+//! ```js
+//! import init, { parse } from "./pkg/ame2020.js";
+//!
+//! await init();
+//! const table = parse(await (await fetch("mass.mas20")).text());
+//! const fe56 = table.getBySymbol("Fe", 56);
+//! console.log(fe56.massExcess.mean, fe56.massExcess.uncertainty);
+//! ```
+use crate::{
+    dataset::{Dataset, Duplicates},
+    element, Iter, Nuclide,
+};
+use js_sys::Uint8Array;
+use std::io::Cursor;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsError, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+impl Iter<Cursor<Vec<u8>>> {
+    /// Creates a new `Iter` from the contents of `array`.
+    ///
+    /// The contents are copied out of the JavaScript heap.
+    #[must_use]
+    pub fn from_uint8_array(array: &Uint8Array) -> Self {
+        Self::new(Cursor::new(array.to_vec()))
+    }
+}
+
+/// Fetches the data at `url` with the browser's `fetch()`, and creates an `Iter` over it.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+/// use ame2020::{wasm::fetch, Nuclide};
+///
+/// let iter = fetch("mass.mas20").await.map_err(|_| "fetch failed")?;
+/// let data: Vec<Nuclide> = iter.collect::<Result<_, _>>()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// If there is no `window`, the request fails, or the response is not successful, the JavaScript
+/// error is returned.
+pub async fn fetch(url: &str) -> Result<Iter<Cursor<Vec<u8>>>, JsValue> {
+    let window = web_sys::window().ok_or("no window")?;
+    let response: Response = JsFuture::from(window.fetch_with_str(url))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(format!("HTTP status {}", response.status()).into());
+    }
+    let buffer = JsFuture::from(response.array_buffer()?).await?;
+    Ok(Iter::from_uint8_array(&Uint8Array::new(&buffer)))
+}
+
+/// Parses the text of a `mass.mas20` file into a [`Table`].
+///
+/// # Errors
+///
+/// If parsing fails, or a nuclide appears more than once, the error is returned.
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<Table, JsError> {
+    Dataset::from_slice(text.as_bytes(), Duplicates::Error)
+        .map(Table)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// A [`Dataset`], for JavaScript.
+#[wasm_bindgen]
+pub struct Table(Dataset);
+
+#[wasm_bindgen]
+impl Table {
+    /// Parses the contents of `array`.
+    ///
+    /// # Errors
+    ///
+    /// If parsing fails, or a nuclide appears more than once, the error is returned.
+    #[wasm_bindgen(js_name = fromUint8Array)]
+    pub fn from_uint8_array(array: &Uint8Array) -> Result<Table, JsError> {
+        Dataset::from_slice(&array.to_vec(), Duplicates::Error)
+            .map(Table)
+            .map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    /// Returns the nuclide with `z` protons and `n` neutrons.
+    #[must_use]
+    pub fn get(&self, z: u32, n: u32) -> Option<JsNuclide> {
+        self.0.get(z, n).cloned().map(JsNuclide)
+    }
+
+    /// Returns the nuclide of the element `symbol`, with mass number `a`.
+    #[must_use]
+    #[wasm_bindgen(js_name = getBySymbol)]
+    pub fn get_by_symbol(&self, symbol: &str, a: u32) -> Option<JsNuclide> {
+        let z = element::z(symbol)?;
+        self.get(z, a.checked_sub(z)?)
+    }
+
+    /// Returns the number of nuclides.
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A [`Nuclide`], for JavaScript.
+#[wasm_bindgen(js_name = Nuclide)]
+pub struct JsNuclide(Nuclide);
+
+#[wasm_bindgen(js_class = Nuclide)]
+impl JsNuclide {
+    /// The number of neutrons
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn n(&self) -> u32 {
+        self.0.n
+    }
+
+    /// The number of protons
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn z(&self) -> u32 {
+        self.0.z
+    }
+
+    /// The mass number
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> u32 {
+        self.0.a()
+    }
+
+    /// The element symbol
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn element(&self) -> String {
+        self.0.element.to_string()
+    }
+
+    /// The origin of the data, if any
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn origin(&self) -> Option<String> {
+        self.0.origin.map(|origin| origin.to_string())
+    }
+
+    /// The mass excess, in keV
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = massExcess)]
+    pub fn mass_excess(&self) -> JsValue {
+        value(&self.0.mass_excess)
+    }
+
+    /// The binding energy per nucleon, in keV, if any
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = bindingEnergyPerA)]
+    pub fn binding_energy_per_a(&self) -> JsValue {
+        self.0
+            .binding_energy_per_a
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, value)
+    }
+
+    /// The label of the decay, if any
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = decayMode)]
+    pub fn decay_mode(&self) -> Option<String> {
+        self.0.decay_mode.map(|mode| mode.label().to_string())
+    }
+
+    /// The beta decay energy, in keV, if any
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = betaDecayEnergy)]
+    pub fn beta_decay_energy(&self) -> JsValue {
+        self.0
+            .beta_decay_energy
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, value)
+    }
+
+    /// The atomic mass, in atomic mass units
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = atomicMass)]
+    pub fn atomic_mass(&self) -> JsValue {
+        value(&self.0.atomic_mass)
+    }
+}
+
+// a plain object, `{ mean, uncertainty, isEstimated }`
+fn value(value: &crate::Value) -> JsValue {
+    let object = js_sys::Object::new();
+    for (key, val) in [
+        ("mean", JsValue::from(value.mean)),
+        ("uncertainty", JsValue::from(value.uncertainty)),
+        ("isEstimated", JsValue::from(value.is_estimated)),
+    ] {
+        // setting a property of a new plain object can't fail
+        let _ = js_sys::Reflect::set(&object, &key.into(), &val);
+    }
+    object.into()
+}